@@ -4,16 +4,25 @@
 //! Enums like consonants, vowels, etc. are only related to the MLCTS and might
 //! not be able to map one-to-one with the Myanmar alphabets.
 
+use std::fmt;
+
+mod packed;
+
 /// The starting offset value to make providing emum values easier.
 /// If we want to use 0x1000 as the value for 'k', we can just
 /// set this value to 0x1000 and then all the following values will be
 /// automatically calculated.
 pub const BASIC_CONSONANT_START_VALUE: u8 = 0x00;
 
+/// The version of the scheme of [`Syllable::audio_key`]. It is bumped
+/// whenever a key of any syllable changes, so that audio clips named after
+/// the keys can be renamed.
+pub const AUDIO_KEY_VERSION: u32 = 1;
+
 /// Represents a basic consonant letter in the Myanmar script.
 #[repr(u8)]
 #[derive(
-  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash,
 )]
 pub enum BasicConsonant
 {
@@ -174,7 +183,7 @@ impl BasicConsonant
 /// Represents medial diacritics in the Myanmar script.
 #[repr(u8)]
 #[derive(
-  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash,
 )]
 pub enum MedialDiacritic
 {
@@ -264,7 +273,7 @@ impl MedialDiacritic
 /// This can be a basic consonant or a basic consonant followed by one or more
 /// medial diacritics (three at most).
 #[derive(
-  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash,
 )]
 pub struct Consonant
 {
@@ -287,7 +296,10 @@ impl Consonant
   /// # Returns
   ///
   /// A new consonant with the given basic consonant and medial diacritic.
-  pub fn new(basic: BasicConsonant, medial: Option<MedialDiacritic>) -> Self
+  pub const fn new(
+    basic: BasicConsonant,
+    medial: Option<MedialDiacritic>,
+  ) -> Self
   {
     Self { basic, medial }
   }
@@ -302,7 +314,7 @@ impl Consonant
   /// # Returns
   ///
   /// A new consonant with the given basic consonant.
-  pub fn simple(basic: BasicConsonant) -> Self
+  pub const fn simple(basic: BasicConsonant) -> Self
   {
     Self::new(basic, None)
   }
@@ -318,7 +330,10 @@ impl Consonant
   /// # Returns
   ///
   /// A new consonant with the given basic consonant and medial diacritic.
-  pub fn with_medial(basic: BasicConsonant, medial: MedialDiacritic) -> Self
+  pub const fn with_medial(
+    basic: BasicConsonant,
+    medial: MedialDiacritic,
+  ) -> Self
   {
     Self::new(basic, Some(medial))
   }
@@ -330,22 +345,40 @@ impl Consonant
   /// The corresponding MLCTS string.
   pub fn to_mlcts(&self) -> String
   {
-    let result = self.basic.to_mlcts().to_string();
-    match self.medial
+    let mut result = String::new();
+    let _ = self.write_mlcts(&mut result);
+    result
+  }
+
+  /// Write the MLCTS string of the consonant without allocating.
+  ///
+  /// # Arguments
+  ///
+  /// * `w` - The writer to write to.
+  ///
+  /// # Returns
+  ///
+  /// The result of writing.
+  pub fn write_mlcts<W: fmt::Write>(&self, w: &mut W) -> fmt::Result
+  {
+    let (prefix, suffix) = match self.medial
     {
-      Some(MedialDiacritic::Hrw) => format!("h{}rw", result),
-      Some(MedialDiacritic::Hyw) => format!("h{}yw", result),
-      Some(MedialDiacritic::Hw) => format!("h{}w", result),
-      Some(MedialDiacritic::Hr) => format!("h{}r", result),
-      Some(MedialDiacritic::Hy) => format!("h{}y", result),
-      Some(MedialDiacritic::H) => format!("h{}", result),
-      Some(MedialDiacritic::Rw) => format!("{}rw", result),
-      Some(MedialDiacritic::R) => format!("{}r", result),
-      Some(MedialDiacritic::Yw) => format!("{}yw", result),
-      Some(MedialDiacritic::Y) => format!("{}y", result),
-      Some(MedialDiacritic::W) => format!("{}w", result),
-      None => result,
-    }
+      Some(MedialDiacritic::Hrw) => ("h", "rw"),
+      Some(MedialDiacritic::Hyw) => ("h", "yw"),
+      Some(MedialDiacritic::Hw) => ("h", "w"),
+      Some(MedialDiacritic::Hr) => ("h", "r"),
+      Some(MedialDiacritic::Hy) => ("h", "y"),
+      Some(MedialDiacritic::H) => ("h", ""),
+      Some(MedialDiacritic::Rw) => ("", "rw"),
+      Some(MedialDiacritic::R) => ("", "r"),
+      Some(MedialDiacritic::Yw) => ("", "yw"),
+      Some(MedialDiacritic::Y) => ("", "y"),
+      Some(MedialDiacritic::W) => ("", "w"),
+      None => ("", ""),
+    };
+    w.write_str(prefix)?;
+    w.write_str(self.basic.to_mlcts())?;
+    w.write_str(suffix)
   }
 }
 
@@ -368,7 +401,7 @@ macro_rules! consonant {
 /// cannot have a tone mark.
 #[repr(u8)]
 #[derive(
-  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash,
 )]
 pub enum Tone
 {
@@ -399,7 +432,7 @@ impl Tone
 /// Virama can follow a consonant or vowel. But a vowel cannot follow a virama.
 #[repr(u8)]
 #[derive(
-  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash,
 )]
 pub enum Virama
 {
@@ -466,6 +499,30 @@ impl Virama
       Self::A => "a",
     }
   }
+
+  /// Check if the virama is a checked (stop) final, which is never followed
+  /// by a tone mark.
+  ///
+  /// # Returns
+  ///
+  /// `true` if the virama is က်, ဂ်, စ်, ဇ်, တ်, ထ်, ဒ်, ပ်, ဗ် or သ်, `false`
+  /// otherwise.
+  pub fn is_checked(&self) -> bool
+  {
+    matches!(
+      self,
+      Self::K
+        | Self::G
+        | Self::C
+        | Self::J
+        | Self::T
+        | Self::Ht
+        | Self::D
+        | Self::P
+        | Self::B
+        | Self::S
+    )
+  }
 }
 
 impl Into<BasicConsonant> for Virama
@@ -504,7 +561,7 @@ impl Into<BasicConsonant> for Virama
 /// same sound but different tone will be treated as the same vowels.
 #[repr(u8)]
 #[derive(
-  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash,
 )]
 pub enum BasicVowel
 {
@@ -556,7 +613,7 @@ impl BasicVowel
 /// Virama with consonantal finals (က, စ, ဋ, တ, ပ) cannot be
 /// followed by a tone mark since they already sounds Creaky tone.
 #[derive(
-  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq,
+  serde::Serialize, serde::Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash,
 )]
 pub struct Vowel
 {
@@ -581,7 +638,7 @@ impl Vowel
   /// # Returns
   ///
   /// A new vowel with the given basic vowel, virama and tone mark.
-  pub fn new(
+  pub const fn new(
     basic: BasicVowel,
     virama: Option<Virama>,
     tone: Option<Tone>,
@@ -604,7 +661,7 @@ impl Vowel
   /// # Returns
   ///
   /// A new simple vowel with the given basic vowel.
-  pub fn simple(basic: BasicVowel) -> Self
+  pub const fn simple(basic: BasicVowel) -> Self
   {
     Self::new(basic, None, None)
   }
@@ -620,7 +677,7 @@ impl Vowel
   /// # Returns
   ///
   /// A new vowel with the given basic vowel and tone mark.
-  pub fn with_tone(basic: BasicVowel, tone: Option<Tone>) -> Self
+  pub const fn with_tone(basic: BasicVowel, tone: Option<Tone>) -> Self
   {
     Self::new(basic, None, tone)
   }
@@ -636,7 +693,7 @@ impl Vowel
   /// # Returns
   ///
   /// A new vowel with the given basic vowel and virama.
-  pub fn with_virama(basic: BasicVowel, virama: Virama) -> Self
+  pub const fn with_virama(basic: BasicVowel, virama: Virama) -> Self
   {
     Self::new(basic, Some(virama), None)
   }
@@ -648,24 +705,32 @@ impl Vowel
   /// The corresponding MLCTS string.
   pub fn to_mlcts(&self) -> String
   {
-    let result = self.basic.to_mlcts().to_string();
-    let virama = if self.virama.is_some()
+    let mut result = String::new();
+    let _ = self.write_mlcts(&mut result);
+    result
+  }
+
+  /// Write the MLCTS string of the vowel without allocating.
+  ///
+  /// # Arguments
+  ///
+  /// * `w` - The writer to write to.
+  ///
+  /// # Returns
+  ///
+  /// The result of writing.
+  pub fn write_mlcts<W: fmt::Write>(&self, w: &mut W) -> fmt::Result
+  {
+    w.write_str(self.basic.to_mlcts())?;
+    if let Some(virama) = self.virama
     {
-      self.virama.unwrap().to_mlcts().to_string()
+      w.write_str(virama.to_mlcts())?;
     }
-    else
+    if let Some(tone) = self.tone
     {
-      "".to_string()
-    };
-    let tone = if self.tone.is_some()
-    {
-      self.tone.unwrap().to_mlcts().to_string()
+      w.write_str(tone.to_mlcts())?;
     }
-    else
-    {
-      "".to_string()
-    };
-    format!("{}{}{}", result, virama, tone)
+    Ok(())
   }
 }
 
@@ -700,7 +765,9 @@ macro_rules! vowel {
 /// A syllable can have at most one consonant part and one vowel part.
 /// Syllable will always contains both consonant and vowel parts since 'a' can
 /// be both a consonant and a vowel.
-#[derive(serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq)]
+#[derive(
+  serde::Serialize, serde::Deserialize, Debug, Clone, PartialEq, Eq, Hash,
+)]
 pub struct Syllable
 {
   /// The consonant part.
@@ -747,9 +814,259 @@ impl Syllable
   /// # Returns
   ///
   /// A new syllable with just the vowel part.
-  pub fn simple(vowel: Vowel) -> Self
+  pub const fn simple(vowel: Vowel) -> Self
+  {
+    Self::unstacked(consonant!(A), vowel)
+  }
+
+  /// Creates a new syllable with the given consonant and vowel, without a
+  /// stacked syllable. Unlike [`Syllable::new`], this can be used in `const`
+  /// contexts to build static syllable tables.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_core::{consonant, syllable, vowel, Syllable};
+  ///
+  /// const SYLLABLES: [Syllable; 2] = [
+  ///   syllable!(consonant!(M), vowel!(A, Ng)),
+  ///   syllable!(consonant!(L), vowel!(A)),
+  /// ];
+  ///
+  /// assert_eq!(SYLLABLES[0].to_mlcts(), "mang");
+  /// assert_eq!(SYLLABLES[1].to_mlcts(), "la");
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `consonant` - The consonant part.
+  /// * `vowel` - The vowel part.
+  ///
+  /// # Returns
+  ///
+  /// A new syllable with the given consonant and vowel.
+  pub const fn unstacked(consonant: Consonant, vowel: Vowel) -> Self
+  {
+    Self {
+      consonant,
+      vowel,
+      stacked: None,
+    }
+  }
+
+  /// Get the rime (the vowel, the final and the tone) which ends the
+  /// syllable. For a syllable with stacked syllables, this is the rime of
+  /// the last stacked syllable, e.g. `a.` of မင်္ဂ (mangga.).
+  ///
+  /// # Returns
+  ///
+  /// The vowel part of the last stacked syllable.
+  pub fn rime(&self) -> Vowel
+  {
+    let mut syllable = self;
+    while let Some(stacked) = &syllable.stacked
+    {
+      syllable = stacked;
+    }
+    syllable.vowel
+  }
+
+  /// Get the syllable without tone marks, as a key to group syllables which
+  /// only differ in their tones.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_core::{consonant, syllable, vowel};
+  ///
+  /// let syllable = syllable!(consonant!(K), vowel!(A, N; High));
+  /// assert_eq!(syllable.tone_key(), syllable!(consonant!(K), vowel!(A, N)));
+  /// ```
+  ///
+  /// # Returns
+  ///
+  /// The syllable and its stacked syllables without tone marks.
+  pub fn tone_key(&self) -> Syllable
   {
-    Self::new(consonant!(A), vowel, None)
+    self.map_parts(|consonant, vowel| {
+      (consonant, Vowel::new(vowel.basic, vowel.virama, None))
+    })
+  }
+
+  /// Check if two syllables are the same apart from their tones.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_core::{consonant, syllable, vowel};
+  ///
+  /// let kan = syllable!(consonant!(K), vowel!(A, N));
+  /// assert!(kan.eq_ignoring_tone(&syllable!(consonant!(K), vowel!(A, N; High))));
+  /// assert!(!kan.eq_ignoring_tone(&syllable!(consonant!(K), vowel!(A, M))));
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The syllable to compare with.
+  ///
+  /// # Returns
+  ///
+  /// `true` if the syllables only differ in their tones, `false` otherwise.
+  pub fn eq_ignoring_tone(&self, other: &Self) -> bool
+  {
+    self.tone_key() == other.tone_key()
+  }
+
+  /// Get the syllable with homophone letters folded into one, as a key to
+  /// group syllables which sound the same. The voiced aspirated consonants
+  /// (ဃ, ဈ, ဎ, ဓ, ဘ) are folded into the voiced ones and the medial ြ into ျ.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_core::{consonant, syllable, vowel};
+  ///
+  /// // ဘ and ဗ, ကြ and ကျ
+  /// let bha = syllable!(consonant!(Bh), vowel!(A));
+  /// assert_eq!(bha.phonetic_key(), syllable!(consonant!(B), vowel!(A)));
+  /// let kra = syllable!(consonant!(K, R), vowel!(A));
+  /// assert_eq!(kra.phonetic_key(), syllable!(consonant!(K, Y), vowel!(A)));
+  /// ```
+  ///
+  /// # Returns
+  ///
+  /// The syllable and its stacked syllables with homophone letters folded.
+  pub fn phonetic_key(&self) -> Syllable
+  {
+    self.map_parts(|consonant, vowel| {
+      let basic = match consonant.basic
+      {
+        BasicConsonant::Gh => BasicConsonant::G,
+        BasicConsonant::Jh => BasicConsonant::J,
+        BasicConsonant::Dh => BasicConsonant::D,
+        BasicConsonant::Bh => BasicConsonant::B,
+        basic => basic,
+      };
+      let medial = consonant.medial.map(|medial| match medial
+      {
+        MedialDiacritic::R => MedialDiacritic::Y,
+        MedialDiacritic::Rw => MedialDiacritic::Yw,
+        MedialDiacritic::Hr => MedialDiacritic::Hy,
+        MedialDiacritic::Hrw => MedialDiacritic::Hyw,
+        medial => medial,
+      });
+      (Consonant::new(basic, medial), vowel)
+    })
+  }
+
+  /// Check if two syllables sound the same, i.e. they only differ in
+  /// homophone letters (see [`Syllable::phonetic_key`]).
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_core::{consonant, syllable, vowel};
+  ///
+  /// let kywan = syllable!(consonant!(K, Yw), vowel!(A, N));
+  /// assert!(kywan.eq_phonetic(&syllable!(consonant!(K, Rw), vowel!(A, N))));
+  /// assert!(!kywan.eq_phonetic(&syllable!(consonant!(Hk, Yw), vowel!(A, N))));
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `other` - The syllable to compare with.
+  ///
+  /// # Returns
+  ///
+  /// `true` if the syllables sound the same, `false` otherwise.
+  pub fn eq_phonetic(&self, other: &Self) -> bool
+  {
+    self.phonetic_key() == other.phonetic_key()
+  }
+
+  /// Rebuild the syllable and its stacked syllables with changed parts.
+  ///
+  /// # Arguments
+  ///
+  /// * `f` - Maps the consonant and the vowel of every syllable.
+  ///
+  /// # Returns
+  ///
+  /// The rebuilt syllable.
+  fn map_parts(
+    &self,
+    f: impl Fn(Consonant, Vowel) -> (Consonant, Vowel) + Copy,
+  ) -> Syllable
+  {
+    let (consonant, vowel) = f(self.consonant, self.vowel);
+    Syllable::new(
+      consonant,
+      vowel,
+      self.stacked.as_ref().map(|stacked| stacked.map_parts(f)),
+    )
+  }
+
+  /// Get a stable, filesystem-safe key of the syllable, e.g. to name the
+  /// audio clip of its pronunciation.
+  ///
+  /// The key is the MLCTS of the syllable without tone marks, followed by
+  /// the tone as a digit: `1` for the low tone (no mark), `2` for the high
+  /// tone (`:`), `3` for the creaky tone (`.`) and `4` for the checked tone
+  /// of a syllable ending with a stop (e.g. `kyak4`). A stacked syllable
+  /// adds its own key, e.g. `mang1ga3` for မင်္ဂ. Keys only contain ASCII
+  /// lowercase letters and digits. The scheme is versioned with
+  /// [`AUDIO_KEY_VERSION`].
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_core::{consonant, syllable, vowel};
+  ///
+  /// assert_eq!(syllable!(consonant!(K, Y), vowel!(A)).audio_key(), "kya1");
+  /// let kaung = syllable!(consonant!(K), vowel!(Au, Ng; High));
+  /// assert_eq!(kaung.audio_key(), "kaung2");
+  /// let ka = syllable!(consonant!(K), vowel!(A; Creaky));
+  /// assert_eq!(ka.audio_key(), "ka3");
+  /// let kyak = syllable!(consonant!(K, Y), vowel!(A, K));
+  /// assert_eq!(kyak.audio_key(), "kyak4");
+  /// let mangga = syllable!(
+  ///   consonant!(M),
+  ///   vowel!(A, Ng),
+  ///   syllable!(consonant!(G), vowel!(A; Creaky))
+  /// );
+  /// assert_eq!(mangga.audio_key(), "mang1ga3");
+  /// ```
+  ///
+  /// # Returns
+  ///
+  /// The audio key of the syllable.
+  pub fn audio_key(&self) -> String
+  {
+    let mut key = String::new();
+    let mut syllable = Some(self);
+    while let Some(current) = syllable
+    {
+      if current.consonant.basic != BasicConsonant::A
+      {
+        let _ = current.consonant.write_mlcts(&mut key);
+      }
+      let vowel = current.vowel;
+      key.push_str(vowel.basic.to_mlcts());
+      if let Some(virama) = vowel.virama
+      {
+        key.push_str(virama.to_mlcts());
+      }
+      let checked = vowel.virama.is_some_and(|v| v.is_checked());
+      key.push(match vowel.tone
+      {
+        Some(Tone::High) => '2',
+        Some(Tone::Creaky) => '3',
+        None if checked => '4',
+        None => '1',
+      });
+      syllable = current.stacked.as_deref();
+    }
+    key
   }
 
   /// Convert Syllable to MLCTS string
@@ -759,24 +1076,44 @@ impl Syllable
   /// The corresponding MLCTS string.
   pub fn to_mlcts(&self) -> String
   {
-    let consonant = if self.consonant.basic == BasicConsonant::A
+    let mut result = String::new();
+    let _ = self.write_mlcts(&mut result);
+    result
+  }
+
+  /// Write the MLCTS string of the syllable without allocating.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_core::{consonant, syllable, vowel};
+  ///
+  /// let mut output = String::from("ta ");
+  /// let syllable = syllable!(consonant!(K, Y), vowel!(Au, Ng; High));
+  /// syllable.write_mlcts(&mut output).unwrap();
+  ///
+  /// assert_eq!(output, "ta kyaung:");
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `w` - The writer to write to.
+  ///
+  /// # Returns
+  ///
+  /// The result of writing.
+  pub fn write_mlcts<W: fmt::Write>(&self, w: &mut W) -> fmt::Result
+  {
+    if self.consonant.basic != BasicConsonant::A
     {
-      "".to_string()
+      self.consonant.write_mlcts(w)?;
     }
-    else
+    self.vowel.write_mlcts(w)?;
+    match &self.stacked
     {
-      self.consonant.to_mlcts()
-    };
-    let vowel = self.vowel.to_mlcts();
-    let stacked = if self.stacked.is_some()
-    {
-      self.stacked.as_ref().unwrap().to_mlcts()
+      Some(stacked) => stacked.write_mlcts(w),
+      None => Ok(()),
     }
-    else
-    {
-      "".to_string()
-    };
-    format!("{}{}{}", consonant, vowel, stacked)
   }
 }
 
@@ -787,7 +1124,7 @@ macro_rules! syllable {
     $crate::Syllable::simple($vowel)
   };
   ($consonant:expr, $vowel:expr) => {
-    $crate::Syllable::new($consonant, $vowel, None)
+    $crate::Syllable::unstacked($consonant, $vowel)
   };
   ($consonant:expr, $vowel:expr, $stacked:expr) => {
     $crate::Syllable::new($consonant, $vowel, Some($stacked))
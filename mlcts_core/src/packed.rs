@@ -0,0 +1,376 @@
+//! Compact integer encoding of syllables.
+//!
+//! A syllable without a stacked syllable is packed into a `u32`:
+//!
+//! | bits    | field                                     |
+//! |---------|-------------------------------------------|
+//! | 0 - 7   | basic consonant (`BasicConsonant as u8`)  |
+//! | 8 - 11  | medial diacritic (0 = none)               |
+//! | 12 - 15 | basic vowel                               |
+//! | 16 - 20 | virama (0 = none)                         |
+//! | 21 - 22 | tone mark (0 = none)                      |
+//!
+//! The remaining bits are zero. The `u64` variant stores the syllable in the
+//! lower half and its stacked syllable, if any, in the upper half. Bit 31
+//! tells if the upper half is used.
+//!
+//! Decoding rejects combinations the syllable parser never produces: a tone
+//! mark after a checked final (see [`Virama::is_checked`]), and a syllable
+//! with a stacked syllable but without a final or with a tone mark.
+
+use crate::*;
+
+const MEDIAL_SHIFT: u32 = 8;
+const VOWEL_SHIFT: u32 = 12;
+const VIRAMA_SHIFT: u32 = 16;
+const TONE_SHIFT: u32 = 21;
+/// Bits used by a single packed syllable.
+const SYLLABLE_MASK: u32 = (1 << 23) - 1;
+/// Set in the lower half of a `u64` packed syllable with a stacked syllable.
+const STACKED_FLAG: u32 = 1 << 31;
+
+const BASIC_CONSONANTS: [BasicConsonant; 27] = [
+  BasicConsonant::K,
+  BasicConsonant::Hk,
+  BasicConsonant::G,
+  BasicConsonant::Gh,
+  BasicConsonant::Ng,
+  BasicConsonant::C,
+  BasicConsonant::Hc,
+  BasicConsonant::J,
+  BasicConsonant::Jh,
+  BasicConsonant::Ny,
+  BasicConsonant::T,
+  BasicConsonant::Ht,
+  BasicConsonant::D,
+  BasicConsonant::Dh,
+  BasicConsonant::N,
+  BasicConsonant::P,
+  BasicConsonant::Hp,
+  BasicConsonant::B,
+  BasicConsonant::Bh,
+  BasicConsonant::M,
+  BasicConsonant::Y,
+  BasicConsonant::R,
+  BasicConsonant::L,
+  BasicConsonant::W,
+  BasicConsonant::S,
+  BasicConsonant::H,
+  BasicConsonant::A,
+];
+
+const MEDIAL_DIACRITICS: [MedialDiacritic; 11] = [
+  MedialDiacritic::Y,
+  MedialDiacritic::R,
+  MedialDiacritic::W,
+  MedialDiacritic::H,
+  MedialDiacritic::Yw,
+  MedialDiacritic::Rw,
+  MedialDiacritic::Hy,
+  MedialDiacritic::Hr,
+  MedialDiacritic::Hw,
+  MedialDiacritic::Hyw,
+  MedialDiacritic::Hrw,
+];
+
+const BASIC_VOWELS: [BasicVowel; 8] = [
+  BasicVowel::A,
+  BasicVowel::I,
+  BasicVowel::U,
+  BasicVowel::E,
+  BasicVowel::Ei,
+  BasicVowel::Ai,
+  BasicVowel::Au,
+  BasicVowel::Ui,
+];
+
+const VIRAMAS: [Virama; 16] = [
+  Virama::K,
+  Virama::G,
+  Virama::Ng,
+  Virama::C,
+  Virama::J,
+  Virama::Ny,
+  Virama::T,
+  Virama::Ht,
+  Virama::D,
+  Virama::N,
+  Virama::P,
+  Virama::B,
+  Virama::M,
+  Virama::S,
+  Virama::L,
+  Virama::A,
+];
+
+const TONES: [Tone; 2] = [Tone::High, Tone::Creaky];
+
+impl Syllable
+{
+  /// Pack a syllable without a stacked syllable into a `u32`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_core::{consonant, syllable, vowel, Syllable};
+  ///
+  /// let syllable = syllable!(consonant!(K, Y), vowel!(Au, Ng; High));
+  /// let packed = syllable.to_packed().unwrap();
+  ///
+  /// assert_eq!(Syllable::from_packed(packed), Some(syllable));
+  /// assert_eq!(Syllable::from_packed(u32::MAX), None);
+  /// ```
+  ///
+  /// # Returns
+  ///
+  /// The packed syllable or `None` if the syllable has a stacked syllable.
+  pub fn to_packed(&self) -> Option<u32>
+  {
+    if self.stacked.is_some()
+    {
+      return None;
+    }
+
+    let medial = self.consonant.medial.map_or(0, |m| m as u32 + 1);
+    let virama = self.vowel.virama.map_or(0, |v| v as u32 + 1);
+    let tone = self.vowel.tone.map_or(0, |t| t as u32 + 1);
+
+    Some(
+      self.consonant.basic as u32
+        | medial << MEDIAL_SHIFT
+        | (self.vowel.basic as u32) << VOWEL_SHIFT
+        | virama << VIRAMA_SHIFT
+        | tone << TONE_SHIFT,
+    )
+  }
+
+  /// Unpack a syllable packed with [`Syllable::to_packed`].
+  ///
+  /// # Arguments
+  ///
+  /// * `packed` - The packed syllable.
+  ///
+  /// # Returns
+  ///
+  /// The syllable or `None` if the value is not a valid packed syllable.
+  pub fn from_packed(packed: u32) -> Option<Self>
+  {
+    if packed & !SYLLABLE_MASK != 0
+    {
+      return None;
+    }
+
+    let field = |shift: u32, bits: u32| (packed >> shift) & ((1 << bits) - 1);
+    let optional = |value: u32| value.checked_sub(1).map(|v| v as usize);
+
+    let basic = packed & 0xFF;
+    let basic = *BASIC_CONSONANTS.iter().find(|c| **c as u32 == basic)?;
+    let medial = match optional(field(MEDIAL_SHIFT, 4))
+    {
+      Some(i) => Some(*MEDIAL_DIACRITICS.get(i)?),
+      None => None,
+    };
+    let vowel = *BASIC_VOWELS.get(field(VOWEL_SHIFT, 4) as usize)?;
+    let virama = match optional(field(VIRAMA_SHIFT, 5))
+    {
+      Some(i) => Some(*VIRAMAS.get(i)?),
+      None => None,
+    };
+    let tone = match optional(field(TONE_SHIFT, 2))
+    {
+      Some(i) => Some(*TONES.get(i)?),
+      None => None,
+    };
+
+    let vowel = Vowel::new(vowel, virama, tone);
+    if vowel.virama.is_some_and(|v| v.is_checked()) && vowel.tone.is_some()
+    {
+      return None;
+    }
+
+    Some(Self::unstacked(Consonant::new(basic, medial), vowel))
+  }
+
+  /// Pack a syllable with at most one level of stacked syllable into a
+  /// `u64`.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_core::{consonant, syllable, vowel, Syllable};
+  ///
+  /// // မင်္ဂ
+  /// let stacked = syllable!(consonant!(G), vowel!(A; Creaky));
+  /// let syllable = syllable!(consonant!(M), vowel!(A, Ng), stacked);
+  /// let packed = syllable.to_packed_u64().unwrap();
+  ///
+  /// assert_eq!(Syllable::from_packed_u64(packed), Some(syllable));
+  /// ```
+  ///
+  /// # Returns
+  ///
+  /// The packed syllable or `None` if the stacked syllable has a stacked
+  /// syllable too.
+  pub fn to_packed_u64(&self) -> Option<u64>
+  {
+    let top = Self::unstacked(self.consonant, self.vowel).to_packed()?;
+    match &self.stacked
+    {
+      Some(stacked) =>
+      {
+        let bottom = stacked.to_packed()?;
+        Some((bottom as u64) << 32 | (top | STACKED_FLAG) as u64)
+      }
+      None => Some(top as u64),
+    }
+  }
+
+  /// Unpack a syllable packed with [`Syllable::to_packed_u64`].
+  ///
+  /// # Arguments
+  ///
+  /// * `packed` - The packed syllable.
+  ///
+  /// # Returns
+  ///
+  /// The syllable or `None` if the value is not a valid packed syllable.
+  pub fn from_packed_u64(packed: u64) -> Option<Self>
+  {
+    let top = packed as u32;
+    let bottom = (packed >> 32) as u32;
+
+    let mut syllable = Self::from_packed(top & !STACKED_FLAG)?;
+    if top & STACKED_FLAG != 0
+    {
+      // the final of the upper syllable is the onset of the stacked one
+      if syllable.vowel.virama.is_none() || syllable.vowel.tone.is_some()
+      {
+        return None;
+      }
+      syllable.stacked = Some(Box::new(Self::from_packed(bottom)?));
+    }
+    else if bottom != 0
+    {
+      return None;
+    }
+
+    Some(syllable)
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use crate::*;
+
+  #[test]
+  fn test_round_trip()
+  {
+    let syllables = [
+      // ကျောင်း, မြို့, ပိတ်, ရှိ, အ
+      syllable!(consonant!(K, Y), vowel!(Au, Ng; High)),
+      syllable!(consonant!(M, R), vowel!(Ui; Creaky)),
+      syllable!(consonant!(P), vowel!(I, T)),
+      syllable!(consonant!(R, H), vowel!(I; Creaky)),
+      syllable!(consonant!(A), vowel!(A)),
+      // ကွှေ့
+      syllable!(consonant!(K, Hw), vowel!(E; Creaky)),
+    ];
+    for syllable in syllables
+    {
+      let packed = syllable.to_packed().unwrap();
+      assert_eq!(Syllable::from_packed(packed), Some(syllable.clone()));
+      let packed = syllable.to_packed_u64().unwrap();
+      assert_eq!(packed >> 32, 0);
+      assert_eq!(Syllable::from_packed_u64(packed), Some(syllable));
+    }
+
+    // သင်္ချိုင်း, ဗုဒ္ဓ
+    let stacked = [
+      syllable!(
+        consonant!(S),
+        vowel!(A, Ng),
+        syllable!(consonant!(Hc, Y), vowel!(Ui, Ng; High))
+      ),
+      syllable!(
+        consonant!(B),
+        vowel!(U, D),
+        syllable!(consonant!(Dh), vowel!(A))
+      ),
+    ];
+    for syllable in stacked
+    {
+      assert_eq!(syllable.to_packed(), None);
+      let packed = syllable.to_packed_u64().unwrap();
+      assert_eq!(Syllable::from_packed_u64(packed), Some(syllable));
+    }
+  }
+
+  #[test]
+  fn test_invalid()
+  {
+    let pack = |syllable: Syllable| {
+      let stacked = syllable.stacked.as_deref().map(|s| s.to_packed().unwrap());
+      let top = Syllable::unstacked(syllable.consonant, syllable.vowel)
+        .to_packed()
+        .unwrap();
+      match stacked
+      {
+        Some(bottom) =>
+        {
+          (bottom as u64) << 32 | (top | super::STACKED_FLAG) as u64
+        }
+        None => top as u64,
+      }
+    };
+
+    // tone marks after checked finals, e.g. ကဒ့် and ကဂ်း
+    for virama in [
+      Virama::K,
+      Virama::G,
+      Virama::C,
+      Virama::T,
+      Virama::D,
+      Virama::P,
+    ]
+    {
+      for tone in [Tone::High, Tone::Creaky]
+      {
+        let vowel = Vowel::new(BasicVowel::A, Some(virama), Some(tone));
+        let packed = pack(syllable!(consonant!(K), vowel));
+        assert_eq!(Syllable::from_packed(packed as u32), None);
+        assert_eq!(Syllable::from_packed_u64(packed), None);
+      }
+    }
+
+    let invalid = [
+      // a stacked syllable after a syllable without a final or with a tone
+      pack(syllable!(
+        consonant!(K),
+        vowel!(A),
+        syllable!(consonant!(K), vowel!(A))
+      )),
+      pack(syllable!(
+        consonant!(M),
+        vowel!(A, Ng; High),
+        syllable!(consonant!(G), vowel!(A))
+      )),
+      // unknown fields
+      0xFF,
+      12 << super::MEDIAL_SHIFT,
+      8 << super::VOWEL_SHIFT,
+      17 << super::VIRAMA_SHIFT,
+      3 << super::TONE_SHIFT,
+      // bits outside of the fields
+      1 << 23,
+      // an upper half without the flag
+      1 << 32,
+      // a stacked flag in the upper half
+      (super::STACKED_FLAG as u64) << 32 | super::STACKED_FLAG as u64,
+    ];
+    for packed in invalid
+    {
+      assert_eq!(Syllable::from_packed_u64(packed), None, "{:#x}", packed);
+    }
+  }
+}
@@ -0,0 +1,332 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use dataset::load_g2p_mlcts_dict;
+use indicatif::{ProgressBar, ProgressStyle};
+use mlcts_generator::{mlcts_from_myanmar, mlcts_from_myanmar_with_options, ConvertOptions};
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::SeedableRng;
+
+mod dataset;
+
+/// CLI options
+#[derive(Parser)]
+struct ClapOptions
+{
+  /// Seed of the random choices of the variants
+  #[arg(short, long, default_value_t = 42)]
+  seed: u64,
+}
+
+/// Characters which are typed or read as each other.
+const CONFUSABLES: &[(char, char)] = &[
+  ('ဝ', '၀'),
+  ('ရ', '၇'),
+  ('ဉ', 'ဥ'),
+  ('ည', 'ဉ'),
+  ('ဒ', 'ဓ'),
+  ('ိ', 'ီ'),
+  ('ု', 'ူ'),
+  ('ျ', 'ြ'),
+  ('ံ', 'ိ'),
+];
+
+/// A noisy variant of a dictionary word and the clean word.
+#[derive(serde::Serialize)]
+struct Variant
+{
+  noisy: String,
+  clean: String,
+  kind: &'static str,
+}
+
+/// Makes a noisy variant of a word, `None` if the word has nothing to
+/// change.
+type MakeVariant = fn(&str, &mut StdRng) -> Option<String>;
+
+/// The kinds of noise and the functions making their variants.
+const KINDS: &[(&str, MakeVariant)] = &[
+  ("zawgyi", to_zawgyi),
+  ("reordered marks", reorder_marks),
+  ("confusable", substitute_confusable),
+  ("missing tone marks", drop_tone_marks),
+];
+
+fn main()
+{
+  let cli_options = ClapOptions::parse();
+  let mut rng = StdRng::seed_from_u64(cli_options.seed);
+
+  let dict = load_g2p_mlcts_dict();
+  let progress_bar = ProgressBar::new(dict.len() as u64);
+  progress_bar.set_style(
+    ProgressStyle::with_template("[*] augmenting {wide_bar} {pos}/{len}")
+      .unwrap(),
+  );
+
+  let output_path = asset_path("augmented.jsonl");
+  let mut writer = BufWriter::new(File::create(&output_path).unwrap());
+  let mut options = ConvertOptions::default();
+  options.autofix = true;
+  // variants and variants converted like the clean word, by kind
+  let mut counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+  for row in &dict
+  {
+    progress_bar.inc(1);
+    let clean = &row.myanmar_word;
+    let clean_mlcts = mlcts_from_myanmar(clean);
+    for (kind, make_variant) in KINDS
+    {
+      let Some(noisy) = make_variant(clean, &mut rng)
+      else
+      {
+        continue;
+      };
+      let count = counts.entry(kind).or_default();
+      count.0 += 1;
+      if mlcts_from_myanmar_with_options(&noisy, &options) == clean_mlcts
+      {
+        count.1 += 1;
+      }
+
+      let variant = Variant {
+        noisy,
+        clean: clean.clone(),
+        kind,
+      };
+      serde_json::to_writer(&mut writer, &variant).unwrap();
+      writer.write_all(b"\n").unwrap();
+    }
+  }
+  writer.flush().unwrap();
+  progress_bar.finish();
+
+  println!("[*] variants and variants converted like the clean word");
+  for (kind, (variants, recovered)) in counts
+  {
+    println!("  {}: {} ({} recovered)", kind, variants, recovered);
+  }
+  println!("[*] variants are written to {}", output_path.display());
+}
+
+/// Re-encode a word in Zawgyi. The vowel sign ေ and the medial ြ are moved
+/// in front of the consonant, and the medials and the asat are mapped to
+/// their Zawgyi code points.
+///
+/// # Arguments
+///
+/// * `word` - The clean word.
+/// * `_rng` - Unused, the whole word is re-encoded.
+///
+/// # Returns
+///
+/// The variant or `None` if the word is the same in Zawgyi or has stacked
+/// consonants, which Zawgyi writes with precomposed forms.
+fn to_zawgyi(word: &str, _rng: &mut StdRng) -> Option<String>
+{
+  if word.contains('\u{1039}')
+  {
+    return None;
+  }
+
+  let mut zawgyi = String::with_capacity(word.len());
+  let mut chars = word.chars().peekable();
+  while let Some(c) = chars.next()
+  {
+    if !matches!(c, 'က' ..= 'အ')
+    {
+      zawgyi.push(zawgyi_mark(c));
+      continue;
+    }
+
+    // the medials, vowel signs and tone marks of the consonant
+    let mut marks = Vec::new();
+    while let Some(&mark) = chars.peek()
+    {
+      if !is_mark(mark)
+      {
+        break;
+      }
+      marks.push(mark);
+      chars.next();
+    }
+    if marks.contains(&'ေ')
+    {
+      zawgyi.push('ေ');
+    }
+    if marks.contains(&'ြ')
+    {
+      zawgyi.push('\u{103B}');
+    }
+    zawgyi.push(c);
+    zawgyi.extend(
+      marks
+        .into_iter()
+        .filter(|&mark| mark != 'ေ' && mark != 'ြ')
+        .map(zawgyi_mark),
+    );
+  }
+
+  (zawgyi != word).then_some(zawgyi)
+}
+
+/// Map a character to its Zawgyi code point.
+///
+/// # Arguments
+///
+/// * `c` - The Unicode character.
+///
+/// # Returns
+///
+/// The Zawgyi character, the same character if it is the same in Zawgyi.
+fn zawgyi_mark(c: char) -> char
+{
+  match c
+  {
+    // the asat
+    '\u{103A}' => '\u{1039}',
+    // the medials ျ, ွ and ှ
+    '\u{103B}' => '\u{103A}',
+    '\u{103D}' => '\u{103C}',
+    '\u{103E}' => '\u{103D}',
+    _ => c,
+  }
+}
+
+/// Swap two different adjacent marks, e.g. a medial and a vowel sign.
+///
+/// # Arguments
+///
+/// * `word` - The clean word.
+/// * `rng` - The random number generator.
+///
+/// # Returns
+///
+/// The variant or `None` if the word has no adjacent marks.
+fn reorder_marks(word: &str, rng: &mut StdRng) -> Option<String>
+{
+  let mut chars = word.chars().collect::<Vec<_>>();
+  let pos = chars
+    .windows(2)
+    .enumerate()
+    .filter(|(_, pair)| is_mark(pair[0]) && is_mark(pair[1]))
+    .filter(|(_, pair)| pair[0] != pair[1])
+    .map(|(i, _)| i)
+    .choose(rng)?;
+  chars.swap(pos, pos + 1);
+  Some(chars.into_iter().collect())
+}
+
+/// Replace a character with a confusable one.
+///
+/// # Arguments
+///
+/// * `word` - The clean word.
+/// * `rng` - The random number generator.
+///
+/// # Returns
+///
+/// The variant or `None` if the word has no confusable characters.
+fn substitute_confusable(word: &str, rng: &mut StdRng) -> Option<String>
+{
+  let mut chars = word.chars().collect::<Vec<_>>();
+  let (pos, other) = chars
+    .iter()
+    .enumerate()
+    .flat_map(|(i, &c)| {
+      CONFUSABLES.iter().filter_map(move |&(a, b)| match c
+      {
+        _ if c == a => Some((i, b)),
+        _ if c == b => Some((i, a)),
+        _ => None,
+      })
+    })
+    .choose(rng)?;
+  chars[pos] = other;
+  Some(chars.into_iter().collect())
+}
+
+/// Remove the tone marks ့ and း.
+///
+/// # Arguments
+///
+/// * `word` - The clean word.
+/// * `_rng` - Unused, every tone mark is removed.
+///
+/// # Returns
+///
+/// The variant or `None` if the word has no tone marks.
+fn drop_tone_marks(word: &str, _rng: &mut StdRng) -> Option<String>
+{
+  let is_tone = |c| matches!(c, '\u{1037}' | '\u{1038}');
+  match word.contains(is_tone)
+  {
+    true => Some(word.replace(is_tone, "")),
+    false => None,
+  }
+}
+
+/// Check if a character is a dependent mark (a vowel sign, a medial, a tone
+/// mark, etc.).
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is a mark, `false` otherwise.
+fn is_mark(c: char) -> bool
+{
+  matches!(c, '\u{102B}' ..= '\u{103E}')
+}
+
+/// Get the path of a file in the assets directory.
+///
+/// # Arguments
+///
+/// * `name` - The file name.
+///
+/// # Returns
+///
+/// The path of the file.
+fn asset_path(name: &str) -> PathBuf
+{
+  Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("assets")
+    .join(name)
+}
+
+#[cfg(test)]
+mod tests
+{
+  use rand::rngs::StdRng;
+  use rand::SeedableRng;
+
+  use super::to_zawgyi;
+
+  #[test]
+  fn test_to_zawgyi()
+  {
+    let mut rng = StdRng::seed_from_u64(0);
+    let cases = [
+      ("ကျောင်း", Some("ေက\u{103A}ာင\u{1039}း")),
+      ("မြန်မာ", Some("\u{103B}မန\u{1039}မာ")),
+      ("ပြော", Some("ေ\u{103B}ပာ")),
+      ("ကွှေ", Some("ေက\u{103C}\u{103D}")),
+      ("မှာ", Some("မ\u{103D}ာ")),
+      ("ကော", Some("ေကာ")),
+      ("ပါ", None),
+      ("သင်္ချိုင်း", None),
+    ];
+    for (word, expected) in cases
+    {
+      assert_eq!(to_zawgyi(word, &mut rng).as_deref(), expected, "{}", word);
+    }
+  }
+}
@@ -0,0 +1,59 @@
+//! The G2P MLCTS dictionary generated by `mk-myg2p-mlcts`, the dataset most
+//! dev tools work on.
+
+use std::path::{Path, PathBuf};
+
+/// A row of the G2P MLCTS dictionary.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct MyG2pMlcTsRow
+{
+  /// The word in Myanmar script.
+  pub myanmar_word: String,
+  /// The romanization of the word by mya2rom.
+  pub mlcts_romanization: String,
+  /// The number of syllables of the word.
+  pub syllable_count: usize,
+  /// The syllables of the word separated by `|`.
+  pub myanmar_syllables: String,
+  /// The romanizations of the syllables separated by `|`.
+  pub mlcts_syllables: String,
+}
+
+/// Load the G2P MLCTS dictionary from the assets directory. Run
+/// `cargo make myg2p-mlcts` to generate it.
+///
+/// # Returns
+///
+/// A vector of rows from the G2P MLCTS dictionary.
+pub fn load_g2p_mlcts_dict() -> Vec<MyG2pMlcTsRow>
+{
+  load_dataset(&dict_path())
+}
+
+/// Load a version of the G2P MLCTS dictionary.
+///
+/// # Arguments
+///
+/// * `path` - The path of the dictionary.
+///
+/// # Returns
+///
+/// A vector of rows from the dictionary.
+pub fn load_dataset(path: &Path) -> Vec<MyG2pMlcTsRow>
+{
+  let mut rdr = csv::Reader::from_path(path).unwrap();
+  rdr.deserialize().map(|r| r.unwrap()).collect()
+}
+
+/// Get the path of the G2P MLCTS dictionary in the assets directory.
+///
+/// # Returns
+///
+/// The path of the dictionary.
+fn dict_path() -> PathBuf
+{
+  Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("assets")
+    .join("myg2p-dict-mlcts.csv")
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use clap::Parser;
+use dataset::{load_dataset, load_g2p_mlcts_dict, MyG2pMlcTsRow};
+
+mod dataset;
+
+/// CLI options
+#[derive(Parser)]
+struct ClapOptions
+{
+  /// The dataset before the regeneration
+  old: PathBuf,
+  /// The dataset after the regeneration, assets/myg2p-dict-mlcts.csv by
+  /// default
+  new: Option<PathBuf>,
+  /// Number of examples to print for each category
+  #[arg(short, long, default_value_t = 5)]
+  examples: usize,
+}
+
+fn main()
+{
+  let cli_options = ClapOptions::parse();
+  let old = by_word(load_dataset(&cli_options.old));
+  let new = by_word(match &cli_options.new
+  {
+    Some(path) => load_dataset(path),
+    None => load_g2p_mlcts_dict(),
+  });
+
+  let changes = compare(&old, &new);
+  let changed = changes.values().map(Vec::len).sum::<usize>();
+  println!(
+    "[*] {} changes from {} to {} entries",
+    changed,
+    old.len(),
+    new.len()
+  );
+
+  for (category, rows) in &changes
+  {
+    println!("\n{} ({})", category, rows.len());
+    for (before, after) in rows.iter().take(cli_options.examples)
+    {
+      match (before, after)
+      {
+        (Some(before), Some(after)) => println!(
+          "  {} {} -> {} ({} -> {})",
+          after.myanmar_word,
+          before.mlcts_romanization,
+          after.mlcts_romanization,
+          before.myanmar_syllables,
+          after.myanmar_syllables
+        ),
+        (None, Some(row)) | (Some(row), None) => println!(
+          "  {} {} ({})",
+          row.myanmar_word, row.mlcts_romanization, row.myanmar_syllables
+        ),
+        (None, None) => (),
+      }
+    }
+  }
+}
+
+/// A row before and after the regeneration, `None` if it is missing.
+type Change<'d> = (Option<&'d MyG2pMlcTsRow>, Option<&'d MyG2pMlcTsRow>);
+
+/// Compare two versions of the dataset.
+///
+/// # Arguments
+///
+/// * `old` - The rows before the regeneration by word.
+/// * `new` - The rows after the regeneration by word.
+///
+/// # Returns
+///
+/// The changed rows grouped by category.
+fn compare<'d>(
+  old: &'d BTreeMap<String, MyG2pMlcTsRow>,
+  new: &'d BTreeMap<String, MyG2pMlcTsRow>,
+) -> BTreeMap<&'static str, Vec<Change<'d>>>
+{
+  let mut changes: BTreeMap<&'static str, Vec<Change>> = BTreeMap::new();
+  for (word, after) in new
+  {
+    let before = old.get(word);
+    if before == Some(after)
+    {
+      continue;
+    }
+    changes
+      .entry(categorize(before, after))
+      .or_default()
+      .push((before, Some(after)));
+  }
+  for (word, before) in old
+  {
+    if !new.contains_key(word)
+    {
+      changes
+        .entry("removed")
+        .or_default()
+        .push((Some(before), None));
+    }
+  }
+  changes
+}
+
+/// Categorize a changed row by its cause. The words and syllables of myG2P
+/// are the source, the romanizations are made by the rules of mya2rom.
+///
+/// # Arguments
+///
+/// * `before` - The row before the regeneration, `None` if it is new.
+/// * `after` - The row after the regeneration.
+///
+/// # Returns
+///
+/// The category name.
+fn categorize(
+  before: Option<&MyG2pMlcTsRow>,
+  after: &MyG2pMlcTsRow,
+) -> &'static str
+{
+  match before
+  {
+    None => "added",
+    Some(before)
+      if before.myanmar_syllables != after.myanmar_syllables
+        || before.syllable_count != after.syllable_count =>
+    {
+      "source change"
+    }
+    Some(before) if before.mlcts_romanization != after.mlcts_romanization =>
+    {
+      "rule change"
+    }
+    Some(before) if before.mlcts_syllables != after.mlcts_syllables =>
+    {
+      "rule change (syllables only)"
+    }
+    _ => "other change",
+  }
+}
+
+/// Index the rows of a dataset by word.
+///
+/// # Arguments
+///
+/// * `rows` - The rows of the dataset.
+///
+/// # Returns
+///
+/// The rows by word. The last row of a duplicated word wins.
+fn by_word(rows: Vec<MyG2pMlcTsRow>) -> BTreeMap<String, MyG2pMlcTsRow>
+{
+  rows
+    .into_iter()
+    .map(|row| (row.myanmar_word.clone(), row))
+    .collect()
+}
@@ -0,0 +1,212 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use clap::Parser;
+use dataset::{load_g2p_mlcts_dict, MyG2pMlcTsRow};
+use indicatif::{ProgressBar, ProgressStyle};
+use mlcts_generator::mlcts_from_myanmar;
+
+mod dataset;
+
+/// CLI options
+#[derive(Parser)]
+struct ClapOptions
+{
+  /// Number of examples to print for each category
+  #[arg(short, long, default_value_t = 3)]
+  examples: usize,
+}
+
+/// A syllable which is romanized differently by mya2rom and this crate.
+struct Difference
+{
+  myanmar_syllable: String,
+  mya2rom: String,
+  mlcts_generator: String,
+}
+
+fn main()
+{
+  let cli_options = ClapOptions::parse();
+
+  let dict = load_g2p_mlcts_dict();
+  let syllables = collect_syllables(&dict);
+
+  let progress_bar = ProgressBar::new(syllables.len() as u64);
+  progress_bar.set_style(
+    ProgressStyle::with_template("[*] comparing {wide_bar} {pos}/{len}")
+      .unwrap(),
+  );
+
+  println!("[*] comparing mya2rom and mlcts_generator");
+  let mut differences: BTreeMap<String, Vec<Difference>> = BTreeMap::new();
+  for (myanmar_syllable, mya2rom) in &syllables
+  {
+    let generated = mlcts_from_myanmar(myanmar_syllable);
+    if generated != *mya2rom
+    {
+      differences
+        .entry(categorize(myanmar_syllable))
+        .or_default()
+        .push(Difference {
+          myanmar_syllable: myanmar_syllable.to_string(),
+          mya2rom: mya2rom.to_string(),
+          mlcts_generator: generated,
+        });
+    }
+    progress_bar.inc(1);
+  }
+  progress_bar.finish();
+
+  write_report(&differences);
+  print_summary(&differences, syllables.len(), cli_options.examples);
+}
+
+/// Collect unique (Myanmar syllable, mya2rom romanization) pairs from the G2P
+/// MLCTS dictionary.
+///
+/// # Arguments
+///
+/// * `dict` - The G2P MLCTS dictionary.
+///
+/// # Returns
+///
+/// Unique syllable pairs sorted by the Myanmar syllable.
+fn collect_syllables(dict: &[MyG2pMlcTsRow]) -> Vec<(&str, &str)>
+{
+  let mut syllables = dict
+    .iter()
+    .flat_map(|row| {
+      row
+        .myanmar_syllables
+        .split("|")
+        .zip(row.mlcts_syllables.split("|"))
+        .filter(|(_, mlcts)| *mlcts != "INVALID")
+    })
+    .collect::<Vec<_>>();
+
+  syllables.sort();
+  syllables.dedup();
+  syllables
+}
+
+/// Categorize a Myanmar syllable by its construct or rhyme so that
+/// systematic differences are grouped together.
+///
+/// # Arguments
+///
+/// * `syllable` - The Myanmar syllable.
+///
+/// # Returns
+///
+/// The category name.
+fn categorize(syllable: &str) -> String
+{
+  const INDEPENDENT_VOWELS: &str = "ဣဤဥဦဧဩဪ၌၍၏၎";
+  const MEDIALS: &str = "ျြွှ";
+
+  if syllable.contains("င်္")
+  {
+    return "kinzi".to_string();
+  }
+  if syllable.contains('္') || syllable.contains('ဿ')
+  {
+    return "stacked consonant".to_string();
+  }
+  if syllable.chars().any(|c| INDEPENDENT_VOWELS.contains(c))
+  {
+    return "independent vowel".to_string();
+  }
+
+  // strip the initial consonant to get the rhyme
+  let rhyme = syllable.chars().skip(1).collect::<String>();
+  let medials = rhyme
+    .chars()
+    .take_while(|c| MEDIALS.contains(*c))
+    .collect::<String>();
+  let rhyme = rhyme.trim_start_matches(|c| MEDIALS.contains(c));
+
+  match (medials.is_empty(), rhyme.is_empty())
+  {
+    (true, true) => "rhyme: (inherent)".to_string(),
+    (true, false) => format!("rhyme: -{}", rhyme),
+    (false, true) => format!("medial: -{}", medials),
+    (false, false) => format!("medial: -{} + rhyme: -{}", medials, rhyme),
+  }
+}
+
+/// Write all differences into a CSV file for further inspection.
+///
+/// # Arguments
+///
+/// * `differences` - Differences grouped by category.
+fn write_report(differences: &BTreeMap<String, Vec<Difference>>)
+{
+  let output_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("assets")
+    .join("mya2rom-diff.csv");
+
+  let mut csv_writer = csv::Writer::from_path(&output_path).unwrap();
+  csv_writer
+    .write_record([
+      "category",
+      "myanmar_syllable",
+      "mya2rom",
+      "mlcts_generator",
+    ])
+    .unwrap();
+
+  for (category, diffs) in differences
+  {
+    for diff in diffs
+    {
+      csv_writer
+        .write_record([
+          category.as_str(),
+          diff.myanmar_syllable.as_str(),
+          diff.mya2rom.as_str(),
+          diff.mlcts_generator.as_str(),
+        ])
+        .unwrap();
+    }
+  }
+
+  csv_writer.flush().unwrap();
+  println!("[*] full report is written to {}", output_path.display());
+}
+
+/// Print the categorized summary, largest categories first.
+///
+/// # Arguments
+///
+/// * `differences` - Differences grouped by category.
+/// * `total` - Total number of compared syllables.
+/// * `examples` - Number of examples to print for each category.
+fn print_summary(
+  differences: &BTreeMap<String, Vec<Difference>>,
+  total: usize,
+  examples: usize,
+)
+{
+  let mut categories = differences.iter().collect::<Vec<_>>();
+  categories.sort_by_key(|(_, diffs)| std::cmp::Reverse(diffs.len()));
+
+  let different = categories.iter().map(|(_, d)| d.len()).sum::<usize>();
+  println!(
+    "[*] {} of {} syllables are romanized differently",
+    different, total
+  );
+
+  for (category, diffs) in categories
+  {
+    println!("\n{} ({})", category, diffs.len());
+    for diff in diffs.iter().take(examples)
+    {
+      println!(
+        "  {} mya2rom: {:?} mlcts_generator: {:?}",
+        diff.myanmar_syllable, diff.mya2rom, diff.mlcts_generator
+      );
+    }
+  }
+}
@@ -0,0 +1,303 @@
+//! Downloads of the source data of the dev tools.
+//!
+//! Downloaded files are kept in a cache directory with their ETags and are
+//! verified with the SHA-256 checksums in `assets/checksums.sha256`. A file
+//! without a checksum is an error, unless recording checksums is enabled
+//! explicitly (`--record-checksums`); the checksum file should then be
+//! committed.
+
+use std::collections::BTreeMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use indicatif::{ProgressBar, ProgressStyle};
+use sha2::{Digest, Sha256};
+
+/// Number of attempts of a download before giving up.
+const ATTEMPTS: u32 = 3;
+
+/// The content and the ETag of a downloaded file.
+type Downloaded = (Vec<u8>, Option<String>);
+
+/// Downloads files into a cache directory.
+pub struct Downloader
+{
+  /// The directory of the downloaded files and their ETags.
+  cache_dir: PathBuf,
+  /// Only use cached files and never connect.
+  offline: bool,
+  /// Check cached files with the server for a newer version.
+  refresh: bool,
+  /// Record the checksums of files which have none instead of failing.
+  record_checksums: bool,
+}
+
+impl Downloader
+{
+  /// Create a downloader.
+  ///
+  /// # Arguments
+  ///
+  /// * `cache_dir` - The cache directory, `MLCTS_CACHE_DIR` or `assets/cache`
+  ///   by default.
+  /// * `offline` - Only use cached files and never connect.
+  /// * `refresh` - Check cached files with the server for a newer version.
+  /// * `record_checksums` - Record the checksums of files which have none
+  ///   instead of failing.
+  ///
+  /// # Returns
+  ///
+  /// A new downloader.
+  pub fn new(
+    cache_dir: Option<PathBuf>,
+    offline: bool,
+    refresh: bool,
+    record_checksums: bool,
+  ) -> Self
+  {
+    let cache_dir = cache_dir
+      .or_else(|| std::env::var_os("MLCTS_CACHE_DIR").map(PathBuf::from))
+      .unwrap_or_else(|| assets_dir().join("cache"));
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    Self {
+      cache_dir,
+      offline,
+      refresh,
+      record_checksums,
+    }
+  }
+
+  /// Get a file from the cache or download it.
+  ///
+  /// # Arguments
+  ///
+  /// * `name` - The name of the file in the cache and the checksums.
+  /// * `url` - The URL to download the file from.
+  ///
+  /// # Returns
+  ///
+  /// The verified content of the file.
+  pub fn fetch(&self, name: &str, url: &str) -> Vec<u8>
+  {
+    let path = self.cache_dir.join(name);
+    let etag_path = self.cache_dir.join(format!("{}.etag", name));
+    let cached = std::fs::read(&path).ok();
+
+    let content = match cached
+    {
+      Some(content) if self.offline || !self.refresh => content,
+      None if self.offline => panic!(
+        "{} is not cached in {} and downloads are disabled",
+        name,
+        self.cache_dir.display()
+      ),
+      cached =>
+      {
+        let etag = cached
+          .as_ref()
+          .and_then(|_| std::fs::read_to_string(&etag_path).ok());
+        match download_with_retries(name, url, etag.as_deref())
+        {
+          // the cached file is up to date
+          None => cached.unwrap(),
+          Some((content, etag)) =>
+          {
+            verify(name, &content, self.record_checksums);
+            std::fs::write(&path, &content).unwrap();
+            match etag
+            {
+              Some(etag) => std::fs::write(&etag_path, etag).unwrap(),
+              None => std::fs::remove_file(&etag_path).unwrap_or(()),
+            }
+            return content;
+          }
+        }
+      }
+    };
+    verify(name, &content, self.record_checksums);
+    content
+  }
+}
+
+/// Download a file, retrying on failures.
+///
+/// # Arguments
+///
+/// * `name` - The name of the file.
+/// * `url` - The URL of the file.
+/// * `etag` - The ETag of the cached file.
+///
+/// # Returns
+///
+/// The content and the ETag of the file, `None` if the cached file is up to
+/// date.
+fn download_with_retries(
+  name: &str,
+  url: &str,
+  etag: Option<&str>,
+) -> Option<Downloaded>
+{
+  let mut attempt = 1;
+  loop
+  {
+    match download(name, url, etag)
+    {
+      Ok(result) => return result,
+      Err(e) if attempt < ATTEMPTS =>
+      {
+        println!("[*] downloading {} failed ({}), retrying", name, e);
+        std::thread::sleep(std::time::Duration::from_secs(attempt as u64));
+        attempt += 1;
+      }
+      Err(e) => panic!("downloading {} failed: {}", name, e),
+    }
+  }
+}
+
+/// Download a file unless it is not modified.
+///
+/// # Arguments
+///
+/// * `name` - The name of the file.
+/// * `url` - The URL of the file.
+/// * `etag` - The ETag of the cached file.
+///
+/// # Returns
+///
+/// The content and the ETag of the file, `None` if the cached file is up to
+/// date.
+fn download(
+  name: &str,
+  url: &str,
+  etag: Option<&str>,
+) -> Result<Option<Downloaded>, Box<dyn std::error::Error>>
+{
+  let client = reqwest::blocking::Client::new();
+  let mut request = client.get(url);
+  if let Some(etag) = etag
+  {
+    request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+  }
+  let mut response = request.send()?;
+  if response.status() == reqwest::StatusCode::NOT_MODIFIED
+  {
+    return Ok(None);
+  }
+  response = response.error_for_status()?;
+  let etag = response
+    .headers()
+    .get(reqwest::header::ETAG)
+    .and_then(|etag| etag.to_str().ok())
+    .map(str::to_string);
+
+  let progress_bar = ProgressBar::new(response.content_length().unwrap_or(0));
+  progress_bar.set_style(
+    ProgressStyle::with_template(&format!(
+      "[*] downloading {} {{wide_bar}} {{bytes}}/{{total_bytes}} ({{eta}})",
+      name
+    ))
+    .unwrap(),
+  );
+
+  let mut content = Vec::new();
+  let mut buffer = [0; 8192]; // 8KB buffer
+  loop
+  {
+    let bytes_read = response.read(&mut buffer)?;
+    if bytes_read == 0
+    {
+      break;
+    }
+    content.extend_from_slice(&buffer[.. bytes_read]);
+    progress_bar.inc(bytes_read as u64);
+  }
+  progress_bar.finish();
+  Ok(Some((content, etag)))
+}
+
+/// Verify the checksum of a file, or record it if the file has none yet and
+/// recording is enabled.
+///
+/// # Arguments
+///
+/// * `name` - The name of the file.
+/// * `content` - The content of the file.
+/// * `record` - Record the checksum if the file has none instead of failing.
+fn verify(name: &str, content: &[u8], record: bool)
+{
+  let actual = format!("{:x}", Sha256::digest(content));
+  let path = assets_dir().join("checksums.sha256");
+  let mut checksums = read_checksums(&path);
+  match checksums.get(name)
+  {
+    Some(expected) if *expected == actual => (),
+    Some(expected) => panic!(
+      "checksum of {} is {} instead of {}, remove it from {} if the file \
+       is expected to change",
+      name,
+      actual,
+      expected,
+      path.display()
+    ),
+    None if !record => panic!(
+      "there is no checksum of {} in {}, check the file and run with \
+       --record-checksums to record its checksum {}",
+      name,
+      path.display(),
+      actual
+    ),
+    None =>
+    {
+      println!(
+        "[*] recording the checksum of {}, commit {}",
+        name,
+        path.display()
+      );
+      checksums.insert(name.to_string(), actual);
+      let comments = std::fs::read_to_string(&path)
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| line.starts_with('#'))
+        .map(|line| format!("{}\n", line))
+        .collect::<String>();
+      let lines = checksums
+        .iter()
+        .map(|(name, checksum)| format!("{}  {}\n", checksum, name))
+        .collect::<String>();
+      std::fs::write(&path, comments + &lines).unwrap();
+    }
+  }
+}
+
+/// Read a checksum file in the format of `sha256sum`. Lines starting with `#`
+/// are comments.
+///
+/// # Arguments
+///
+/// * `path` - The path of the checksum file.
+///
+/// # Returns
+///
+/// The checksums by file name, empty if there is no checksum file.
+fn read_checksums(path: &Path) -> BTreeMap<String, String>
+{
+  std::fs::read_to_string(path)
+    .unwrap_or_default()
+    .lines()
+    .filter(|line| !line.starts_with('#'))
+    .filter_map(|line| line.split_once("  "))
+    .map(|(checksum, name)| (name.to_string(), checksum.to_string()))
+    .collect()
+}
+
+/// Get the assets directory.
+///
+/// # Returns
+///
+/// The path of the assets directory.
+fn assets_dir() -> PathBuf
+{
+  Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("assets")
+}
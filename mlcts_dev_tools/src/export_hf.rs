@@ -0,0 +1,229 @@
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use dataset::load_g2p_mlcts_dict;
+use indicatif::{ProgressBar, ProgressStyle};
+use mlcts_generator::mlcts_from_myanmar;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+
+mod dataset;
+
+/// CLI options
+#[derive(Parser)]
+struct ClapOptions
+{
+  /// Text files with a Myanmar sentence on each line to add to the dataset
+  #[arg(short, long)]
+  corpus: Vec<PathBuf>,
+
+  /// Seed of the shuffle before splitting
+  #[arg(short, long, default_value_t = 42)]
+  seed: u64,
+
+  /// Fraction of the pairs for the validation split
+  #[arg(long, default_value_t = 0.05)]
+  valid: f64,
+
+  /// Fraction of the pairs for the test split
+  #[arg(long, default_value_t = 0.05)]
+  test: f64,
+}
+
+/// A (Myanmar, MLCTS) pair of the dataset.
+#[derive(serde::Serialize)]
+struct Pair
+{
+  myanmar: String,
+  mlcts: String,
+  source: &'static str,
+}
+
+fn main()
+{
+  let cli_options = ClapOptions::parse();
+  assert!(
+    cli_options.valid >= 0.0
+      && cli_options.test >= 0.0
+      && cli_options.valid + cli_options.test < 1.0,
+    "validation and test fractions must add up to less than 1"
+  );
+
+  let mut inputs = load_g2p_mlcts_dict()
+    .into_iter()
+    .map(|row| (row.myanmar_word, "dictionary"))
+    .collect::<Vec<_>>();
+  for path in &cli_options.corpus
+  {
+    inputs.extend(load_corpus(path).into_iter().map(|line| (line, "corpus")));
+  }
+
+  let progress_bar = ProgressBar::new(inputs.len() as u64);
+  progress_bar.set_style(
+    ProgressStyle::with_template("[*] romanizing {wide_bar} {pos}/{len}")
+      .unwrap(),
+  );
+
+  println!("[*] romanizing the dictionary and the corpus");
+  let mut seen = BTreeSet::new();
+  let mut skipped = 0;
+  let mut pairs = Vec::new();
+  for (myanmar, source) in inputs
+  {
+    progress_bar.inc(1);
+    if !seen.insert(myanmar.clone())
+    {
+      continue;
+    }
+
+    // unsupported syllables are echoed back in Myanmar script
+    let mlcts = mlcts_from_myanmar(&myanmar);
+    if mlcts.chars().any(is_myanmar_char)
+    {
+      skipped += 1;
+      continue;
+    }
+    pairs.push(Pair {
+      myanmar,
+      mlcts,
+      source,
+    });
+  }
+  progress_bar.finish();
+
+  pairs.shuffle(&mut StdRng::seed_from_u64(cli_options.seed));
+  let valid_len = (pairs.len() as f64 * cli_options.valid).round() as usize;
+  let test_len = (pairs.len() as f64 * cli_options.test).round() as usize;
+  let (test, rest) = pairs.split_at(test_len);
+  let (valid, train) = rest.split_at(valid_len);
+
+  let output_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("assets")
+    .join("hf-dataset");
+  std::fs::create_dir_all(&output_dir).unwrap();
+
+  let splits = [("train", train), ("valid", valid), ("test", test)];
+  for (name, split) in splits
+  {
+    write_split(&output_dir.join(format!("{}.jsonl", name)), split);
+  }
+  write_metadata(&output_dir.join("metadata.json"), &cli_options, &splits);
+
+  println!(
+    "[*] {} pairs ({} train, {} valid, {} test), {} unsupported skipped",
+    pairs.len(),
+    train.len(),
+    valid.len(),
+    test.len(),
+    skipped
+  );
+  println!("[*] dataset is written to {}", output_dir.display());
+}
+
+/// Write a split as JSON lines.
+///
+/// # Arguments
+///
+/// * `path` - The output path of the split.
+/// * `pairs` - The pairs of the split.
+fn write_split(path: &Path, pairs: &[Pair])
+{
+  let mut writer = BufWriter::new(File::create(path).unwrap());
+  for pair in pairs
+  {
+    serde_json::to_writer(&mut writer, pair).unwrap();
+    writer.write_all(b"\n").unwrap();
+  }
+  writer.flush().unwrap();
+}
+
+/// Write the metadata of the dataset.
+///
+/// # Arguments
+///
+/// * `path` - The output path of the metadata.
+/// * `cli_options` - The options the dataset is exported with.
+/// * `splits` - The names and pairs of the splits.
+fn write_metadata(
+  path: &Path,
+  cli_options: &ClapOptions,
+  splits: &[(&str, &[Pair])],
+)
+{
+  let split_info = splits
+    .iter()
+    .map(|(name, pairs)| {
+      let count = |source| pairs.iter().filter(|p| p.source == source).count();
+      (
+        name.to_string(),
+        serde_json::json!({
+          "file": format!("{}.jsonl", name),
+          "num_examples": pairs.len(),
+          "num_dictionary_examples": count("dictionary"),
+          "num_corpus_examples": count("corpus"),
+        }),
+      )
+    })
+    .collect::<serde_json::Map<_, _>>();
+
+  let metadata = serde_json::json!({
+    "description": "Myanmar text and its MLCTS romanization by mlcts_generator",
+    "features": {
+      "myanmar": "string",
+      "mlcts": "string",
+      "source": "string",
+    },
+    "sources": {
+      "dictionary": "words of myG2P ver2 (github.com/ye-kyaw-thu/myG2P)",
+      "corpus": cli_options
+        .corpus
+        .iter()
+        .map(|path| path.display().to_string())
+        .collect::<Vec<_>>(),
+    },
+    "seed": cli_options.seed,
+    "splits": split_info,
+  });
+
+  let file = File::create(path).unwrap();
+  serde_json::to_writer_pretty(file, &metadata).unwrap();
+}
+
+/// Check if a character is in the Myanmar block.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is in the Myanmar block, `false` otherwise.
+fn is_myanmar_char(c: char) -> bool
+{
+  matches!(c, '\u{1000}' ..= '\u{109F}')
+}
+
+/// Load the non-empty lines of a corpus file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the corpus file.
+///
+/// # Returns
+///
+/// The trimmed lines with Myanmar text.
+fn load_corpus(path: &Path) -> Vec<String>
+{
+  std::fs::read_to_string(path)
+    .unwrap()
+    .lines()
+    .map(str::trim)
+    .filter(|line| line.chars().any(is_myanmar_char))
+    .map(str::to_string)
+    .collect()
+}
@@ -0,0 +1,310 @@
+use std::path::Path;
+
+use mlcts_generator::mlcts_from_myanmar;
+
+/// Medial diacritic combinations.
+const MEDIALS: &[&str] = &["ျ", "ြ", "ွ", "ှ", "ျွ", "ြွ", "ျှ", "ြှ", "ွှ", "ျွှ", "ြွှ"];
+
+/// Stacked consonant pairs (upper consonant, lower consonant) including
+/// kinzi and the great sa.
+const STACKS: &[&str] = &[
+  "က္က",
+  "က္ခ",
+  "ဂ္ဂ",
+  "ဂ္ဃ",
+  "င်္က",
+  "င်္ခ",
+  "င်္ဂ",
+  "င်္ဃ",
+  "စ္စ",
+  "စ္ဆ",
+  "ဇ္ဇ",
+  "ဇ္ဈ",
+  "ည္စ",
+  "ည္ဆ",
+  "ည္ဇ",
+  "ည္ဈ",
+  "ဋ္ဋ",
+  "ဋ္ဌ",
+  "ဍ္ဍ",
+  "ဍ္ဎ",
+  "ဏ္ဋ",
+  "ဏ္ဌ",
+  "ဏ္ဍ",
+  "ဏ္ဎ",
+  "ဏ္ဏ",
+  "တ္တ",
+  "တ္ထ",
+  "ဒ္ဒ",
+  "ဒ္ဓ",
+  "န္တ",
+  "န္ထ",
+  "န္ဒ",
+  "န္ဓ",
+  "န္န",
+  "ပ္ပ",
+  "ပ္ဖ",
+  "ဗ္ဗ",
+  "ဗ္ဘ",
+  "မ္ပ",
+  "မ္ဖ",
+  "မ္ဗ",
+  "မ္ဘ",
+  "မ္မ",
+  "လ္လ",
+  "ဿ",
+];
+
+/// Coverage of a single grammar item.
+struct Coverage
+{
+  kind: &'static str,
+  item: String,
+  sample: String,
+  supported: bool,
+  exercised: bool,
+}
+
+fn main()
+{
+  let tested_syllables = load_tested_syllables();
+
+  let mut coverages = Vec::new();
+  for rhyme in load_rhymes()
+  {
+    let sample = format!("က{}", rhyme);
+    coverages.push(check("rhyme", rhyme, sample));
+  }
+  for medial in MEDIALS
+  {
+    coverages.push(check(
+      "medial",
+      medial.to_string(),
+      format!("မ{}ာ", medial),
+    ));
+  }
+  for stack in STACKS
+  {
+    coverages.push(check("stack", stack.to_string(), format!("တ{}ာ", stack)));
+  }
+
+  // an item is exercised if one of the test inputs contains it
+  for coverage in &mut coverages
+  {
+    coverage.exercised = tested_syllables.iter().any(|s| {
+      let (medial, rhyme) = split_syllable(s);
+      match coverage.kind
+      {
+        "rhyme" => rhyme == coverage.item,
+        "medial" => medial == coverage.item,
+        _ => s.contains(&coverage.item),
+      }
+    });
+  }
+
+  write_report(&coverages);
+  print_summary(&coverages, tested_syllables.is_empty());
+}
+
+/// Check if the sample syllable of a grammar item is supported by
+/// `mlcts_generator`.
+///
+/// # Arguments
+///
+/// * `kind` - The kind of the grammar item.
+/// * `item` - The grammar item.
+/// * `sample` - A sample syllable containing the item.
+///
+/// # Returns
+///
+/// The coverage of the grammar item.
+fn check(kind: &'static str, item: String, sample: String) -> Coverage
+{
+  // unsupported syllables are echoed as-is by the generator
+  let mlcts = mlcts_from_myanmar(&sample);
+  let supported = !mlcts.chars().any(is_myanmar_char);
+
+  Coverage {
+    kind,
+    item,
+    sample,
+    supported,
+    exercised: false,
+  }
+}
+
+/// Load the rhymes (vowel signs, finals and tone marks following the initial
+/// consonant) from `mlcts_generator/rhymes.csv`, the rhymes the parser is
+/// generated from.
+///
+/// # Returns
+///
+/// The inherent vowel (no signs) followed by the rhymes of `rhymes.csv`.
+fn load_rhymes() -> Vec<String>
+{
+  let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("mlcts_generator")
+    .join("rhymes.csv");
+
+  let mut rdr = csv::ReaderBuilder::new()
+    .comment(Some(b'#'))
+    .from_path(path)
+    .unwrap();
+  let rhymes = rdr
+    .records()
+    .map(|r| r.unwrap())
+    .map(|r| r.get(0).unwrap_or_default().to_string());
+  std::iter::once(String::new()).chain(rhymes).collect()
+}
+
+/// Split a simple syllable (without stacked consonants) into the medial
+/// diacritics and the rhyme following the initial consonant.
+///
+/// # Arguments
+///
+/// * `syllable` - The syllable to split.
+///
+/// # Returns
+///
+/// The medial diacritics and the rhyme.
+fn split_syllable(syllable: &str) -> (&str, &str)
+{
+  let without_consonant =
+    syllable.strip_prefix(is_consonant).unwrap_or(syllable);
+  let rhyme = without_consonant.trim_start_matches(['ျ', 'ြ', 'ွ', 'ှ']);
+  let medial_len = without_consonant.len() - rhyme.len();
+  (&without_consonant[.. medial_len], rhyme)
+}
+
+/// Check if a character is in the Myanmar Unicode block.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is a Myanmar character, `false` otherwise.
+fn is_myanmar_char(c: char) -> bool
+{
+  matches!(c, '\u{1000}' ..= '\u{109F}')
+}
+
+/// Check if a character is a Myanmar consonant.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is a Myanmar consonant, `false` otherwise.
+fn is_consonant(c: char) -> bool
+{
+  matches!(c, 'က' ..= 'အ')
+}
+
+/// Load Myanmar syllables used in the generated tokenizer test inputs.
+/// Run `cargo make tokenizer-inputs` to generate them.
+///
+/// # Returns
+///
+/// Tested syllables or an empty vector if the inputs are not generated.
+fn load_tested_syllables() -> Vec<String>
+{
+  let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("mlcts_tokenizer")
+    .join("tests")
+    .join("inputs_single_syllable.csv");
+
+  let mut rdr = match csv::Reader::from_path(path)
+  {
+    Ok(rdr) => rdr,
+    Err(_) => return Vec::new(),
+  };
+
+  rdr
+    .records()
+    .map(|r| r.unwrap())
+    .map(|r| r.get(1).unwrap_or_default().to_string())
+    .collect()
+}
+
+/// Write the coverage of every grammar item into a CSV file.
+///
+/// # Arguments
+///
+/// * `coverages` - Coverage of the grammar items.
+fn write_report(coverages: &[Coverage])
+{
+  let output_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("assets")
+    .join("grammar-coverage.csv");
+
+  let mut csv_writer = csv::Writer::from_path(&output_path).unwrap();
+  csv_writer
+    .write_record(["kind", "item", "sample", "supported", "exercised"])
+    .unwrap();
+
+  for coverage in coverages
+  {
+    csv_writer
+      .write_record([
+        coverage.kind,
+        coverage.item.as_str(),
+        coverage.sample.as_str(),
+        &coverage.supported.to_string(),
+        &coverage.exercised.to_string(),
+      ])
+      .unwrap();
+  }
+
+  csv_writer.flush().unwrap();
+  println!("[*] full report is written to {}", output_path.display());
+}
+
+/// Print the coverage summary and the gaps of each kind.
+///
+/// # Arguments
+///
+/// * `coverages` - Coverage of the grammar items.
+/// * `no_test_inputs` - If the tokenizer test inputs are not generated.
+fn print_summary(coverages: &[Coverage], no_test_inputs: bool)
+{
+  if no_test_inputs
+  {
+    println!("[!] tokenizer test inputs are not found, run tokenizer-inputs");
+  }
+
+  for kind in ["rhyme", "medial", "stack"]
+  {
+    let items = coverages
+      .iter()
+      .filter(|c| c.kind == kind)
+      .collect::<Vec<_>>();
+    let supported = items.iter().filter(|c| c.supported).count();
+    let exercised = items.iter().filter(|c| c.exercised).count();
+
+    println!(
+      "\n{}: {}/{} supported, {}/{} exercised by tests",
+      kind,
+      supported,
+      items.len(),
+      exercised,
+      items.len()
+    );
+    for item in items.iter().filter(|c| !c.supported || !c.exercised)
+    {
+      println!(
+        "  {} (sample: {}){}{}",
+        item.item,
+        item.sample,
+        if item.supported { "" } else { " unsupported" },
+        if item.exercised { "" } else { " untested" },
+      );
+    }
+  }
+}
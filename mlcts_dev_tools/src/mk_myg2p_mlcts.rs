@@ -1,69 +1,196 @@
-use std::io::Read;
-use std::path::Path;
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
 
 use clap::Parser;
 use deno_core::{serde_v8, v8, JsRuntime, RuntimeOptions};
+use download::Downloader;
 use indicatif::{ProgressBar, ProgressStyle};
-use lazy_static::lazy_static;
 
-lazy_static! {
-  static ref ROMANIZATIONS_JS: String = get_js_code("romanisations.js");
-  static ref MYA2ROM_JS: String = get_js_code("mya2rom.js");
-}
+mod download;
 
 /// CLI options
 #[derive(Parser)]
 struct ClapOptions
 {
-  /// If the original dictionary should be re-downloaded
+  /// If the cached original dictionary and JS code should be checked for a
+  /// newer version
   #[arg(short, long, default_value_t = false)]
   download_original: bool,
+  /// Only use cached downloads and never connect
+  #[arg(long, default_value_t = false)]
+  offline: bool,
+  /// Directory of the cached downloads, `MLCTS_CACHE_DIR` or assets/cache
+  /// by default
+  #[arg(long)]
+  cache_dir: Option<PathBuf>,
+  /// Record the checksums of downloads which have none in
+  /// assets/checksums.sha256 instead of failing
+  #[arg(long, default_value_t = false)]
+  record_checksums: bool,
+  /// Continue an interrupted run after the rows already written
+  #[arg(short, long, default_value_t = false)]
+  resume: bool,
+  /// Number of JS runtimes generating rows in parallel, the number of CPUs
+  /// by default
+  #[arg(short, long)]
+  jobs: Option<usize>,
 }
 
+/// Columns of the generated dictionary.
+const HEADER: [&str; 5] = [
+  "myanmar_word",
+  "mlcts_romanization",
+  "syllable_count",
+  "myanmar_syllables",
+  "mlcts_syllables",
+];
+
 fn main()
 {
   let cli_options = ClapOptions::parse();
-
-  let mut deno_rt = JsRuntime::new(RuntimeOptions::default());
-  let libs = format!("{}{}", *ROMANIZATIONS_JS, *MYA2ROM_JS);
-  // Load library code into Deno runtime
-  deno_eval(&mut deno_rt, libs.clone());
+  let jobs = cli_options
+    .jobs
+    .unwrap_or_else(|| {
+      std::thread::available_parallelism().map_or(1, usize::from)
+    })
+    .max(1);
 
   let output_path = Path::new(env!("CARGO_MANIFEST_DIR"))
     .join("..")
     .join("assets")
     .join("myg2p-dict-mlcts.csv");
 
-  let mut csv_writer = csv::Writer::from_path(output_path).unwrap();
-  csv_writer
-    .write_record(&[
-      "myanmar_word",
-      "mlcts_romanization",
-      "syllable_count",
-      "myanmar_syllables",
-      "mlcts_syllables",
-    ])
-    .unwrap();
-
-  let original_dict_content = get_original_dict(cli_options.download_original);
+  let downloader = Downloader::new(
+    cli_options.cache_dir.clone(),
+    cli_options.offline,
+    cli_options.download_original,
+    cli_options.record_checksums,
+  );
+  let original_dict_content = get_original_dict(&downloader);
   let dict = load_original_dict(original_dict_content.as_str());
 
+  let done = match cli_options.resume
+  {
+    true => complete_rows(&output_path),
+    false => 0,
+  };
+  let mut csv_writer = match done
+  {
+    0 =>
+    {
+      let mut csv_writer = csv::Writer::from_path(&output_path).unwrap();
+      csv_writer.write_record(HEADER).unwrap();
+      csv_writer
+    }
+    _ =>
+    {
+      println!("[*] resuming after {} rows", done);
+      let file = OpenOptions::new().append(true).open(&output_path).unwrap();
+      csv::Writer::from_writer(file)
+    }
+  };
+
   let progress_bar = ProgressBar::new(dict.len() as u64);
   progress_bar.set_style(
     ProgressStyle::with_template("[*] generating {wide_bar} {pos}/{len}")
       .unwrap(),
   );
+  progress_bar.set_position(done as u64);
 
-  println!("[*] generating MLCTS romanization");
-  for row in dict
-  {
-    generate_mlcts(row, &mut csv_writer, &mut deno_rt);
-    progress_bar.inc(1);
-  }
+  println!("[*] generating MLCTS romanization with {} runtimes", jobs);
+  let libs = format!(
+    "{}{}",
+    get_js_code(&downloader, "romanisations.js"),
+    get_js_code(&downloader, "mya2rom.js")
+  );
+  let rows = &dict[done.min(dict.len()) ..];
+  let (sender, receiver) = mpsc::channel();
+  std::thread::scope(|scope| {
+    for worker in 0 .. jobs
+    {
+      let sender = sender.clone();
+      let libs = &libs;
+      scope.spawn(move || {
+        // runtimes cannot be shared between threads, each worker has one
+        let mut deno_rt = JsRuntime::new(RuntimeOptions::default());
+        // Load library code into Deno runtime
+        deno_eval(&mut deno_rt, libs.clone());
+        for (i, row) in rows.iter().enumerate().skip(worker).step_by(jobs)
+        {
+          let record = generate_mlcts(row, &mut deno_rt);
+          if sender.send((i, record)).is_err()
+          {
+            break;
+          }
+        }
+      });
+    }
+    drop(sender);
+
+    // rows are written in the order of the dictionary, so that an
+    // interrupted run can be resumed from the number of written rows
+    let mut pending = BTreeMap::new();
+    let mut next = 0;
+    for (i, record) in receiver
+    {
+      pending.insert(i, record);
+      while let Some(record) = pending.remove(&next)
+      {
+        csv_writer.write_record(&record).unwrap();
+        progress_bar.inc(1);
+        next += 1;
+      }
+      csv_writer.flush().unwrap();
+    }
+  });
 
   progress_bar.finish();
   println!("[*] done generating MLCTS romanization");
-  csv_writer.flush().unwrap();
+}
+
+/// Count the complete rows of a previous run and cut off a partially
+/// written row at the end.
+///
+/// # Arguments
+///
+/// * `path` - The path of the generated dictionary.
+///
+/// # Returns
+///
+/// The number of complete rows, `0` if there is nothing to resume.
+fn complete_rows(path: &Path) -> usize
+{
+  let Ok(content) = std::fs::read(path)
+  else
+  {
+    return 0;
+  };
+
+  let mut reader = csv::Reader::from_reader(content.as_slice());
+  let mut record = csv::StringRecord::new();
+  let mut rows = 0;
+  let mut end = 0;
+  while let Ok(true) = reader.read_record(&mut record)
+  {
+    let position = reader.position().byte();
+    // the last row may be cut off in the middle of its last field
+    let cut_off = position == content.len() as u64 && !content.ends_with(b"\n");
+    if record.len() != HEADER.len() || cut_off
+    {
+      break;
+    }
+    rows += 1;
+    end = position;
+  }
+
+  if rows > 0
+  {
+    let file = OpenOptions::new().write(true).open(path).unwrap();
+    file.set_len(end).unwrap();
+  }
+  rows
 }
 
 /// Load the original dictionary, clean it and prepare for processing.
@@ -87,43 +214,41 @@ fn load_original_dict(content: &str) -> Vec<(&str, Vec<&str>)>
 /// # Arguments
 ///
 /// * `row` - A row of the original dictionary
+/// * `deno_rt` - Deno runtime with the library code loaded
 ///
 /// # Returns
 ///
 /// A new row with MLCTS romanization
 fn generate_mlcts(
-  row: (&str, Vec<&str>),
-  csv_writer: &mut csv::Writer<std::fs::File>,
+  row: &(&str, Vec<&str>),
   deno_rt: &mut JsRuntime,
-)
+) -> Vec<String>
 {
   let whole_word = mya2rom(row.0, deno_rt)
     .split(" ")
     .map(|s| s.to_string())
     .collect::<Vec<String>>();
 
-  csv_writer
-    .write_record(&[
-      row.0,
-      &whole_word.join(""),
-      &row.1.len().to_string(),
-      &row.1.join("|"),
-      &row
-        .1
-        .iter()
-        .enumerate()
-        .map(|(i, s)| {
-          let romanization = mya2rom(s, deno_rt);
-          if i < whole_word.len() && romanization == whole_word[i]
-          {
-            return romanization;
-          }
-          return "INVALID".to_string();
-        })
-        .collect::<Vec<String>>()
-        .join("|"),
-    ])
-    .unwrap();
+  vec![
+    row.0.to_string(),
+    whole_word.join(""),
+    row.1.len().to_string(),
+    row.1.join("|"),
+    row
+      .1
+      .iter()
+      .enumerate()
+      .map(|(i, s)| {
+        let romanization = mya2rom(s, deno_rt);
+        if i < whole_word.len() && romanization == whole_word[i]
+        {
+          return romanization;
+        }
+        return "INVALID".to_string();
+      })
+      .collect::<Vec<String>>()
+      .join("|"),
+  ]
 }
 
 /// Convert a Myanmar word to MLCTS romanization.
@@ -151,80 +276,39 @@ fn mya2rom(word: &str, deno_rt: &mut JsRuntime) -> String
 ///
 /// # Arguments
 ///
+/// * `downloader` - The downloader of the files
 /// * `filename` - JS file name
 ///
 /// # Returns
 ///
 /// Content of the JS file
-fn get_js_code(filename: &str) -> String
+fn get_js_code(downloader: &Downloader, filename: &str) -> String
 {
   let base =
     "https://raw.githubusercontent.com/myanmaropenwordnet/mya2rom/933c186";
-  reqwest::blocking::get(&format!("{}/{}", base, filename))
-    .unwrap()
-    .text()
-    .unwrap()
+  let content = downloader.fetch(filename, &format!("{}/{}", base, filename));
+  String::from_utf8(content).unwrap()
 }
 
 /// Get the original dictionary from the URL
 ///
+/// # Arguments
+///
+/// * `downloader` - The downloader of the files
+///
 /// # Returns
 ///
 /// Original dictionary content
-fn get_original_dict(download: bool) -> String
+fn get_original_dict(downloader: &Downloader) -> String
 {
-  let dict_path = Path::new(env!("CARGO_MANIFEST_DIR"))
-    .join("..")
-    .join("assets")
-    .join("myg2p-dict.txt");
-
-  if !download && dict_path.exists()
-  {
-    return std::fs::read_to_string(&dict_path).unwrap();
-  }
-
-  println!("[*] downloading the original dictionary");
-  let client = reqwest::blocking::Client::new();
   let url = "https://raw.githubusercontent.com/ye-kyaw-thu/myG2P/refs/heads/master/ver2/myg2p.ver2.0.txt";
-  let mut response = client.get(url).send().unwrap();
+  let content = downloader.fetch("myg2p.ver2.0.txt", url);
 
-  let total_size = response.content_length().unwrap();
-
-  let progress_bar = ProgressBar::new(total_size);
-  progress_bar.set_style(
-    ProgressStyle::with_template(
-      "[*] downloading {wide_bar} {bytes}/{total_bytes} ({eta})",
-    )
-    .unwrap(),
-  );
-
-  let mut content = Vec::new();
-  let mut downloaded_size = 0;
-  let mut buffer = [0; 8192]; // 8KB buffer
-
-  loop
-  {
-    let bytes_read = response.read(&mut buffer).unwrap();
-    if bytes_read == 0
-    {
-      break;
-    }
-    content.extend_from_slice(&buffer[.. bytes_read]);
-    downloaded_size += bytes_read as u64;
-    progress_bar.set_position(downloaded_size);
-    std::thread::sleep(std::time::Duration::from_millis(5));
-  }
-
-  let content = String::from_utf8(content)
+  String::from_utf8(content)
     .unwrap()
     .replace("စျ", "ဈ")
     .replace("မိစ်ဆာ", "မိစ္ဆာ")
-    .replace("ဒိဋ်ဌိ", "ဒိဋ္ဌိ");
-
-  progress_bar.finish();
-  println!("[*] done downloading the original dictionary");
-  std::fs::write(&dict_path, &content).unwrap();
-  content
+    .replace("ဒိဋ်ဌိ", "ဒိဋ္ဌိ")
 }
 
 /// Evaluate JS code in Deno runtime.
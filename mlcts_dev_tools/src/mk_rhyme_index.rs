@@ -0,0 +1,104 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs::File;
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use dataset::load_g2p_mlcts_dict;
+use indicatif::{ProgressBar, ProgressStyle};
+use mlcts_generator::metrics::syllable_lines;
+
+mod dataset;
+
+/// CLI options
+#[derive(Parser)]
+struct ClapOptions
+{
+  /// A word list with a Myanmar word on each line, instead of the G2P
+  /// dictionary
+  #[arg(short, long)]
+  words: Option<PathBuf>,
+
+  /// Group rimes which only differ in their tones
+  #[arg(long)]
+  ignore_tone: bool,
+}
+
+fn main()
+{
+  let cli_options = ClapOptions::parse();
+  let words = match &cli_options.words
+  {
+    Some(path) => load_word_list(path),
+    None => load_g2p_mlcts_dict()
+      .into_iter()
+      .map(|row| row.myanmar_word)
+      .collect(),
+  };
+
+  let progress_bar = ProgressBar::new(words.len() as u64);
+  progress_bar.set_style(
+    ProgressStyle::with_template("[*] indexing {wide_bar} {pos}/{len}")
+      .unwrap(),
+  );
+
+  println!("[*] indexing the rimes of {} words", words.len());
+  let mut index = BTreeMap::<String, BTreeSet<String>>::new();
+  let mut skipped = 0;
+  for word in words
+  {
+    progress_bar.inc(1);
+    // a word rhymes with the rime of its last syllable
+    let last = syllable_lines(&word).into_iter().flatten().last();
+    let Some(syllable) = last
+    else
+    {
+      skipped += 1;
+      continue;
+    };
+
+    let rime = if cli_options.ignore_tone
+    {
+      syllable.tone_key().rime()
+    }
+    else
+    {
+      syllable.rime()
+    };
+    index.entry(rime.to_mlcts()).or_default().insert(word);
+  }
+  progress_bar.finish();
+
+  let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("assets")
+    .join("rhyme-index.json");
+  let file = File::create(&path).unwrap();
+  serde_json::to_writer_pretty(file, &index).unwrap();
+
+  println!(
+    "[+] wrote {} rimes to {} ({} words without syllables skipped)",
+    index.len(),
+    path.display(),
+    skipped
+  );
+}
+
+/// Load the non-empty lines of a word list.
+///
+/// # Arguments
+///
+/// * `path` - The path of the word list.
+///
+/// # Returns
+///
+/// The trimmed words.
+fn load_word_list(path: &Path) -> Vec<String>
+{
+  std::fs::read_to_string(path)
+    .unwrap()
+    .lines()
+    .map(str::trim)
+    .filter(|line| !line.is_empty())
+    .map(str::to_string)
+    .collect()
+}
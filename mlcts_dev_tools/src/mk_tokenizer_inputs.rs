@@ -1,18 +1,9 @@
 use std::collections::HashSet;
 use std::path::Path;
 
-#[derive(Debug, serde::Deserialize)]
-pub struct MyG2pMlcTsRow
-{
-  #[allow(dead_code)]
-  myanmar_word: String,
-  #[allow(dead_code)]
-  mlcts_romanization: String,
-  #[allow(dead_code)]
-  syllable_count: usize,
-  myanmar_syllables: String,
-  mlcts_syllables: String,
-}
+use dataset::{load_g2p_mlcts_dict, MyG2pMlcTsRow};
+
+mod dataset;
 
 fn main()
 {
@@ -363,19 +354,3 @@ fn extract_consonant_from_mlcts(
     (capitalize_first_letter(&without_vowel), None)
   }
 }
-
-/// Load the G2P MLCTS dictionary.
-///
-/// # Returns
-///
-/// A vector of rows from the G2P MLCTS dictionary.
-fn load_g2p_mlcts_dict() -> Vec<MyG2pMlcTsRow>
-{
-  let path = Path::new(env!("CARGO_MANIFEST_DIR"))
-    .join("..")
-    .join("assets")
-    .join("myg2p-dict-mlcts.csv");
-
-  let mut rdr = csv::Reader::from_path(path).unwrap();
-  rdr.deserialize().into_iter().map(|r| r.unwrap()).collect()
-}
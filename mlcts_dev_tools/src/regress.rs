@@ -0,0 +1,265 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use dataset::{load_g2p_mlcts_dict, MyG2pMlcTsRow};
+use indicatif::{ProgressBar, ProgressStyle};
+use mlcts_generator::{mlcts_from_myanmar, split_syllables};
+
+mod dataset;
+
+/// CLI options
+#[derive(Parser)]
+struct ClapOptions
+{
+  /// Number of examples to print for each category
+  #[arg(short, long, default_value_t = 3)]
+  examples: usize,
+  /// Replace the committed snapshot with the new one after reviewing the
+  /// differences
+  #[arg(long)]
+  accept: bool,
+}
+
+/// A word whose conversion differs from the snapshot.
+struct Change
+{
+  myanmar_word: String,
+  /// The conversion in the snapshot, `None` if the word is new.
+  before: Option<String>,
+  /// The current conversion, `None` if the word is removed.
+  after: Option<String>,
+}
+
+fn main()
+{
+  let cli_options = ClapOptions::parse();
+
+  let dict = load_g2p_mlcts_dict();
+  let words = convert_words(&dict);
+
+  let new_path = asset_path("regress-snapshot.new.tsv");
+  std::fs::write(&new_path, to_tsv(&words)).unwrap();
+  println!("[*] new snapshot is written to {}", new_path.display());
+
+  let snapshot_path = asset_path("regress-snapshot.tsv");
+  let snapshot = match std::fs::read_to_string(&snapshot_path)
+  {
+    Ok(content) => from_tsv(&content),
+    Err(_) =>
+    {
+      println!("[*] no snapshot at {}", snapshot_path.display());
+      BTreeMap::new()
+    }
+  };
+
+  let changes = compare(&snapshot, &words);
+  print_summary(&changes, words.len(), cli_options.examples);
+
+  let changed = changes.values().map(Vec::len).sum::<usize>();
+  if cli_options.accept
+  {
+    std::fs::rename(&new_path, &snapshot_path).unwrap();
+    println!("[*] snapshot is updated, commit it with the change");
+  }
+  else if changed > 0
+  {
+    println!("[*] review the changes and run again with --accept");
+    std::process::exit(1);
+  }
+}
+
+/// Convert every word of the dictionary.
+///
+/// # Arguments
+///
+/// * `dict` - The G2P MLCTS dictionary.
+///
+/// # Returns
+///
+/// The MLCTS of every unique word, sorted by the word.
+fn convert_words(dict: &[MyG2pMlcTsRow]) -> BTreeMap<String, String>
+{
+  let progress_bar = ProgressBar::new(dict.len() as u64);
+  progress_bar.set_style(
+    ProgressStyle::with_template("[*] converting {wide_bar} {pos}/{len}")
+      .unwrap(),
+  );
+
+  let mut words = BTreeMap::new();
+  for row in dict
+  {
+    let mlcts = mlcts_from_myanmar(&row.myanmar_word);
+    words.insert(row.myanmar_word.clone(), mlcts);
+    progress_bar.inc(1);
+  }
+  progress_bar.finish();
+  words
+}
+
+/// Write the conversions as a snapshot, one `word\tmlcts` per line.
+///
+/// # Arguments
+///
+/// * `words` - The conversions.
+///
+/// # Returns
+///
+/// The content of the snapshot file.
+fn to_tsv(words: &BTreeMap<String, String>) -> String
+{
+  words
+    .iter()
+    .map(|(word, mlcts)| format!("{}\t{}\n", word, mlcts))
+    .collect()
+}
+
+/// Read a snapshot written by [`to_tsv`].
+///
+/// # Arguments
+///
+/// * `content` - The content of the snapshot file.
+///
+/// # Returns
+///
+/// The conversions in the snapshot.
+fn from_tsv(content: &str) -> BTreeMap<String, String>
+{
+  content
+    .lines()
+    .filter_map(|line| line.split_once('\t'))
+    .map(|(word, mlcts)| (word.to_string(), mlcts.to_string()))
+    .collect()
+}
+
+/// Compare the conversions with the snapshot.
+///
+/// # Arguments
+///
+/// * `snapshot` - The conversions in the snapshot.
+/// * `words` - The current conversions.
+///
+/// # Returns
+///
+/// The changes grouped by category.
+fn compare(
+  snapshot: &BTreeMap<String, String>,
+  words: &BTreeMap<String, String>,
+) -> BTreeMap<&'static str, Vec<Change>>
+{
+  let mut changes: BTreeMap<&'static str, Vec<Change>> = BTreeMap::new();
+  for (word, after) in words
+  {
+    let before = snapshot.get(word);
+    if before == Some(after)
+    {
+      continue;
+    }
+    changes
+      .entry(categorize(word, before.map(String::as_str), after))
+      .or_default()
+      .push(Change {
+        myanmar_word: word.clone(),
+        before: before.cloned(),
+        after: Some(after.clone()),
+      });
+  }
+  for (word, before) in snapshot
+  {
+    if !words.contains_key(word)
+    {
+      changes.entry("removed").or_default().push(Change {
+        myanmar_word: word.clone(),
+        before: Some(before.clone()),
+        after: None,
+      });
+    }
+  }
+  changes
+}
+
+/// Categorize a change so that regressions stand out from fixes.
+///
+/// # Arguments
+///
+/// * `word` - The Myanmar word.
+/// * `before` - The conversion in the snapshot, `None` if the word is new.
+/// * `after` - The current conversion.
+///
+/// # Returns
+///
+/// The category name.
+fn categorize(word: &str, before: Option<&str>, after: &str) -> &'static str
+{
+  // Myanmar characters left in the output were not read
+  let is_read = |mlcts: &str| mlcts.is_ascii();
+  let syllables = |mlcts: &str| mlcts.split_whitespace().count();
+
+  match before
+  {
+    None => "added",
+    Some(before) if is_read(before) && !is_read(after) => "no longer read",
+    Some(before) if !is_read(before) && is_read(after) => "now read",
+    Some(before) if syllables(before) != syllables(after) =>
+    {
+      match syllables(after) == split_syllables(word).len()
+      {
+        true => "syllable count fixed",
+        false => "syllable count changed",
+      }
+    }
+    _ => "romanization changed",
+  }
+}
+
+/// Print the categorized summary, largest categories first.
+///
+/// # Arguments
+///
+/// * `changes` - Changes grouped by category.
+/// * `total` - Total number of converted words.
+/// * `examples` - Number of examples to print for each category.
+fn print_summary(
+  changes: &BTreeMap<&'static str, Vec<Change>>,
+  total: usize,
+  examples: usize,
+)
+{
+  let mut categories = changes.iter().collect::<Vec<_>>();
+  categories.sort_by_key(|(_, changes)| std::cmp::Reverse(changes.len()));
+
+  let changed = categories.iter().map(|(_, c)| c.len()).sum::<usize>();
+  println!(
+    "[*] {} of {} words differ from the snapshot",
+    changed, total
+  );
+
+  for (category, changes) in categories
+  {
+    println!("\n{} ({})", category, changes.len());
+    for change in changes.iter().take(examples)
+    {
+      println!(
+        "  {} before: {:?} after: {:?}",
+        change.myanmar_word, change.before, change.after
+      );
+    }
+  }
+}
+
+/// Get the path of a file in the assets directory.
+///
+/// # Arguments
+///
+/// * `name` - The file name.
+///
+/// # Returns
+///
+/// The path of the file.
+fn asset_path(name: &str) -> PathBuf
+{
+  Path::new(env!("CARGO_MANIFEST_DIR"))
+    .join("..")
+    .join("assets")
+    .join(name)
+}
@@ -0,0 +1,70 @@
+//! Generates the rhyme lookup of the syllable parser from `rhymes.csv`.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::path::Path;
+
+fn main()
+{
+  println!("cargo:rerun-if-changed=rhymes.csv");
+  let csv = std::fs::read_to_string("rhymes.csv").unwrap();
+
+  let mut arms = String::new();
+  let mut table = String::new();
+  let mut seen = BTreeSet::new();
+  let rows = csv
+    .lines()
+    .enumerate()
+    .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+    // the header
+    .skip(1);
+  for (number, line) in rows
+  {
+    let fields = line.split(',').collect::<Vec<_>>();
+    let [signs, basic, virama, tone] = fields[..]
+    else
+    {
+      panic!("rhymes.csv:{}: expected 4 fields", number + 1);
+    };
+    assert!(
+      seen.insert(signs),
+      "rhymes.csv:{}: {} is listed twice",
+      number + 1,
+      signs
+    );
+
+    let option = |kind, value| match value
+    {
+      "" => "None".to_string(),
+      value => format!("Some({}::{})", kind, value),
+    };
+    let vowel = format!(
+      "Vowel::new(BasicVowel::{}, {}, {})",
+      basic,
+      option("Virama", virama),
+      option("Tone", tone)
+    );
+    let _ = writeln!(arms, "    {:?} => Some({}),", signs, vowel);
+    let _ = writeln!(table, "  ({:?}, {}),", signs, vowel);
+  }
+
+  let code = format!(
+    "/// Get the vowel of the whole rhyme after a consonant.\n\
+     ///\n\
+     /// # Arguments\n\
+     ///\n\
+     /// * `signs` - The signs from the vowel to the end of the syllable.\n\
+     ///\n\
+     /// # Returns\n\
+     ///\n\
+     /// The vowel or `None` if the signs are not a rhyme of `rhymes.csv`.\n\
+     fn rhyme_from_signs(signs: &str) -> Option<Vowel>\n\
+     {{\n  match signs\n  {{\n{}    _ => None,\n  }}\n}}\n\n\
+     /// The rhymes of `rhymes.csv` and their vowels.\n\
+     #[cfg(test)]\n\
+     const RHYMES: &[(&str, Vowel)] = &[\n{}];\n",
+    arms, table
+  );
+  let out_dir = std::env::var("OUT_DIR").unwrap();
+  std::fs::write(Path::new(&out_dir).join("rhymes.rs"), code).unwrap();
+}
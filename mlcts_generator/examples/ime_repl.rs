@@ -0,0 +1,61 @@
+//! A toy input method: type MLCTS and get the closest Myanmar words of a
+//! word list, one word per line in the list.
+//!
+//! ```sh
+//! cargo run --example ime_repl -- words.txt
+//! > kyaung:
+//! 1. ကျောင်း (kyaung:)
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+use mlcts_generator::fuzzy::FuzzyIndex;
+use mlcts_generator::mlcts_from_myanmar;
+
+/// The number of candidates shown.
+const CANDIDATES: usize = 5;
+
+/// The maximum number of edits between the input and a candidate.
+const MAX_EDITS: usize = 2;
+
+fn main() -> io::Result<()>
+{
+  let Some(path) = std::env::args().nth(1)
+  else
+  {
+    eprintln!("usage: ime_repl <word list>");
+    std::process::exit(2);
+  };
+  let content = std::fs::read_to_string(path)?;
+  let words = content
+    .lines()
+    .map(str::trim)
+    .filter(|word| !word.is_empty())
+    .collect::<Vec<_>>();
+  // romanize and index the word list once instead of on every lookup
+  let index =
+    FuzzyIndex::new(words.iter().map(|word| mlcts_from_myanmar(word)));
+
+  let mut stdout = io::stdout().lock();
+  let mut lines = io::stdin().lock().lines();
+  loop
+  {
+    write!(stdout, "> ")?;
+    stdout.flush()?;
+    let Some(line) = lines.next().transpose()?
+    else
+    {
+      return Ok(());
+    };
+
+    let matches = index.fuzzy_match(line.trim(), MAX_EDITS);
+    if matches.is_empty()
+    {
+      writeln!(stdout, "no candidates")?;
+    }
+    for (i, m) in matches.iter().take(CANDIDATES).enumerate()
+    {
+      writeln!(stdout, "{}. {} ({})", i + 1, words[m.index], m.candidate)?;
+    }
+  }
+}
@@ -0,0 +1,27 @@
+//! Romanize a Myanmar text file, or the standard input if no file is given,
+//! and write the MLCTS to the standard output as it is converted.
+//!
+//! ```sh
+//! cargo run --example romanize_file -- input.txt
+//! echo "မင်္ဂလာပါ" | cargo run --example romanize_file
+//! ```
+
+use std::fs::File;
+use std::io::{self, BufReader};
+
+use mlcts_generator::stream::StreamConverter;
+
+fn main() -> io::Result<()>
+{
+  let converter = StreamConverter::default();
+  let mut stdout = io::stdout().lock();
+  match std::env::args().nth(1)
+  {
+    Some(path) =>
+    {
+      let file = BufReader::new(File::open(path)?);
+      converter.convert_reader(file, &mut stdout)
+    }
+    None => converter.convert_reader(io::stdin().lock(), &mut stdout),
+  }
+}
@@ -0,0 +1,36 @@
+//! Split Myanmar text from the standard input into words with a word list,
+//! one word per line in the list, and print the words of every line with
+//! their MLCTS.
+//!
+//! ```sh
+//! echo "မြန်မာကျောင်းသားပါ" | cargo run --example segment_words -- words.txt
+//! ```
+
+use std::io::{self, BufRead};
+
+use mlcts_generator::mlcts_from_myanmar;
+use mlcts_generator::normalize::normalize_myanmar;
+use mlcts_generator::segment::{segment_words, DictionarySegmenter};
+
+fn main() -> io::Result<()>
+{
+  let Some(path) = std::env::args().nth(1)
+  else
+  {
+    eprintln!("usage: segment_words <word list>");
+    std::process::exit(2);
+  };
+  let words = std::fs::read_to_string(path)?;
+  let segmenter = DictionarySegmenter::new(words.lines());
+
+  for line in io::stdin().lock().lines()
+  {
+    let line = normalize_myanmar(&line?).into_owned();
+    let words = segment_words(&line, &segmenter)
+      .into_iter()
+      .map(|(word, ..)| format!("{} ({})", word, mlcts_from_myanmar(word)))
+      .collect::<Vec<_>>();
+    println!("{}", words.join(" | "));
+  }
+  Ok(())
+}
@@ -0,0 +1,324 @@
+//! Safe corrections of common typing and encoding errors in Myanmar text.
+//!
+//! This is the companion of [`crate::validate`]. Only corrections which do
+//! not change how the text is read are applied.
+
+use crate::validate::{is_base, is_dependent_sign, is_medial, is_vowel_sign};
+
+/// Represents the kind of a correction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FixKind
+{
+  /// A sign typed twice in a row is removed.
+  DuplicatedSign,
+  /// Medials and vowel signs are reordered into the encoding order, e.g.
+  /// ကေျ into ကျေ or ုိ into ို. Signs typed twice in the reordered run are
+  /// removed by the same correction.
+  ReorderedSigns,
+  /// A look-alike character is replaced, e.g. ၀ (zero) with ဝ (wa).
+  Confusable,
+}
+
+/// Represents a correction applied to the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fix
+{
+  /// The kind of the correction.
+  pub kind: FixKind,
+  /// The start position of the replaced text in the input.
+  pub start: usize,
+  /// The length of the replaced text in the input.
+  pub len: usize,
+  /// The replacement text.
+  pub replacement: String,
+}
+
+impl Fix
+{
+  /// Creates a new correction.
+  ///
+  /// # Arguments
+  ///
+  /// * `kind` - The kind of the correction.
+  /// * `start` - The start position of the replaced text in the input.
+  /// * `len` - The length of the replaced text in the input.
+  /// * `replacement` - The replacement text.
+  ///
+  /// # Returns
+  ///
+  /// A new correction.
+  pub fn new(kind: FixKind, start: usize, len: usize, replacement: &str)
+    -> Self
+  {
+    Self {
+      kind,
+      start,
+      len,
+      replacement: replacement.to_string(),
+    }
+  }
+}
+
+/// Apply safe corrections to Myanmar text:
+///
+/// * remove signs typed twice in a row (e.g. ကားး)
+/// * move medials in front of vowel signs (e.g. ကေျာ) and put ိ in front of ု
+/// * replace ၀ (zero) and ၇ (seven) used as ဝ and ရ, and ဥ used as ဉ in ဉ်
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::autofix::{autofix_myanmar, FixKind};
+///
+/// let (fixed, fixes) = autofix_myanmar("ကေျာင်းသားး");
+/// assert_eq!(fixed, "ကျောင်းသား");
+/// assert_eq!(fixes[0].kind, FixKind::ReorderedSigns);
+/// assert_eq!(fixes[1].kind, FixKind::DuplicatedSign);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to fix.
+///
+/// # Returns
+///
+/// The fixed text and the applied corrections in the order they appear in
+/// the input.
+pub fn autofix_myanmar(input: &str) -> (String, Vec<Fix>)
+{
+  let chars = input.char_indices().collect::<Vec<_>>();
+  let mut output = String::with_capacity(input.len());
+  let mut fixes = Vec::new();
+  let mut i = 0;
+
+  while i < chars.len()
+  {
+    let (pos, current) = chars[i];
+    let current = match confusable(&chars, i)
+    {
+      Some(replacement) =>
+      {
+        let fix = replacement.encode_utf8(&mut [0; 4]).to_string();
+        fixes.push(Fix::new(
+          FixKind::Confusable,
+          pos,
+          current.len_utf8(),
+          &fix,
+        ));
+        replacement
+      }
+      None => current,
+    };
+    output.push(current);
+    i += 1;
+
+    if is_base(current)
+    {
+      let run_start = i;
+      while i < chars.len() && is_dependent_sign(chars[i].1)
+      {
+        i += 1;
+      }
+      fix_signs(&chars[run_start .. i], &mut output, &mut fixes);
+    }
+  }
+
+  (output, fixes)
+}
+
+/// Fix the dependent signs following a base and append them to the output.
+/// The run of signs gets either a single reordering correction or a
+/// correction for each duplicated sign, so the corrections never overlap.
+///
+/// # Arguments
+///
+/// * `signs` - The signs following a base with their positions.
+/// * `output` - The output to append to.
+/// * `fixes` - The applied corrections.
+fn fix_signs(signs: &[(usize, char)], output: &mut String, fixes: &mut Vec<Fix>)
+{
+  let mut deduped = Vec::with_capacity(signs.len());
+  let mut duplicates = Vec::new();
+  for (i, &(pos, sign)) in signs.iter().enumerate()
+  {
+    if i > 0 && signs[i - 1].1 == sign
+    {
+      duplicates.push(Fix::new(
+        FixKind::DuplicatedSign,
+        pos,
+        sign.len_utf8(),
+        "",
+      ));
+      continue;
+    }
+    deduped.push(sign);
+  }
+
+  // only medials and vowel signs right after the base are reordered, the
+  // medial of a contracted syllable like ယောက်ျား follows an asat
+  let prefix_len = deduped
+    .iter()
+    .take_while(|c| is_medial(**c) || is_vowel_sign(**c))
+    .count();
+  let (prefix, rest) = deduped.split_at(prefix_len);
+
+  let mut medials = prefix
+    .iter()
+    .filter(|c| is_medial(**c))
+    .copied()
+    .collect::<Vec<_>>();
+  medials.sort();
+  let vowel_signs = prefix
+    .iter()
+    .filter(|c| is_vowel_sign(**c))
+    .copied()
+    .collect::<String>()
+    .replace("ုိ", "ို");
+
+  let mut fixed = medials.into_iter().collect::<String>();
+  fixed.push_str(&vowel_signs);
+  if fixed != prefix.iter().collect::<String>()
+  {
+    // the whole run of signs is logged as replaced, including the removed
+    // duplicates
+    let start = signs[0].0;
+    let (last_pos, last) = signs[signs.len() - 1];
+    let replacement = fixed.clone() + &rest.iter().collect::<String>();
+    fixes.push(Fix::new(
+      FixKind::ReorderedSigns,
+      start,
+      last_pos + last.len_utf8() - start,
+      &replacement,
+    ));
+  }
+  else
+  {
+    fixes.append(&mut duplicates);
+  }
+
+  output.push_str(&fixed);
+  output.extend(rest);
+}
+
+/// Check if a character might be replaced as a look-alike character, which
+/// depends on the characters around it.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character might be replaced, `false` otherwise.
+pub(crate) fn is_confusable(c: char) -> bool
+{
+  matches!(c, '၀' | '၇' | 'ဥ')
+}
+
+/// Get the replacement of a look-alike character.
+///
+/// # Arguments
+///
+/// * `chars` - Characters of the input with their positions.
+/// * `i` - The index of the character to check.
+///
+/// # Returns
+///
+/// The replacement or `None` if the character is not a confusable.
+fn confusable(chars: &[(usize, char)], i: usize) -> Option<char>
+{
+  let at = |i: usize| chars.get(i).map(|(_, c)| *c);
+  let prev = i.checked_sub(1).and_then(at);
+  let next = at(i + 1);
+
+  // ၀ and ၇ followed by a sign or by a final consonant are used as letters,
+  // but never next to another digit. A word before them is not enough, since
+  // numbers are often written right after a word, e.g. အခန်း၇
+  let is_digit = |c: char| matches!(c, '၀' ..= '၉');
+  let used_as_letter = || {
+    !prev.is_some_and(is_digit)
+      && !next.is_some_and(is_digit)
+      && (next.is_some_and(is_dependent_sign)
+        || (next.is_some_and(|c| matches!(c, 'က' ..= 'အ'))
+          && at(i + 2) == Some('်')))
+  };
+
+  match chars[i].1
+  {
+    '၀' if used_as_letter() => Some('ဝ'),
+    '၇' if used_as_letter() => Some('ရ'),
+    'ဥ' if next == Some('်') => Some('ဉ'),
+    _ => None,
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::FixKind;
+  use crate::validate::validate_myanmar;
+
+  #[test]
+  fn test_autofix_myanmar()
+  {
+    let input = "မင်္ဂလာပါ ယောက်ျား ၁၀၇ ကိုယ့်";
+    assert_eq!(super::autofix_myanmar(input), (input.to_string(), vec![]));
+
+    let cases = [
+      ("ကေျာင်း", "ကျောင်း", FixKind::ReorderedSigns),
+      ("ကုိ", "ကို", FixKind::ReorderedSigns),
+      ("ကွျန်", "ကျွန်", FixKind::ReorderedSigns),
+      ("ကားး", "ကား", FixKind::DuplicatedSign),
+      ("၀ယ်", "ဝယ်", FixKind::Confusable),
+      ("၀ါ", "ဝါ", FixKind::Confusable),
+      ("ပု၀ါ", "ပုဝါ", FixKind::Confusable),
+      ("၇ာ", "ရာ", FixKind::Confusable),
+      ("ကဥ်", "ကဉ်", FixKind::Confusable),
+    ];
+    for (input, expected, kind) in cases
+    {
+      let (fixed, fixes) = super::autofix_myanmar(input);
+      assert_eq!(fixed, expected);
+      assert_eq!(fixes.len(), 1, "{}", input);
+      assert_eq!(fixes[0].kind, kind, "{}", input);
+      assert!(validate_myanmar(&fixed).is_empty(), "{}", input);
+    }
+
+    // a reordered run with a duplicated sign is a single correction
+    let (fixed, fixes) = super::autofix_myanmar("ကေျျာ");
+    assert_eq!(fixed, "ကျော");
+    assert_eq!(fixes.len(), 1);
+    assert_eq!(fixes[0].kind, FixKind::ReorderedSigns);
+    assert_eq!((fixes[0].start, fixes[0].len), (3, 12));
+    assert_eq!(fixes[0].replacement, "ျော");
+
+    // the corrections are in order and do not overlap
+    let input = "ကားး ကေျျာ ၀ါး";
+    let (fixed, fixes) = super::autofix_myanmar(input);
+    assert_eq!(fixed, "ကား ကျော ဝါး");
+    assert!(fixes
+      .windows(2)
+      .all(|f| f[0].start + f[0].len <= f[1].start));
+    let mut patched = input.to_string();
+    for fix in fixes.iter().rev()
+    {
+      patched.replace_range(fix.start .. fix.start + fix.len, &fix.replacement);
+    }
+    assert_eq!(patched, fixed);
+  }
+
+  #[test]
+  fn test_autofix_myanmar_keeps_numbers()
+  {
+    for input in ["အခန်း၇", "နံပါတ်၇၀", "ကား၀", "၁၀၇", "၇၀ါ", "အခန်း ၇ ခု"]
+    {
+      assert_eq!(
+        super::autofix_myanmar(input),
+        (input.to_string(), vec![]),
+        "{}",
+        input
+      );
+    }
+  }
+}
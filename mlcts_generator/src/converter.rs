@@ -0,0 +1,631 @@
+//! A reusable converter configured once with a builder.
+//!
+//! [`Converter`] bundles the conversion options, the user dictionary, the
+//! segmenter and the custom stages, so they don't have to be passed to every
+//! call. It is cheap to clone and can be shared between threads. The free
+//! functions such as [`crate::mlcts_from_myanmar`] stay available with the
+//! default options.
+
+use std::sync::Arc;
+use std::{fmt, io};
+
+use crate::dictionary::UserDictionary;
+use crate::join::TokenJoiner;
+use crate::names::conventional_into;
+use crate::normalize::{strip_bom, Newline, ZeroWidth};
+use crate::pipeline::{Pipeline, Stage};
+use crate::segment::Segmenter;
+use crate::{prepare_input, ConvertCounters, ConvertOptions, Fallback, Homophones};
+
+/// The romanization written by a [`Converter`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Scheme
+{
+  /// MLCTS, see [`crate::mlcts_from_myanmar`].
+  #[default]
+  Mlcts,
+  /// The conventional spelling of names, see
+  /// [`crate::names::conventional_from_myanmar`]. Only the user dictionary,
+  /// autofix and the handling of zero-width characters apply to it, the
+  /// other options are rejected by [`ConverterBuilder::build`].
+  Conventional,
+}
+
+/// Converts Myanmar text with the options it was built with.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::converter::Converter;
+/// use mlcts_generator::Fallback;
+///
+/// let converter = Converter::builder()
+///   .western_punctuation(true)
+///   .fallback(Fallback::Replace("?".to_string()))
+///   .build()
+///   .unwrap();
+///
+/// assert_eq!(converter.convert("မင်္ဂလာပါ။"), "mangga. la pa.");
+/// assert_eq!(converter.convert("ာ ကား"), "? ka:");
+///
+/// let (_, counters) = converter.analyze("ာ ကား");
+/// assert_eq!(counters.syllables, 1);
+/// assert_eq!(counters.fallbacks, 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Converter
+{
+  scheme: Scheme,
+  pipeline: Arc<Pipeline>,
+  max_input_len: Option<usize>,
+}
+
+/// The input given to [`Converter::try_convert`] is longer than the limit of
+/// the converter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputTooLarge
+{
+  /// The length of the input in bytes.
+  pub len: usize,
+  /// The maximum length in bytes.
+  pub max: usize,
+}
+
+impl fmt::Display for InputTooLarge
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    write!(f, "input of {} bytes, at most {}", self.len, self.max)
+  }
+}
+
+impl std::error::Error for InputTooLarge
+{
+}
+
+/// An option set on a [`ConverterBuilder`] does not apply to the scheme of
+/// the converter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedOption
+{
+  /// The scheme of the converter.
+  pub scheme: Scheme,
+  /// The name of the builder method setting the option.
+  pub option: &'static str,
+}
+
+impl fmt::Display for UnsupportedOption
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    write!(
+      f,
+      "{} does not apply to the {:?} scheme",
+      self.option, self.scheme
+    )
+  }
+}
+
+impl std::error::Error for UnsupportedOption
+{
+}
+
+impl Converter
+{
+  /// Start building a converter with the default options.
+  ///
+  /// # Returns
+  ///
+  /// A new converter builder.
+  pub fn builder() -> ConverterBuilder
+  {
+    ConverterBuilder::default()
+  }
+
+  /// Convert Myanmar text.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The Myanmar text to convert.
+  ///
+  /// # Returns
+  ///
+  /// Space-separated syllables in the scheme of the converter.
+  pub fn convert(&self, input: &str) -> String
+  {
+    match self.scheme
+    {
+      Scheme::Mlcts => self.pipeline.convert(input),
+      Scheme::Conventional => self.analyze(input).0,
+    }
+  }
+
+  /// Convert Myanmar text if it is not longer than the limit set with
+  /// [`ConverterBuilder::max_input_len`], e.g. for a server endpoint.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_generator::converter::{Converter, InputTooLarge};
+  ///
+  /// let converter = Converter::builder().max_input_len(20).build().unwrap();
+  ///
+  /// assert_eq!(converter.try_convert("မြန်မာ").unwrap(), "mran ma");
+  /// assert_eq!(
+  ///   converter.try_convert("မြန်မာစာ"),
+  ///   Err(InputTooLarge { len: 24, max: 20 })
+  /// );
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The Myanmar text to convert.
+  ///
+  /// # Returns
+  ///
+  /// Space-separated syllables in the scheme of the converter, or the error
+  /// if the input is too large.
+  pub fn try_convert(&self, input: &str) -> Result<String, InputTooLarge>
+  {
+    match self.max_input_len
+    {
+      Some(max) if input.len() > max => Err(InputTooLarge {
+        len: input.len(),
+        max,
+      }),
+      _ => Ok(self.convert(input)),
+    }
+  }
+
+  /// Convert Myanmar text and write it to the given [`io::Write`] writer,
+  /// e.g. a file or the standard output.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The Myanmar text to convert.
+  /// * `w` - The writer to write to.
+  ///
+  /// # Returns
+  ///
+  /// The result of writing.
+  pub fn convert_to<W: io::Write>(
+    &self,
+    input: &str,
+    w: &mut W,
+  ) -> io::Result<()>
+  {
+    if self.scheme != Scheme::Mlcts
+    {
+      return w.write_all(self.convert(input).as_bytes());
+    }
+
+    let mut result = Ok(());
+    let mut joiner = TokenJoiner::new(&self.pipeline.options);
+    self.pipeline.convert_into(input, &mut joiner, |joiner| {
+      if result.is_ok()
+      {
+        result = joiner.flush(|output| w.write_all(output.as_bytes()));
+      }
+    });
+    result
+  }
+
+  /// Convert Myanmar text and count what happened during the conversion.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The Myanmar text to convert.
+  ///
+  /// # Returns
+  ///
+  /// The converted text and the counters of the conversion.
+  pub fn analyze(&self, input: &str) -> (String, ConvertCounters)
+  {
+    let options = &self.pipeline.options;
+    if self.scheme == Scheme::Conventional
+    {
+      let mut counters = ConvertCounters::default();
+      let input = prepare_input(strip_bom(input), options, &mut counters);
+      let output =
+        conventional_into(&input, &options.dictionary, &mut counters);
+      return (output, counters);
+    }
+
+    let mut joiner = TokenJoiner::new(options);
+    let counters = self.pipeline.convert_into(input, &mut joiner, |_| ());
+    (joiner.take_output(), counters)
+  }
+}
+
+/// Builds a [`Converter`].
+#[derive(Debug, Default)]
+pub struct ConverterBuilder
+{
+  scheme: Scheme,
+  pipeline: Pipeline,
+  max_input_len: Option<usize>,
+}
+
+impl ConverterBuilder
+{
+  /// Set the romanization scheme, MLCTS by default.
+  ///
+  /// # Arguments
+  ///
+  /// * `scheme` - The romanization scheme.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the scheme.
+  pub fn scheme(mut self, scheme: Scheme) -> Self
+  {
+    self.scheme = scheme;
+    self
+  }
+
+  /// Set how tokens which are not syllables are written, see
+  /// [`ConvertOptions::fallback`].
+  ///
+  /// # Arguments
+  ///
+  /// * `fallback` - The fallback policy.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the fallback policy.
+  pub fn fallback(mut self, fallback: Fallback) -> Self
+  {
+    self.pipeline.options.fallback = fallback;
+    self
+  }
+
+  /// Set how letters which share the MLCTS of another letter are written,
+  /// see [`ConvertOptions::homophones`].
+  ///
+  /// # Arguments
+  ///
+  /// * `homophones` - The homophone policy.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the homophone policy.
+  pub fn homophones(mut self, homophones: Homophones) -> Self
+  {
+    self.pipeline.options.homophones = homophones;
+    self
+  }
+
+  /// Set how zero-width characters are handled, see
+  /// [`ConvertOptions::zero_width`].
+  ///
+  /// # Arguments
+  ///
+  /// * `zero_width` - The handling of zero-width characters.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the handling of zero-width characters.
+  pub fn zero_width(mut self, zero_width: ZeroWidth) -> Self
+  {
+    self.pipeline.options.zero_width = zero_width;
+    self
+  }
+
+  /// Set if the input is corrected first, see [`ConvertOptions::autofix`].
+  ///
+  /// # Arguments
+  ///
+  /// * `autofix` - If the input is corrected.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the autofix flag.
+  pub fn autofix(mut self, autofix: bool) -> Self
+  {
+    self.pipeline.options.autofix = autofix;
+    self
+  }
+
+  /// Set if sentences are capitalized, see
+  /// [`ConvertOptions::capitalize_sentences`].
+  ///
+  /// # Arguments
+  ///
+  /// * `capitalize_sentences` - If sentences are capitalized.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the capitalization flag.
+  pub fn capitalize_sentences(mut self, capitalize_sentences: bool) -> Self
+  {
+    self.pipeline.options.capitalize_sentences = capitalize_sentences;
+    self
+  }
+
+  /// Set if punctuation is attached to words, see
+  /// [`ConvertOptions::western_punctuation`].
+  ///
+  /// # Arguments
+  ///
+  /// * `western_punctuation` - If punctuation is attached to words.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the punctuation flag.
+  pub fn western_punctuation(mut self, western_punctuation: bool) -> Self
+  {
+    self.pipeline.options.western_punctuation = western_punctuation;
+    self
+  }
+
+  /// Set how line breaks are written, see [`ConvertOptions::newline`].
+  ///
+  /// # Arguments
+  ///
+  /// * `newline` - The line break handling.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the line break handling.
+  pub fn newline(mut self, newline: Newline) -> Self
+  {
+    self.pipeline.options.newline = newline;
+    self
+  }
+
+  /// Set the user dictionary, see [`ConvertOptions::dictionary`].
+  ///
+  /// # Arguments
+  ///
+  /// * `dictionary` - The user dictionary.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the user dictionary.
+  pub fn dictionary(mut self, dictionary: UserDictionary) -> Self
+  {
+    self.pipeline.options.dictionary = dictionary;
+    self
+  }
+
+  /// Add a custom stage after the stages added so far, see
+  /// [`crate::pipeline`].
+  ///
+  /// # Arguments
+  ///
+  /// * `stage` - The stage to add.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the stage.
+  pub fn stage(mut self, stage: impl Stage + 'static) -> Self
+  {
+    self.pipeline = self.pipeline.stage(stage);
+    self
+  }
+
+  /// Set the segmenter splitting runs of syllables into words, see
+  /// [`crate::pipeline`].
+  ///
+  /// # Arguments
+  ///
+  /// * `segmenter` - The segmenter.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the segmenter.
+  pub fn segmenter(mut self, segmenter: impl Segmenter + 'static) -> Self
+  {
+    self.pipeline = self.pipeline.segmenter(segmenter);
+    self
+  }
+
+  /// Set the maximum length of the input in bytes accepted by
+  /// [`Converter::try_convert`]. There is no limit by default.
+  ///
+  /// # Arguments
+  ///
+  /// * `max_input_len` - The maximum length in bytes.
+  ///
+  /// # Returns
+  ///
+  /// The builder with the limit.
+  pub fn max_input_len(mut self, max_input_len: usize) -> Self
+  {
+    self.max_input_len = Some(max_input_len);
+    self
+  }
+
+  /// Build the converter.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_generator::converter::{Converter, Scheme, UnsupportedOption};
+  ///
+  /// let converter = Converter::builder()
+  ///   .scheme(Scheme::Conventional)
+  ///   .autofix(true)
+  ///   .build()
+  ///   .unwrap();
+  /// assert_eq!(converter.convert("ကေျာ်ထွန်း"), "Kyaw Htun");
+  ///
+  /// let error = Converter::builder()
+  ///   .scheme(Scheme::Conventional)
+  ///   .capitalize_sentences(true)
+  ///   .build()
+  ///   .unwrap_err();
+  /// assert_eq!(error.option, "capitalize_sentences");
+  /// ```
+  ///
+  /// # Returns
+  ///
+  /// A new converter, or the error if an option does not apply to the
+  /// scheme.
+  pub fn build(self) -> Result<Converter, UnsupportedOption>
+  {
+    if self.scheme == Scheme::Conventional
+    {
+      let options = &self.pipeline.options;
+      let unsupported = [
+        ("fallback", options.fallback != Fallback::default()),
+        ("homophones", options.homophones != Homophones::default()),
+        ("newline", options.newline != Newline::default()),
+        ("capitalize_sentences", options.capitalize_sentences),
+        ("western_punctuation", options.western_punctuation),
+        ("stage", !self.pipeline.stages.is_empty()),
+        ("segmenter", self.pipeline.segmenter.is_some()),
+      ];
+      if let Some((option, _)) = unsupported.iter().find(|(_, set)| *set)
+      {
+        return Err(UnsupportedOption {
+          scheme: self.scheme,
+          option,
+        });
+      }
+    }
+
+    Ok(Converter {
+      scheme: self.scheme,
+      pipeline: Arc::new(self.pipeline),
+      max_input_len: self.max_input_len,
+    })
+  }
+}
+
+impl From<ConvertOptions> for Converter
+{
+  fn from(options: ConvertOptions) -> Self
+  {
+    Self {
+      scheme: Scheme::Mlcts,
+      pipeline: Arc::new(Pipeline::new(options)),
+      max_input_len: None,
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::{Converter, Scheme, UnsupportedOption};
+  use crate::dictionary::UserDictionary;
+  use crate::normalize::ZeroWidth;
+  use crate::pipeline::StageToken;
+  use crate::segment::{DictionarySegmenter, RunSegmenter};
+  use crate::{mlcts_from_myanmar, mlcts_from_myanmar_with_counters, Fallback};
+
+  #[test]
+  fn test_converter()
+  {
+    let input = "ကျွန်တော် ရန်ကုန်မှာ နေပါတယ်။ ာ";
+
+    // the default converter is the same as the free functions
+    let converter = Converter::default();
+    assert_eq!(converter.convert(input), mlcts_from_myanmar(input));
+    assert_eq!(
+      converter.analyze(input),
+      mlcts_from_myanmar_with_counters(input, &Default::default())
+    );
+
+    let mut output = Vec::new();
+    converter.convert_to(input, &mut output).unwrap();
+    assert_eq!(
+      String::from_utf8(output).unwrap(),
+      mlcts_from_myanmar(input)
+    );
+
+    let mut dictionary = UserDictionary::new();
+    dictionary.substitute("ရန်ကုန်", "Yangon");
+    let converter = Converter::builder()
+      .dictionary(dictionary)
+      .capitalize_sentences(true)
+      .western_punctuation(true)
+      .stage(|tokens: &mut [StageToken]| {
+        for token in tokens.iter_mut().filter(|t| t.text == "နေ")
+        {
+          token.output = "***".to_string();
+        }
+      })
+      .build()
+      .unwrap();
+    let expected = "Kywan tau Yangon hma *** pa tai. ာ";
+    assert_eq!(converter.convert(input), expected);
+
+    // clones share the same options
+    let clone = converter.clone();
+    assert_eq!(clone.analyze(input).0, expected);
+    assert_eq!(clone.analyze(input).1.terms, 1);
+
+    // the stages see the words of the segmenter
+    let converter = Converter::builder()
+      .segmenter(DictionarySegmenter::new(["ကျွန်တော်", "နေပါ"]))
+      .stage(|tokens: &mut [StageToken]| {
+        for token in tokens.iter_mut().filter(|t| !t.word_start)
+        {
+          token.output.insert(0, '-');
+        }
+      })
+      .build()
+      .unwrap();
+    assert_eq!(
+      converter.convert(input),
+      "kywan -tau   ran kun hma   ne -pa tai .   ာ"
+    );
+
+    let converter = Converter::builder()
+      .scheme(Scheme::Conventional)
+      .build()
+      .unwrap();
+    assert_eq!(converter.convert("အောင်ဆန်းစုကြည်"), "Aung San Su Kyi");
+  }
+
+  #[test]
+  fn test_converter_conventional()
+  {
+    let converter = Converter::builder()
+      .scheme(Scheme::Conventional)
+      .autofix(true)
+      .zero_width(ZeroWidth::Strip)
+      .build()
+      .unwrap();
+    let (output, counters) = converter.analyze("ကေျာ်\u{200B}ထွန်း။ ာ");
+    assert_eq!(output, "Kyaw Htun. ာ");
+    assert_eq!(counters.syllables, 2);
+    assert_eq!(counters.repaired, 1);
+    assert_eq!(counters.fallbacks, 1);
+    assert_eq!(converter.convert("ကေျာ်ထွန်း"), "Kyaw Htun");
+
+    let mut output = Vec::new();
+    converter.convert_to("ကေျာ်ထွန်း", &mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), "Kyaw Htun");
+
+    // options of the MLCTS output are rejected
+    let error = Converter::builder()
+      .scheme(Scheme::Conventional)
+      .autofix(true)
+      .stage(|_: &mut [StageToken]| ())
+      .build()
+      .unwrap_err();
+    assert_eq!(
+      error,
+      UnsupportedOption {
+        scheme: Scheme::Conventional,
+        option: "stage",
+      }
+    );
+    let error = Converter::builder()
+      .scheme(Scheme::Conventional)
+      .fallback(Fallback::Replace("?".to_string()))
+      .build()
+      .unwrap_err();
+    assert_eq!(error.option, "fallback");
+    let error = Converter::builder()
+      .scheme(Scheme::Conventional)
+      .segmenter(RunSegmenter)
+      .build()
+      .unwrap_err();
+    assert_eq!(error.option, "segmenter");
+  }
+}
@@ -0,0 +1,390 @@
+//! User-supplied terms which are not converted syllable by syllable.
+//!
+//! A term is either passed through as it is (brand names, scripture titles,
+//! etc.) or replaced with a given romanization. Terms are matched at
+//! syllable boundaries, the longest term wins. With a
+//! [`Segmenter`](crate::segment::Segmenter) in the
+//! [`Pipeline`](crate::pipeline::Pipeline), terms are only matched at the
+//! word boundaries it reports, so a term is not found inside a longer word.
+//!
+//! Terms can be added one by one or loaded from TSV files with one term per
+//! line: the Myanmar term, a tab and the romanization. A line with just the
+//! term passes it through. Empty lines and lines starting with `#` are
+//! skipped.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::Bound;
+use std::path::Path;
+
+use crate::normalize::{normalize_myanmar, strip_bom};
+
+/// Represents an error while loading a TSV file.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum TsvError
+{
+  /// The file cannot be read.
+  Io(std::io::Error),
+  /// The line (starting from 1) has more than two columns.
+  InvalidLine(usize),
+}
+
+impl fmt::Display for TsvError
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match self
+    {
+      Self::Io(e) => write!(f, "cannot read the file: {}", e),
+      Self::InvalidLine(line) =>
+      {
+        write!(f, "line {} has more than two columns", line)
+      }
+    }
+  }
+}
+
+impl std::error::Error for TsvError
+{
+}
+
+impl From<std::io::Error> for TsvError
+{
+  fn from(e: std::io::Error) -> Self
+  {
+    Self::Io(e)
+  }
+}
+
+/// A list of protected terms and their replacements.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::dictionary::UserDictionary;
+/// use mlcts_generator::{mlcts_from_myanmar_with_options, ConvertOptions};
+///
+/// let mut dictionary = UserDictionary::new();
+/// dictionary.protect("ပိဋကတ်").substitute("ရန်ကုန်", "Yangon");
+///
+/// let mut options = ConvertOptions::default();
+/// options.dictionary = dictionary;
+/// assert_eq!(
+///   mlcts_from_myanmar_with_options("ရန်ကုန်မြို့ ပိဋကတ်", &options),
+///   "Yangon mrui.   ပိဋကတ်"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserDictionary
+{
+  /// Normalized terms and their replacements. `None` means the term is
+  /// passed through.
+  terms: BTreeMap<String, Option<String>>,
+  /// The byte length of the longest term.
+  longest: usize,
+}
+
+impl UserDictionary
+{
+  /// Create an empty dictionary.
+  ///
+  /// # Returns
+  ///
+  /// An empty dictionary.
+  pub fn new() -> Self
+  {
+    Self::default()
+  }
+
+  /// Pass the term through without converting it.
+  ///
+  /// # Arguments
+  ///
+  /// * `term` - The Myanmar term.
+  ///
+  /// # Returns
+  ///
+  /// The dictionary itself for chaining.
+  pub fn protect(&mut self, term: &str) -> &mut Self
+  {
+    self.insert(term, None)
+  }
+
+  /// Replace the term with the given romanization instead of converting it.
+  ///
+  /// # Arguments
+  ///
+  /// * `term` - The Myanmar term.
+  /// * `romanization` - The romanization to use.
+  ///
+  /// # Returns
+  ///
+  /// The dictionary itself for chaining.
+  pub fn substitute(&mut self, term: &str, romanization: &str) -> &mut Self
+  {
+    self.insert(term, Some(romanization.to_string()))
+  }
+
+  /// Create a dictionary from the content of a TSV file.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_generator::dictionary::UserDictionary;
+  ///
+  /// let tsv = "# names\nအောင်ဆန်း\tAung San\nပိဋကတ်\n";
+  /// let dictionary = UserDictionary::from_tsv(tsv).unwrap();
+  ///
+  /// assert_eq!(dictionary.len(), 2);
+  /// assert!(UserDictionary::from_tsv("က\tka\textra").is_err());
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `content` - The content of the TSV file.
+  ///
+  /// # Returns
+  ///
+  /// The dictionary or the line which cannot be parsed.
+  pub fn from_tsv(content: &str) -> Result<Self, TsvError>
+  {
+    let mut dictionary = Self::new();
+    dictionary.extend_from_tsv(content)?;
+    Ok(dictionary)
+  }
+
+  /// Load a dictionary from a TSV file.
+  ///
+  /// # Arguments
+  ///
+  /// * `path` - The path of the TSV file.
+  ///
+  /// # Returns
+  ///
+  /// The dictionary or the reason it cannot be loaded.
+  pub fn load_tsv(path: impl AsRef<Path>) -> Result<Self, TsvError>
+  {
+    Self::from_tsv(&std::fs::read_to_string(path)?)
+  }
+
+  /// Add the terms of a TSV file to the dictionary. Terms already in the
+  /// dictionary are overridden.
+  ///
+  /// # Arguments
+  ///
+  /// * `content` - The content of the TSV file.
+  ///
+  /// # Returns
+  ///
+  /// The dictionary itself or the line which cannot be parsed. Terms before
+  /// the invalid line are added.
+  pub fn extend_from_tsv(
+    &mut self,
+    content: &str,
+  ) -> Result<&mut Self, TsvError>
+  {
+    for (i, line) in strip_bom(content).lines().enumerate()
+    {
+      if line.trim().is_empty() || line.starts_with('#')
+      {
+        continue;
+      }
+
+      let columns = line.split('\t').collect::<Vec<_>>();
+      match columns[..]
+      {
+        [term] => self.protect(term),
+        [term, romanization] => self.substitute(term, romanization.trim()),
+        _ => return Err(TsvError::InvalidLine(i + 1)),
+      };
+    }
+    Ok(self)
+  }
+
+  /// Get the number of terms.
+  ///
+  /// # Returns
+  ///
+  /// The number of terms.
+  pub fn len(&self) -> usize
+  {
+    self.terms.len()
+  }
+
+  /// Check if the dictionary has no terms.
+  ///
+  /// # Returns
+  ///
+  /// `true` if there are no terms, `false` otherwise.
+  pub fn is_empty(&self) -> bool
+  {
+    self.terms.is_empty()
+  }
+
+  /// Add a term, replacing the previous entry of the same term.
+  ///
+  /// # Arguments
+  ///
+  /// * `term` - The Myanmar term.
+  /// * `replacement` - The replacement or `None` to pass it through.
+  ///
+  /// # Returns
+  ///
+  /// The dictionary itself for chaining.
+  fn insert(&mut self, term: &str, replacement: Option<String>) -> &mut Self
+  {
+    let term = normalize_myanmar(term.trim()).into_owned();
+    if !term.is_empty()
+    {
+      self.longest = self.longest.max(term.len());
+      self.terms.insert(term, replacement);
+    }
+    self
+  }
+
+  /// Get the ends of the prefixes of the input which might be terms, i.e.
+  /// the character boundaries up to the length of the longest term.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The input.
+  ///
+  /// # Returns
+  ///
+  /// The byte positions after the prefixes, the shortest first.
+  fn prefix_ends<'i>(
+    &self,
+    input: &'i str,
+  ) -> impl DoubleEndedIterator<Item = usize> + 'i
+  {
+    let limit = self.longest.min(input.len());
+    (1 ..= limit).filter(move |end| input.is_char_boundary(*end))
+  }
+
+  /// Find the longest term at the start of the input.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The normalized input starting at a syllable or word boundary.
+  /// * `is_boundary` - Checks if a position in the input is a syllable
+  ///   boundary, or a word boundary if the words are segmented.
+  ///
+  /// # Returns
+  ///
+  /// The length of the term and its replacement (the term itself if it is
+  /// passed through).
+  pub(crate) fn longest_match<'d>(
+    &'d self,
+    input: &str,
+    is_boundary: impl Fn(usize) -> bool,
+  ) -> Option<(usize, &'d str)>
+  {
+    self
+      .prefix_ends(input)
+      .rev()
+      .filter(|end| is_boundary(*end))
+      .find_map(|end| self.terms.get_key_value(&input[.. end]))
+      .map(|(term, replacement)| {
+        (term.len(), replacement.as_deref().unwrap_or(term))
+      })
+  }
+
+  /// Check if a term might cross the given position, i.e. a term starts
+  /// before the position and ends after it, or the input ends in the middle
+  /// of a term.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The normalized input.
+  /// * `pos` - The position to check.
+  ///
+  /// # Returns
+  ///
+  /// `true` if a term might cross the position, `false` otherwise.
+  pub(crate) fn crosses(&self, input: &str, pos: usize) -> bool
+  {
+    (pos.saturating_sub(self.longest.saturating_sub(1)) .. pos)
+      .filter(|start| input.is_char_boundary(*start))
+      .any(|start| {
+        let rest = &input[start ..];
+        // a term starting with the rest is the first term from the rest on
+        let continued = self
+          .terms
+          .range::<str, _>((Bound::Included(rest), Bound::Unbounded))
+          .next()
+          .is_some_and(|(term, _)| term.starts_with(rest));
+        continued
+          || self
+            .prefix_ends(rest)
+            .filter(|end| start + end > pos)
+            .any(|end| self.terms.contains_key(&rest[.. end]))
+      })
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use crate::{mlcts_from_myanmar_with_options, ConvertOptions};
+
+  #[test]
+  fn test_user_dictionary()
+  {
+    let mut dictionary = super::UserDictionary::new();
+    dictionary
+      .substitute("ရန်ကုန်", "Yangon")
+      .substitute("ရန်ကုန်မြို့", "Yangon City");
+    assert!(!dictionary.is_empty());
+
+    // a term crosses a position inside it, or the input might continue it
+    let ran = "ရန်".len();
+    assert!(dictionary.crosses("ရန်ကုန်သား", ran));
+    assert!(dictionary.crosses("ရန်ကု", ran));
+    assert!(!dictionary.crosses("ရန်ကုန်သား", "ရန်ကုန်".len()));
+    assert!(!dictionary.crosses("ကားရန်", "ကား".len()));
+
+    let options = ConvertOptions {
+      dictionary,
+      ..Default::default()
+    };
+    let convert = |input| mlcts_from_myanmar_with_options(input, &options);
+
+    // the longest term wins
+    assert_eq!(convert("ရန်ကုန်မြို့"), "Yangon City");
+    assert_eq!(convert("ရန်ကုန်သား"), "Yangon sa:");
+    // terms only match at syllable boundaries
+    assert_eq!(convert("ရန်ကုန်း"), "ran kun:");
+  }
+
+  #[test]
+  fn test_user_dictionary_tsv()
+  {
+    let tsv = "# overrides\r\n\
+      အောင်ဆန်း\tAung San\r\n\
+      \r\n\
+      ပိဋကတ်\r\n";
+    let mut dictionary = super::UserDictionary::from_tsv(tsv).unwrap();
+    assert_eq!(dictionary.len(), 2);
+    assert_eq!(
+      super::UserDictionary::from_tsv(&format!("\u{FEFF}{}", tsv)).unwrap(),
+      dictionary
+    );
+
+    let err = dictionary.extend_from_tsv("ရန်ကုန်\tYangon\nက\tka\tk");
+    assert!(matches!(err, Err(super::TsvError::InvalidLine(2))));
+    assert_eq!(dictionary.len(), 3);
+
+    let options = ConvertOptions {
+      dictionary,
+      ..Default::default()
+    };
+    assert_eq!(
+      mlcts_from_myanmar_with_options("ဗိုလ်ချုပ်အောင်ဆန်း ပိဋကတ်", &options),
+      "buil hkyup Aung San   ပိဋကတ်"
+    );
+
+    let err = super::UserDictionary::load_tsv("does-not-exist.tsv");
+    assert!(matches!(err, Err(super::TsvError::Io(_))));
+  }
+}
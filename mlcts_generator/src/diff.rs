@@ -0,0 +1,309 @@
+//! Syllable-level differences between two Myanmar texts.
+//!
+//! The texts are split with [`crate::split_syllables`] and aligned with the
+//! Levenshtein distance over their syllables, so a wrong syllable is reported
+//! as a single substitution instead of a few changed code points. This is
+//! useful to show OCR corrections or proofreading changes.
+
+use crate::split_syllables;
+
+/// Represents a syllable of one of the compared texts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffSyllable<'i>
+{
+  /// The text of the syllable.
+  pub text: &'i str,
+  /// The start position of the syllable in its text.
+  pub start: usize,
+  /// The length of the syllable in its text.
+  pub len: usize,
+}
+
+/// Represents an edit turning the old text into the new text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Edit<'a, 'b>
+{
+  /// A syllable which is in both texts.
+  Equal(DiffSyllable<'a>, DiffSyllable<'b>),
+  /// A syllable which is only in the new text.
+  Insert(DiffSyllable<'b>),
+  /// A syllable which is only in the old text.
+  Delete(DiffSyllable<'a>),
+  /// A syllable of the old text replaced by a syllable of the new text.
+  Substitute(DiffSyllable<'a>, DiffSyllable<'b>),
+}
+
+/// Align two Myanmar texts syllable by syllable.
+///
+/// Whitespace is not compared, so adding or removing spaces between words is
+/// not an edit. Both texts are expected to be normalized with
+/// [`crate::normalize::normalize_myanmar`].
+///
+/// The syllables both texts start and end with are kept as they are, and
+/// only the syllables between them are aligned. The alignment takes time and
+/// memory proportional to the product of their numbers, so texts with many
+/// scattered changes should be compared in smaller parts (e.g. paragraphs).
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::diff::{diff_myanmar, Edit};
+///
+/// let edits = diff_myanmar("ကျောင်းသားပါ", "ကျောင်းသူ ပါ");
+/// assert_eq!(edits.len(), 3);
+/// assert!(matches!(edits[0], Edit::Equal(..)));
+/// let Edit::Substitute(old, new) = edits[1]
+/// else
+/// {
+///   panic!("expected a substitution");
+/// };
+/// assert_eq!((old.text, old.start), ("သား", 21));
+/// assert_eq!((new.text, new.start), ("သူ", 21));
+/// assert!(matches!(edits[2], Edit::Equal(..)));
+///
+/// let edits = diff_myanmar("မင်္ဂလာ", "မင်္ဂလာပါ");
+/// assert!(matches!(edits.last(), Some(Edit::Insert(s)) if s.text == "ပါ"));
+/// ```
+///
+/// # Arguments
+///
+/// * `old` - The old text.
+/// * `new` - The new text.
+///
+/// # Returns
+///
+/// The edits in the order of the texts, the syllables which are in both
+/// texts included. There are as few insertions, deletions and substitutions
+/// as possible, and as many syllables are kept as possible.
+pub fn diff_myanmar<'a, 'b>(old: &'a str, new: &'b str) -> Vec<Edit<'a, 'b>>
+{
+  let a = syllables(old);
+  let b = syllables(new);
+
+  // the common prefix and suffix are kept, only the rest is aligned
+  let prefix = a
+    .iter()
+    .zip(&b)
+    .take_while(|(x, y)| x.text == y.text)
+    .count();
+  let suffix = a[prefix ..]
+    .iter()
+    .rev()
+    .zip(b[prefix ..].iter().rev())
+    .take_while(|(x, y)| x.text == y.text)
+    .count();
+  let equal = |i: usize, j: usize| Edit::Equal(a[i], b[j]);
+
+  let mut edits = (0 .. prefix).map(|i| equal(i, i)).collect::<Vec<_>>();
+  edits.extend(align(
+    &a[prefix .. a.len() - suffix],
+    &b[prefix .. b.len() - suffix],
+  ));
+  edits.extend(
+    (0 .. suffix)
+      .rev()
+      .map(|k| equal(a.len() - 1 - k, b.len() - 1 - k)),
+  );
+  edits
+}
+
+/// Align two lists of syllables with the fewest edits.
+///
+/// # Arguments
+///
+/// * `a` - The syllables of the old text.
+/// * `b` - The syllables of the new text.
+///
+/// # Returns
+///
+/// The edits in the order of the syllables.
+fn align<'a, 'b>(
+  a: &[DiffSyllable<'a>],
+  b: &[DiffSyllable<'b>],
+) -> Vec<Edit<'a, 'b>>
+{
+  // rows[i][j] is the number of edits between a[.. i] and b[.. j] and how
+  // many of them are substitutions, so that of the alignments with the
+  // fewest edits the one keeping the most syllables is chosen
+  let mut rows = vec![vec![(0, 0); b.len() + 1]; a.len() + 1];
+  for (i, row) in rows.iter_mut().enumerate()
+  {
+    row[0] = (i, 0);
+  }
+  for (j, cell) in rows[0].iter_mut().enumerate()
+  {
+    *cell = (j, 0);
+  }
+  for i in 1 ..= a.len()
+  {
+    for j in 1 ..= b.len()
+    {
+      rows[i][j] = add(rows[i - 1][j], INDEL)
+        .min(add(rows[i][j - 1], INDEL))
+        .min(add(rows[i - 1][j - 1], diagonal(a[i - 1], b[j - 1])));
+    }
+  }
+
+  // walk back from the end
+  let mut edits = Vec::with_capacity(a.len().max(b.len()));
+  let (mut i, mut j) = (a.len(), b.len());
+  while i > 0 || j > 0
+  {
+    if i > 0
+      && j > 0
+      && rows[i][j] == add(rows[i - 1][j - 1], diagonal(a[i - 1], b[j - 1]))
+    {
+      edits.push(match a[i - 1].text == b[j - 1].text
+      {
+        true => Edit::Equal(a[i - 1], b[j - 1]),
+        false => Edit::Substitute(a[i - 1], b[j - 1]),
+      });
+      i -= 1;
+      j -= 1;
+    }
+    else if i > 0 && (j == 0 || rows[i][j] == add(rows[i - 1][j], INDEL))
+    {
+      edits.push(Edit::Delete(a[i - 1]));
+      i -= 1;
+    }
+    else
+    {
+      edits.push(Edit::Insert(b[j - 1]));
+      j -= 1;
+    }
+  }
+
+  edits.reverse();
+  edits
+}
+
+/// The cost of an insertion or a deletion, as (edits, substitutions).
+const INDEL: (usize, usize) = (1, 0);
+
+/// Add two costs.
+///
+/// # Arguments
+///
+/// * `a` - The first cost.
+/// * `b` - The second cost.
+///
+/// # Returns
+///
+/// The sum of the costs.
+fn add(a: (usize, usize), b: (usize, usize)) -> (usize, usize)
+{
+  (a.0 + b.0, a.1 + b.1)
+}
+
+/// Get the cost of aligning two syllables with each other.
+///
+/// # Arguments
+///
+/// * `a` - The syllable of the old text.
+/// * `b` - The syllable of the new text.
+///
+/// # Returns
+///
+/// No cost if the syllables are equal, a substitution otherwise.
+fn diagonal(a: DiffSyllable<'_>, b: DiffSyllable<'_>) -> (usize, usize)
+{
+  match a.text == b.text
+  {
+    true => (0, 0),
+    false => (1, 1),
+  }
+}
+
+/// Split a text into the syllables to compare.
+///
+/// # Arguments
+///
+/// * `input` - The text to split.
+///
+/// # Returns
+///
+/// The syllables of the text without whitespace.
+fn syllables(input: &str) -> Vec<DiffSyllable<'_>>
+{
+  split_syllables(input)
+    .into_iter()
+    .filter(|(text, ..)| !text.trim().is_empty())
+    .map(|(text, start, len)| DiffSyllable { text, start, len })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::{diff_myanmar, Edit};
+
+  /// Write the edits in a short form to compare them.
+  fn describe(edits: &[Edit]) -> Vec<String>
+  {
+    edits
+      .iter()
+      .map(|edit| match edit
+      {
+        Edit::Equal(a, _) => format!("={}", a.text),
+        Edit::Insert(b) => format!("+{}", b.text),
+        Edit::Delete(a) => format!("-{}", a.text),
+        Edit::Substitute(a, b) => format!("{}>{}", a.text, b.text),
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_diff_myanmar()
+  {
+    assert!(diff_myanmar("", "").is_empty());
+    assert_eq!(describe(&diff_myanmar("", "ကား")), ["+ကား"]);
+    assert_eq!(describe(&diff_myanmar("ကား", "")), ["-ကား"]);
+
+    // whitespace is not compared
+    assert_eq!(
+      describe(&diff_myanmar("ရန်ကုန် မြို့", "ရန်ကုန်မြို့")),
+      ["=ရန်", "=ကုန်", "=မြို့"]
+    );
+
+    // a stacked syllable is compared as a whole
+    assert_eq!(
+      describe(&diff_myanmar("မင်္ဂလာပါ", "မင်ဂလာပါ")),
+      ["+မင်", "မင်္ဂ>ဂ", "=လာ", "=ပါ"]
+    );
+
+    assert_eq!(
+      describe(&diff_myanmar(
+        "ကျွန်တော်က ကျောင်းသားပါ။",
+        "ကျွန်တော် ကျောင်းဆရာ ပါ။"
+      )),
+      ["=ကျွန်", "=တော်", "-က", "=ကျောင်း", "+ဆ", "သား>ရာ", "=ပါ", "=။"]
+    );
+
+    // the positions point into the compared texts
+    let old = "ကား ကား";
+    let new = "ကား ကာ";
+    let edits = diff_myanmar(old, new);
+    let Edit::Substitute(a, b) = edits[1]
+    else
+    {
+      panic!("expected a substitution, got {:?}", edits);
+    };
+    assert_eq!(&old[a.start .. a.start + a.len], "ကား");
+    assert_eq!(&new[b.start .. b.start + b.len], "ကာ");
+
+    // long texts with a single change only align the changed syllable
+    let old = "ကား ".repeat(20_000);
+    let new = format!("{}ကာ {}", "ကား ".repeat(10_000), "ကား ".repeat(9_999));
+    let edits = diff_myanmar(&old, &new);
+    assert_eq!(edits.len(), 20_000);
+    assert_eq!(
+      edits
+        .iter()
+        .filter(|e| !matches!(e, Edit::Equal(..)))
+        .count(),
+      1
+    );
+    assert!(matches!(edits[10_000], Edit::Substitute(..)));
+  }
+}
@@ -0,0 +1,110 @@
+//! Curated ground truth of the conversion, for the test suites of crates
+//! built on this one (IMEs, segmenters, etc.). Enable the `test-fixtures`
+//! feature, usually in `[dev-dependencies]`, to use it.
+//!
+//! The fixtures are curated from the cases this crate is tested with, and
+//! `fixtures::tests` checks them against the generator. Run
+//! `cargo make test-fixtures` to check them and the example below.
+//!
+//! ```
+//! use mlcts_generator::{fixtures, mlcts_from_myanmar};
+//!
+//! for fixture in fixtures::syllables().chain(fixtures::words())
+//! {
+//!   assert_eq!(mlcts_from_myanmar(fixture.myanmar), fixture.mlcts);
+//! }
+//! ```
+
+/// Single syllables and their MLCTS.
+const SYLLABLES: &str = include_str!("../fixtures/syllables.tsv");
+/// Words and their MLCTS.
+const WORDS: &str = include_str!("../fixtures/words.tsv");
+
+/// Myanmar text and its expected MLCTS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixture
+{
+  /// The normalized Myanmar text.
+  pub myanmar: &'static str,
+  /// The expected output of [`crate::mlcts_from_myanmar`].
+  pub mlcts: &'static str,
+}
+
+/// Get the single-syllable fixtures, covering the rhymes, medials, stacked
+/// and contracted syllables.
+///
+/// # Returns
+///
+/// An iterator over the fixtures.
+pub fn syllables() -> impl Iterator<Item = Fixture>
+{
+  parse(SYLLABLES)
+}
+
+/// Get the word fixtures, whose syllables are separated by spaces in MLCTS.
+///
+/// # Returns
+///
+/// An iterator over the fixtures.
+pub fn words() -> impl Iterator<Item = Fixture>
+{
+  parse(WORDS)
+}
+
+/// Parse a fixture file with a `myanmar<TAB>mlcts` pair on each line.
+/// Empty lines and lines starting with `#` are skipped.
+///
+/// # Arguments
+///
+/// * `content` - The content of the fixture file.
+///
+/// # Returns
+///
+/// An iterator over the fixtures.
+fn parse(content: &'static str) -> impl Iterator<Item = Fixture>
+{
+  content
+    .lines()
+    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+    .filter_map(|line| line.split_once('\t'))
+    .map(|(myanmar, mlcts)| Fixture { myanmar, mlcts })
+}
+
+#[cfg(test)]
+mod tests
+{
+  use crate::{get_token, mlcts_from_myanmar, TokenKind};
+
+  #[test]
+  fn test_fixtures()
+  {
+    assert!(super::syllables().count() > 30);
+    assert!(super::words().count() > 10);
+
+    for fixture in super::syllables()
+    {
+      let tokens = get_token(fixture.myanmar).collect::<Vec<_>>();
+      assert_eq!(tokens.len(), 1, "{}", fixture.myanmar);
+      assert!(
+        matches!(tokens[0].kind, TokenKind::Syllable(_)),
+        "{}",
+        fixture.myanmar
+      );
+      assert_eq!(
+        mlcts_from_myanmar(fixture.myanmar),
+        fixture.mlcts,
+        "{}",
+        fixture.myanmar
+      );
+    }
+    for fixture in super::words()
+    {
+      assert_eq!(
+        mlcts_from_myanmar(fixture.myanmar),
+        fixture.mlcts,
+        "{}",
+        fixture.myanmar
+      );
+    }
+  }
+}
@@ -0,0 +1,304 @@
+//! Fuzzy matching of Myanmar words over their MLCTS forms.
+//!
+//! Words are compared with the Damerau-Levenshtein distance (optimal string
+//! alignment) of their MLCTS forms. Adding, removing or replacing a tone
+//! mark (`:` or `.`) costs half an edit since it is the most common spelling
+//! mistake.
+
+use crate::mlcts_from_myanmar;
+
+/// Represents a candidate matching the query.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FuzzyMatch<'c>
+{
+  /// The matching candidate.
+  pub candidate: &'c str,
+  /// The index of the candidate in the candidates.
+  pub index: usize,
+  /// The number of edits between the query and the candidate.
+  pub distance: f32,
+}
+
+/// Find the candidates within the given number of edits of the query. Every
+/// candidate is converted to MLCTS on each call, use a [`FuzzyIndex`] to look
+/// up many queries in the same candidates.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::fuzzy::fuzzy_match;
+///
+/// let candidates = ["ကျောင်း", "ကျောင်းသား", "ချောင်း"];
+/// let matches = fuzzy_match("ကျောင်", &candidates, 2);
+///
+/// assert_eq!(matches.len(), 2);
+/// assert_eq!(matches[0].candidate, "ကျောင်း");
+/// assert_eq!(matches[0].distance, 0.5);
+/// assert_eq!(matches[1].candidate, "ချောင်း");
+/// ```
+///
+/// # Arguments
+///
+/// * `query` - The word to look for, in Myanmar script or MLCTS.
+/// * `candidates` - The words to look in, in Myanmar script or MLCTS.
+/// * `max_edits` - The maximum number of edits.
+///
+/// # Returns
+///
+/// The matching candidates, the closest first. Candidates with the same
+/// distance keep their order.
+pub fn fuzzy_match<'c, S>(
+  query: &str,
+  candidates: &'c [S],
+  max_edits: usize,
+) -> Vec<FuzzyMatch<'c>>
+where
+  S: AsRef<str>,
+{
+  let candidates = candidates.iter().map(|candidate| {
+    let candidate = candidate.as_ref();
+    (candidate, fold(candidate))
+  });
+  find(query, candidates, max_edits)
+}
+
+/// Candidates for fuzzy matching whose MLCTS forms are computed once, to
+/// look up many queries in the same word list.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::fuzzy::FuzzyIndex;
+///
+/// let index = FuzzyIndex::new(["ကျောင်း", "ကျောင်းသား", "ချောင်း"]);
+///
+/// let matches = index.fuzzy_match("ကျောင်", 2);
+/// assert_eq!(matches.len(), 2);
+/// assert_eq!(matches[0].candidate, "ကျောင်း");
+/// assert_eq!(index.fuzzy_match("ချောင်း", 0)[0].index, 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FuzzyIndex
+{
+  /// The candidates with their folded MLCTS forms.
+  candidates: Vec<(String, Vec<char>)>,
+}
+
+impl FuzzyIndex
+{
+  /// Create an index of candidates.
+  ///
+  /// # Arguments
+  ///
+  /// * `candidates` - The words to look in, in Myanmar script or MLCTS.
+  ///
+  /// # Returns
+  ///
+  /// A new index.
+  pub fn new<S: AsRef<str>>(candidates: impl IntoIterator<Item = S>) -> Self
+  {
+    let candidates = candidates
+      .into_iter()
+      .map(|candidate| {
+        let candidate = candidate.as_ref();
+        (candidate.to_string(), fold(candidate))
+      })
+      .collect();
+    Self { candidates }
+  }
+
+  /// Find the candidates within the given number of edits of the query, like
+  /// [`fuzzy_match`].
+  ///
+  /// # Arguments
+  ///
+  /// * `query` - The word to look for, in Myanmar script or MLCTS.
+  /// * `max_edits` - The maximum number of edits.
+  ///
+  /// # Returns
+  ///
+  /// The matching candidates, the closest first. Candidates with the same
+  /// distance keep their order.
+  pub fn fuzzy_match(
+    &self,
+    query: &str,
+    max_edits: usize,
+  ) -> Vec<FuzzyMatch<'_>>
+  {
+    let candidates = self
+      .candidates
+      .iter()
+      .map(|(candidate, folded)| (candidate.as_str(), folded.as_slice()));
+    find(query, candidates, max_edits)
+  }
+}
+
+/// Find the candidates within the given number of edits of the query.
+///
+/// # Arguments
+///
+/// * `query` - The word to look for, in Myanmar script or MLCTS.
+/// * `candidates` - The candidates with their folded MLCTS forms.
+/// * `max_edits` - The maximum number of edits.
+///
+/// # Returns
+///
+/// The matching candidates, the closest first.
+fn find<'c, F: AsRef<[char]>>(
+  query: &str,
+  candidates: impl Iterator<Item = (&'c str, F)>,
+  max_edits: usize,
+) -> Vec<FuzzyMatch<'c>>
+{
+  let query = fold(query);
+  let mut matches = candidates
+    .enumerate()
+    .filter_map(|(index, (candidate, folded))| {
+      let distance = distance(&query, folded.as_ref());
+      (distance <= max_edits as f32).then_some(FuzzyMatch {
+        candidate,
+        index,
+        distance,
+      })
+    })
+    .collect::<Vec<_>>();
+
+  matches.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+  matches
+}
+
+/// Get the MLCTS form of a word to compare.
+///
+/// # Arguments
+///
+/// * `input` - The word in Myanmar script or MLCTS.
+///
+/// # Returns
+///
+/// The lowercase MLCTS characters of the word.
+fn fold(input: &str) -> Vec<char>
+{
+  let mlcts = if input.contains(|c| matches!(c, '\u{1000}' ..= '\u{109F}'))
+  {
+    mlcts_from_myanmar(input)
+  }
+  else
+  {
+    input.to_string()
+  };
+
+  mlcts
+    .chars()
+    .filter(|c| !c.is_whitespace())
+    .flat_map(char::to_lowercase)
+    .collect()
+}
+
+/// Get the cost of adding, removing or replacing a character.
+///
+/// # Arguments
+///
+/// * `c` - The character.
+///
+/// # Returns
+///
+/// Half an edit for tone marks, a full edit otherwise.
+fn cost(c: char) -> f32
+{
+  match c
+  {
+    ':' | '.' => 0.5,
+    _ => 1.0,
+  }
+}
+
+/// Get the Damerau-Levenshtein distance (optimal string alignment) between
+/// two words.
+///
+/// # Arguments
+///
+/// * `a` - The first word.
+/// * `b` - The second word.
+///
+/// # Returns
+///
+/// The number of edits.
+fn distance(a: &[char], b: &[char]) -> f32
+{
+  // rows[i][j] is the distance between a[.. i] and b[.. j]
+  let mut rows = vec![vec![0.0; b.len() + 1]; a.len() + 1];
+  for i in 1 ..= a.len()
+  {
+    rows[i][0] = rows[i - 1][0] + cost(a[i - 1]);
+  }
+  for j in 1 ..= b.len()
+  {
+    rows[0][j] = rows[0][j - 1] + cost(b[j - 1]);
+  }
+
+  for i in 1 ..= a.len()
+  {
+    for j in 1 ..= b.len()
+    {
+      let substitution = match (a[i - 1], b[j - 1])
+      {
+        (x, y) if x == y => 0.0,
+        (x, y) => cost(x).max(cost(y)),
+      };
+      let mut d = (rows[i - 1][j] + cost(a[i - 1]))
+        .min(rows[i][j - 1] + cost(b[j - 1]))
+        .min(rows[i - 1][j - 1] + substitution);
+
+      if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1]
+      {
+        d = d.min(rows[i - 2][j - 2] + 1.0);
+      }
+      rows[i][j] = d;
+    }
+  }
+
+  rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests
+{
+  #[test]
+  fn test_distance()
+  {
+    let distance =
+      |a: &str, b: &str| super::distance(&super::fold(a), &super::fold(b));
+
+    assert_eq!(distance("ka:", "ka:"), 0.0);
+    assert_eq!(distance("ka:", "ka."), 0.5);
+    assert_eq!(distance("ka:", "ka"), 0.5);
+    assert_eq!(distance("kaung", "kuang"), 1.0);
+    assert_eq!(distance("kaung", "kang"), 1.0);
+    assert_eq!(distance("ကား", "ka:"), 0.0);
+    assert_eq!(distance("", "ka:"), 2.5);
+  }
+
+  #[test]
+  fn test_fuzzy_match()
+  {
+    let candidates = vec![
+      "မင်္ဂလာပါ".to_string(),
+      "mangga. la".to_string(),
+      "မင်္ဂလာ".to_string(),
+    ];
+    let matches = super::fuzzy_match("mangga: la", &candidates, 1);
+    assert_eq!(
+      matches.iter().map(|m| m.index).collect::<Vec<_>>(),
+      vec![1, 2]
+    );
+    assert_eq!(matches[0].distance, 0.5);
+
+    assert!(super::fuzzy_match("ကျောင်း", &candidates, 2).is_empty());
+
+    let index = super::FuzzyIndex::new(&candidates);
+    assert_eq!(
+      index.fuzzy_match("mangga: la", 1),
+      super::fuzzy_match("mangga: la", &candidates, 1)
+    );
+  }
+}
@@ -0,0 +1,676 @@
+//! Tagging of grammatical particles and sentence types in Myanmar text.
+//!
+//! Particles are a closed class of words, so they are found with a word
+//! list over the words of a [`Segmenter`]. Particles are often written
+//! attached to the word in front of them, so they are also split off the end
+//! of the words, e.g. ကျောင်းသားတွေကို is ကျောင်းသား, တွေ and ကို.
+//!
+//! The type of a sentence is told by the particle it ends with, e.g. a
+//! sentence ending with ပါတယ် is a statement and one ending with ပါသလား is a
+//! question.
+
+use crate::segment::{word_ends, Segmenter};
+use crate::{get_token, TokenKind};
+
+/// Represents the kind of a grammatical particle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Particle
+{
+  /// Marks the subject, e.g. က.
+  Subject,
+  /// Marks the topic, e.g. ကတော့.
+  Topic,
+  /// Marks the object, e.g. ကို.
+  Object,
+  /// Marks a place or a time, e.g. မှာ or တွင်.
+  Location,
+  /// Marks where something comes from, e.g. မှ or ကနေ.
+  Source,
+  /// Marks a plural, e.g. တွေ or များ.
+  Plural,
+  /// Marks the owner, e.g. ရဲ့.
+  Possessive,
+  /// Joins nouns, e.g. နဲ့ or နှင့်.
+  Conjunction,
+  /// Means "also", e.g. လည်း.
+  Additive,
+  /// Means "only", e.g. ပဲ or သာ.
+  Restrictive,
+  /// Makes the verb polite, e.g. ပါ.
+  Polite,
+  /// Ends a statement, e.g. တယ်, သည် or မယ်.
+  Statement,
+  /// Ends a question, e.g. လား or လဲ.
+  Question,
+  /// Ends a negative sentence, e.g. ဘူး.
+  Negative,
+}
+
+/// Particles and their kinds.
+const PARTICLES: &[(&str, Particle)] = &[
+  ("က", Particle::Subject),
+  ("ကတော့", Particle::Topic),
+  ("ကို", Particle::Object),
+  ("မှာ", Particle::Location),
+  ("တွင်", Particle::Location),
+  ("မှ", Particle::Source),
+  ("ကနေ", Particle::Source),
+  ("တွေ", Particle::Plural),
+  ("များ", Particle::Plural),
+  ("ရဲ့", Particle::Possessive),
+  ("နဲ့", Particle::Conjunction),
+  ("နှင့်", Particle::Conjunction),
+  ("လည်း", Particle::Additive),
+  ("ပဲ", Particle::Restrictive),
+  ("သာ", Particle::Restrictive),
+  ("ပါ", Particle::Polite),
+  ("တယ်", Particle::Statement),
+  ("သည်", Particle::Statement),
+  ("မယ်", Particle::Statement),
+  ("မည်", Particle::Statement),
+  ("ပြီ", Particle::Statement),
+  ("လား", Particle::Question),
+  ("လဲ", Particle::Question),
+  ("သလား", Particle::Question),
+  ("နည်း", Particle::Question),
+  ("ဘူး", Particle::Negative),
+];
+
+/// The most syllables of a particle.
+const MAX_PARTICLE_SYLLABLES: usize = 2;
+
+/// Sentence-final particles, split into syllables, and the types of the
+/// sentences ending with them, longest first. Bare ပါ and နဲ့ or နှင့် are
+/// told apart by [`imperative_tail`].
+const SENTENCE_ENDINGS: &[(&[&str], SentenceType)] = &[
+  (&["ပါ", "သ", "လား"], SentenceType::Question),
+  (&["ပါ", "သ", "နည်း"], SentenceType::Question),
+  (&["ပါ", "တယ်"], SentenceType::Statement),
+  (&["ပါ", "သည်"], SentenceType::Statement),
+  (&["ပါ", "မယ်"], SentenceType::Statement),
+  (&["ပါ", "မည်"], SentenceType::Statement),
+  (&["ပါ", "ပြီ"], SentenceType::Statement),
+  (&["ပါ", "ဘူး"], SentenceType::Statement),
+  (&["သ", "လား"], SentenceType::Question),
+  (&["သ", "နည်း"], SentenceType::Question),
+  (&["တယ်"], SentenceType::Statement),
+  (&["သည်"], SentenceType::Statement),
+  (&["မယ်"], SentenceType::Statement),
+  (&["မည်"], SentenceType::Statement),
+  (&["ပြီ"], SentenceType::Statement),
+  (&["ဘူး"], SentenceType::Statement),
+  (&["၏"], SentenceType::Statement),
+  (&["လား"], SentenceType::Question),
+  (&["လဲ"], SentenceType::Question),
+  (&["နည်း"], SentenceType::Question),
+  (&["စမ်း"], SentenceType::Imperative),
+  (&["ကြ"], SentenceType::Imperative),
+  (&["စို့"], SentenceType::Imperative),
+];
+
+/// Verbs following the main verb, e.g. ကြ in သွားကြပါ.
+const AUXILIARIES: &[&str] = &["ကြ", "ပေး", "လိုက်", "စမ်း", "ထား"];
+
+/// Common verbs of requests, the verbs bare ပါ is told to follow, e.g. ထိုင်
+/// in ထိုင်ပါ. Other words ending with ပါ, e.g. ကျောင်းသားပါ or
+/// မင်္ဂလာပါ, are not imperatives.
+const VERBS: &[&str] = &[
+  "ထိုင်",
+  "ထ",
+  "သွား",
+  "စား",
+  "သောက်",
+  "ယူ",
+  "ပေး",
+  "လုပ်",
+  "ကြည့်",
+  "နား",
+  "ဖတ်",
+  "ရေး",
+  "ပြော",
+  "စောင့်",
+  "ဝင်",
+  "ထွက်",
+  "နေ",
+  "ခေါ်",
+  "ပြ",
+  "ဖွင့်",
+  "ပိတ်",
+  "ကူ",
+  "ရပ်",
+  "အိပ်",
+  "မေး",
+  "ဖြေ",
+];
+
+/// Represents the type of a sentence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SentenceType
+{
+  /// A statement, e.g. ကျေးဇူးတင်ပါတယ်.
+  Statement,
+  /// A question, e.g. နေကောင်းလား.
+  Question,
+  /// A command, a request or a suggestion, e.g. ထိုင်ပါ or မသွားနဲ့.
+  Imperative,
+}
+
+impl SentenceType
+{
+  /// Get the western punctuation ending a sentence of the type.
+  ///
+  /// # Returns
+  ///
+  /// `.`, `?` or `!`.
+  pub fn punctuation(self) -> &'static str
+  {
+    match self
+    {
+      SentenceType::Statement => ".",
+      SentenceType::Question => "?",
+      SentenceType::Imperative => "!",
+    }
+  }
+}
+
+/// Represents a sentence of Myanmar text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sentence<'i>
+{
+  /// The text of the sentence without the ။ ending it.
+  pub text: &'i str,
+  /// The start position of the sentence in the input.
+  pub start: usize,
+  /// The length of the sentence in the input.
+  pub len: usize,
+  /// The type of the sentence, `None` if it doesn't end with a known
+  /// particle.
+  pub sentence_type: Option<SentenceType>,
+}
+
+/// Represents a word of segmented text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TaggedWord<'i>
+{
+  /// The text of the word.
+  pub text: &'i str,
+  /// The start position of the word in the input.
+  pub start: usize,
+  /// The length of the word in the input.
+  pub len: usize,
+  /// The kind of the particle, `None` if the word is not a particle.
+  pub particle: Option<Particle>,
+}
+
+/// Split Myanmar text into words and tag the grammatical particles.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::grammar::{tag_particles, Particle};
+/// use mlcts_generator::segment::RunSegmenter;
+///
+/// let input = "ကျောင်းသားတွေကို စာအုပ်ပေးတယ်။";
+/// let words = tag_particles(input, &RunSegmenter);
+/// let tagged = words
+///   .iter()
+///   .map(|w| (w.text, w.particle))
+///   .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///   tagged,
+///   [
+///     ("ကျောင်းသား", None),
+///     ("တွေ", Some(Particle::Plural)),
+///     ("ကို", Some(Particle::Object)),
+///     ("စာအုပ်ပေး", None),
+///     ("တယ်", Some(Particle::Statement)),
+///   ]
+/// );
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text, expected to be normalized with
+///   [`crate::normalize::normalize_myanmar`].
+/// * `segmenter` - The segmenter to split runs of syllables into words.
+///
+/// # Returns
+///
+/// The words in the order they appear in the input. Spaces, punctuation
+/// and other text are not words.
+pub fn tag_particles<'i>(
+  input: &'i str,
+  segmenter: &dyn Segmenter,
+) -> Vec<TaggedWord<'i>>
+{
+  let mut words = Vec::new();
+  let mut run = Vec::new();
+  for token in get_token(input)
+  {
+    match token.kind
+    {
+      TokenKind::Syllable(_) =>
+      {
+        run.push((&input[token.start .. token.start + token.len], token.start))
+      }
+      _ =>
+      {
+        tag_run(input, &run, segmenter, &mut words);
+        run.clear();
+      }
+    }
+  }
+  tag_run(input, &run, segmenter, &mut words);
+  words
+}
+
+/// Split Myanmar text into sentences and tell their types. Sentences end
+/// with ။ and at line breaks.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::grammar::{sentences, SentenceType};
+///
+/// let input = "နေကောင်းလား။ ကောင်းပါတယ်။\nထိုင်ပါ";
+/// let types = sentences(input)
+///   .iter()
+///   .map(|s| (s.text, s.sentence_type))
+///   .collect::<Vec<_>>();
+///
+/// assert_eq!(
+///   types,
+///   [
+///     ("နေကောင်းလား", Some(SentenceType::Question)),
+///     ("ကောင်းပါတယ်", Some(SentenceType::Statement)),
+///     ("ထိုင်ပါ", Some(SentenceType::Imperative)),
+///   ]
+/// );
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text, expected to be normalized with
+///   [`crate::normalize::normalize_myanmar`].
+///
+/// # Returns
+///
+/// The sentences in the order they appear in the input, without the
+/// spaces around them.
+pub fn sentences(input: &str) -> Vec<Sentence<'_>>
+{
+  let mut sentences = Vec::new();
+  let mut push = |start: usize, end: usize| {
+    let text = &input[start .. end];
+    let trimmed = text.trim();
+    if trimmed.is_empty()
+    {
+      return;
+    }
+    let start = start + (text.len() - text.trim_start().len());
+    sentences.push(Sentence {
+      text: trimmed,
+      start,
+      len: trimmed.len(),
+      sentence_type: sentence_type(trimmed),
+    });
+  };
+
+  let mut start = 0;
+  for token in get_token(input)
+  {
+    let text = &input[token.start .. token.start + token.len];
+    if token.kind == TokenKind::SpecialMapped(".")
+      || text.contains(['\n', '\r'])
+    {
+      push(start, token.start);
+      start = token.start + token.len;
+    }
+  }
+  push(start, input.len());
+  sentences
+}
+
+/// Tell the type of a sentence by the particle it ends with.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::grammar::{sentence_type, SentenceType};
+///
+/// assert_eq!(sentence_type("မသွားနဲ့။"), Some(SentenceType::Imperative));
+/// assert_eq!(sentence_type("မင်္ဂလာပါ ခင်ဗျာ"), None);
+/// ```
+///
+/// # Arguments
+///
+/// * `sentence` - The Myanmar sentence, expected to be normalized with
+///   [`crate::normalize::normalize_myanmar`].
+///
+/// # Returns
+///
+/// The type of the sentence, `None` if it doesn't end with a known particle.
+pub fn sentence_type(sentence: &str) -> Option<SentenceType>
+{
+  let syllables = get_token(sentence)
+    .filter(|token| matches!(token.kind, TokenKind::Syllable(_)))
+    .map(|token| &sentence[token.start .. token.start + token.len])
+    .collect::<Vec<_>>();
+  if imperative_tail(&syllables)
+  {
+    return Some(SentenceType::Imperative);
+  }
+  SENTENCE_ENDINGS
+    .iter()
+    .find(|(ending, _)| syllables.ends_with(ending))
+    .map(|(_, sentence_type)| *sentence_type)
+}
+
+/// Check if syllables end with bare ပါ after a verb, e.g. ထိုင်ပါ, or with
+/// နဲ့ or နှင့် after a verb with the negative prefix မ, e.g. မသွားနဲ့.
+///
+/// # Arguments
+///
+/// * `syllables` - The syllables of the sentence.
+///
+/// # Returns
+///
+/// `true` if the syllables end with an imperative ပါ, နဲ့ or နှင့်.
+fn imperative_tail(syllables: &[&str]) -> bool
+{
+  match syllables
+  {
+    [.., before, "ပါ"] =>
+    {
+      VERBS.contains(before) || AUXILIARIES.contains(before)
+    }
+    [rest @ .., "နဲ့" | "နှင့်"] =>
+    {
+      let mut rest = rest;
+      while let [verb @ .., auxiliary] = rest
+      {
+        if *auxiliary != "ပါ" && !AUXILIARIES.contains(auxiliary)
+        {
+          break;
+        }
+        rest = verb;
+      }
+      matches!(rest, [.., "မ", _])
+    }
+    _ => false,
+  }
+}
+
+/// Get the kind of a particle.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::grammar::{particle, Particle};
+///
+/// assert_eq!(particle("ကို"), Some(Particle::Object));
+/// assert_eq!(particle("ကျောင်း"), None);
+/// ```
+///
+/// # Arguments
+///
+/// * `word` - The normalized Myanmar word.
+///
+/// # Returns
+///
+/// The kind of the particle, `None` if the word is not a particle.
+pub fn particle(word: &str) -> Option<Particle>
+{
+  PARTICLES
+    .iter()
+    .find(|(particle, _)| *particle == word)
+    .map(|(_, kind)| *kind)
+}
+
+/// Segment a run of syllables and tag its words. Words known to the
+/// segmenter are tagged as they are, the syllables between them are taken
+/// as one word and the particles attached to its end are split off.
+///
+/// # Arguments
+///
+/// * `input` - The input the syllables are in.
+/// * `run` - The syllables of the run with their start positions.
+/// * `segmenter` - The segmenter to split the run into words.
+/// * `words` - The tagged words to append to.
+fn tag_run<'i>(
+  input: &'i str,
+  run: &[(&'i str, usize)],
+  segmenter: &dyn Segmenter,
+  words: &mut Vec<TaggedWord<'i>>,
+)
+{
+  let texts = run.iter().map(|(text, _)| *text).collect::<Vec<_>>();
+  // the start of the syllables not known as words so far
+  let mut unknown = 0;
+  let mut start = 0;
+  for end in word_ends(segmenter, &texts)
+  {
+    if segmenter.knows(&texts[start .. end])
+    {
+      tag_unknown(input, &run[unknown .. start], words);
+      let syllables = &run[start .. end];
+      words.push(word(input, syllables, particle(&concat(syllables))));
+      unknown = end;
+    }
+    start = end;
+  }
+  tag_unknown(input, &run[unknown ..], words);
+}
+
+/// Tag syllables not known as a word, splitting off the particles attached
+/// to their end.
+///
+/// # Arguments
+///
+/// * `input` - The input the syllables are in.
+/// * `syllables` - The syllables with their start positions.
+/// * `words` - The tagged words to append to.
+fn tag_unknown<'i>(
+  input: &'i str,
+  syllables: &[(&'i str, usize)],
+  words: &mut Vec<TaggedWord<'i>>,
+)
+{
+  if syllables.is_empty()
+  {
+    return;
+  }
+
+  let mut stem_end = syllables.len();
+  let mut particles = Vec::new();
+  while stem_end > 1
+  {
+    let suffix = (1 ..= MAX_PARTICLE_SYLLABLES.min(stem_end - 1))
+      .rev()
+      .find_map(|len| {
+        particle(&concat(&syllables[stem_end - len .. stem_end]))
+          .map(|kind| (len, kind))
+      });
+    let Some((len, kind)) = suffix
+    else
+    {
+      break;
+    };
+    particles.push((&syllables[stem_end - len .. stem_end], Some(kind)));
+    stem_end -= len;
+  }
+
+  let stem = &syllables[.. stem_end];
+  let stem_kind = particle(&concat(stem));
+  for (syllables, kind) in
+    std::iter::once((stem, stem_kind)).chain(particles.into_iter().rev())
+  {
+    words.push(word(input, syllables, kind));
+  }
+}
+
+/// Join the texts of syllables.
+///
+/// # Arguments
+///
+/// * `syllables` - The syllables with their start positions.
+///
+/// # Returns
+///
+/// The joined text.
+fn concat(syllables: &[(&str, usize)]) -> String
+{
+  syllables.iter().map(|(text, _)| *text).collect()
+}
+
+/// Create a tagged word from its syllables.
+///
+/// # Arguments
+///
+/// * `input` - The input the syllables are in.
+/// * `syllables` - The syllables of the word with their start positions, next
+///   to each other in the input.
+/// * `particle` - The kind of the particle.
+///
+/// # Returns
+///
+/// The tagged word.
+fn word<'i>(
+  input: &'i str,
+  syllables: &[(&'i str, usize)],
+  particle: Option<Particle>,
+) -> TaggedWord<'i>
+{
+  let start = syllables[0].1;
+  let len = syllables.iter().map(|(text, _)| text.len()).sum::<usize>();
+  TaggedWord {
+    text: &input[start .. start + len],
+    start,
+    len,
+    particle,
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::{sentence_type, sentences, tag_particles, Particle, SentenceType};
+  use crate::segment::{DictionarySegmenter, RunSegmenter};
+
+  #[test]
+  fn test_tag_particles()
+  {
+    let tagged = |input, segmenter| {
+      tag_particles(input, segmenter)
+        .into_iter()
+        .map(|w| (w.text, w.particle))
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(tagged("", &RunSegmenter), []);
+    assert_eq!(tagged("က", &RunSegmenter), [("က", Some(Particle::Subject))]);
+
+    // the longest particle wins
+    assert_eq!(
+      tagged("သူကတော့ ရန်ကုန်ကနေ လာတယ်", &RunSegmenter),
+      [
+        ("သူ", None),
+        ("ကတော့", Some(Particle::Topic)),
+        ("ရန်ကုန်", None),
+        ("ကနေ", Some(Particle::Source)),
+        ("လာ", None),
+        ("တယ်", Some(Particle::Statement)),
+      ]
+    );
+
+    let segmenter = DictionarySegmenter::new(["မြန်မာ", "စာ", "ကို", "သင်"]);
+    let words = tag_particles("မြန်မာစာကို သင်ပါသလား။", &segmenter);
+    assert_eq!(
+      words
+        .iter()
+        .map(|w| (w.text, w.particle))
+        .collect::<Vec<_>>(),
+      [
+        ("မြန်မာ", None),
+        ("စာ", None),
+        ("ကို", Some(Particle::Object)),
+        ("သင်", None),
+        // syllables not known to the segmenter are joined again
+        ("ပါ", Some(Particle::Polite)),
+        ("သလား", Some(Particle::Question)),
+      ]
+    );
+    // known words keep the syllables which look like particles
+    let segmenter = DictionarySegmenter::new(["သူများ", "စာ"]);
+    assert_eq!(
+      tagged("သူများစာတွေ", &segmenter),
+      [
+        ("သူများ", None),
+        ("စာ", None),
+        ("တွေ", Some(Particle::Plural))
+      ]
+    );
+    assert_eq!(
+      tagged("သူများ", &RunSegmenter),
+      [("သူ", None), ("များ", Some(Particle::Plural))]
+    );
+    assert_eq!(
+      tagged("သင်ပါသလား", &RunSegmenter),
+      [
+        ("သင်", None),
+        ("ပါ", Some(Particle::Polite)),
+        ("သလား", Some(Particle::Question)),
+      ]
+    );
+    // the positions point into the input
+    assert_eq!(words[3].start, "မြန်မာစာကို ".len());
+    assert_eq!(words[3].len, "သင်".len());
+  }
+
+  #[test]
+  fn test_sentences()
+  {
+    let cases = [
+      ("ကျေးဇူးတင်ပါတယ်", Some(SentenceType::Statement)),
+      ("စာအုပ်ဖတ်မယ်", Some(SentenceType::Statement)),
+      ("မသိဘူး", Some(SentenceType::Statement)),
+      ("ရောက်ပြီ", Some(SentenceType::Statement)),
+      ("သူသည် ဆရာဖြစ်၏", Some(SentenceType::Statement)),
+      ("ဘယ်သွားမလဲ", Some(SentenceType::Question)),
+      ("မသိဘူးလား", Some(SentenceType::Question)),
+      ("ဒါဘာနည်း", Some(SentenceType::Question)),
+      ("ဒီမှာထိုင်ပါ", Some(SentenceType::Imperative)),
+      ("သွားကြစို့", Some(SentenceType::Imperative)),
+      ("လုပ်စမ်း", Some(SentenceType::Imperative)),
+      ("သွားကြပါ", Some(SentenceType::Imperative)),
+      ("မလုပ်ပါနဲ့", Some(SentenceType::Imperative)),
+      ("မသွားကြနဲ့", Some(SentenceType::Imperative)),
+      ("ဘာစားမလဲ", Some(SentenceType::Question)),
+      ("နေကောင်းပါသလား", Some(SentenceType::Question)),
+      ("ကျေးဇူးပါ", None),
+      // ပါ and နဲ့ after nouns don't end imperatives
+      ("မင်္ဂလာပါ", None),
+      ("ကျောင်းသားပါ။", None),
+      ("ဆရာနဲ့", None),
+      ("ဆရာမနဲ့", None),
+      ("ကျောင်း", None),
+      ("", None),
+    ];
+    for (input, expected) in cases
+    {
+      assert_eq!(sentence_type(input), expected, "{}", input);
+    }
+
+    let input = " ဟုတ်လား ။။\r\nဟုတ်တယ်";
+    let found = sentences(input);
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].text, "ဟုတ်လား");
+    assert_eq!(found[0].start, 1);
+    assert_eq!(found[0].sentence_type.map(|t| t.punctuation()), Some("?"));
+    assert_eq!(found[1].text, "ဟုတ်တယ်");
+    assert_eq!(&input[found[1].start ..], "ဟုတ်တယ်");
+  }
+}
@@ -0,0 +1,160 @@
+//! Joining converted tokens into the output text.
+
+use crate::normalize::Newline;
+use crate::{ConvertOptions, Token, TokenKind};
+
+/// Joins the MLCTS text of tokens one by one. The state is kept between
+/// calls so that text converted in several parts (see [`crate::stream`]) is
+/// joined the same way as text converted at once.
+#[derive(Debug, Clone)]
+pub(crate) struct TokenJoiner
+{
+  capitalize_sentences: bool,
+  western_punctuation: bool,
+  newline: Newline,
+  /// Joined text which is not taken yet.
+  output: String,
+  /// If nothing is joined yet.
+  empty: bool,
+  /// If the last joined token is a line break.
+  line_start: bool,
+  /// If the next letter starts a sentence.
+  sentence_start: bool,
+}
+
+impl TokenJoiner
+{
+  /// Create a new token joiner.
+  ///
+  /// # Arguments
+  ///
+  /// * `options` - The conversion options.
+  ///
+  /// # Returns
+  ///
+  /// A new token joiner.
+  pub(crate) fn new(options: &ConvertOptions) -> Self
+  {
+    Self {
+      capitalize_sentences: options.capitalize_sentences,
+      western_punctuation: options.western_punctuation,
+      newline: options.newline,
+      output: String::new(),
+      empty: true,
+      line_start: false,
+      sentence_start: true,
+    }
+  }
+
+  /// Join the next token.
+  ///
+  /// # Arguments
+  ///
+  /// * `token` - The token.
+  /// * `mlcts` - The MLCTS text of the token.
+  pub(crate) fn push(&mut self, token: &Token, mlcts: &str)
+  {
+    let is_syllable = matches!(token.kind, TokenKind::Syllable(_));
+    let is_whitespace = !is_syllable && mlcts.chars().all(char::is_whitespace);
+    let is_punctuation = matches!(token.kind, TokenKind::SpecialMapped(_));
+    let mlcts = match mlcts
+    {
+      "\n" | "\r\n" | "\r" if !is_syllable => self.newline.apply(mlcts),
+      _ => mlcts,
+    };
+
+    if !self.western_punctuation
+    {
+      if !self.empty
+      {
+        self.output.push(' ');
+      }
+    }
+    else if is_whitespace
+    {
+      // spaces are collapsed into a single separator, line breaks are kept
+      if !mlcts.contains(['\n', '\r'])
+      {
+        return;
+      }
+      self.output.push_str(mlcts);
+      self.empty = false;
+      self.line_start = true;
+      return;
+    }
+    else if !self.empty && !self.line_start && !is_punctuation
+    {
+      self.output.push(' ');
+    }
+
+    if self.capitalize_sentences
+      && self.sentence_start
+      && mlcts.contains(char::is_alphabetic)
+    {
+      self.sentence_start = false;
+      let mut capitalized = false;
+      for c in mlcts.chars()
+      {
+        if !capitalized && c.is_alphabetic()
+        {
+          capitalized = true;
+          self.output.extend(c.to_uppercase());
+        }
+        else
+        {
+          self.output.push(c);
+        }
+      }
+    }
+    else
+    {
+      self.output.push_str(mlcts);
+    }
+
+    if !is_syllable && matches!(mlcts, "." | "?" | "!")
+    {
+      self.sentence_start = true;
+    }
+    self.empty = false;
+    self.line_start = false;
+  }
+
+  /// Check if nothing is joined yet, i.e. the next token starts the text.
+  ///
+  /// # Returns
+  ///
+  /// `true` if nothing is joined yet, `false` otherwise.
+  pub(crate) fn is_empty(&self) -> bool
+  {
+    self.empty
+  }
+
+  /// Take the joined text so far.
+  ///
+  /// # Returns
+  ///
+  /// Text joined since the last call.
+  pub(crate) fn take_output(&mut self) -> String
+  {
+    std::mem::take(&mut self.output)
+  }
+
+  /// Write the joined text so far and clear it, keeping the buffer.
+  ///
+  /// # Arguments
+  ///
+  /// * `write` - Writes the text joined since the last call.
+  ///
+  /// # Returns
+  ///
+  /// The result of writing.
+  pub(crate) fn flush<E>(
+    &mut self,
+    write: impl FnOnce(&str) -> Result<(), E>,
+  ) -> Result<(), E>
+  {
+    let result = write(&self.output);
+    self.output.clear();
+    result
+  }
+}
@@ -0,0 +1,318 @@
+//! Numeral classifiers, number words and ordinals.
+//!
+//! Myanmar counts things with a number and a classifier, e.g. ၅ ယောက် (five
+//! people). Digits are converted one by one and are not read, so
+//! [`expand_classifier_phrases`] writes the numbers of such phrases out in
+//! words first, e.g. ငါးယောက် (nga: yauk).
+
+use crate::normalize::normalize_myanmar;
+use crate::quantity::{digit, is_number};
+use crate::split_syllables;
+
+/// Common numeral classifiers, normalized with [`normalize_myanmar`]: for
+/// people, monks, animals, things in general, round things, long things,
+/// plants, vehicles, books, clothes, sheets, flat things, cups, events, times,
+/// kinds, buildings and roads.
+pub const CLASSIFIERS: &[&str] = &[
+  "ယောက်",
+  "ဦး",
+  "ပါး",
+  "ကောင်",
+  "ခု",
+  "လုံး",
+  "ချောင်း",
+  "ပင်",
+  "စင်း",
+  "စီး",
+  "စောင်",
+  "အုပ်",
+  "ထည်",
+  "ရွက်",
+  "ချပ်",
+  "ခွက်",
+  "ပွဲ",
+  "ကြိမ်",
+  "ခါ",
+  "မျိုး",
+  "ဆောင်",
+  "လမ်း",
+];
+
+/// Digits in words.
+const DIGITS: [&str; 10] = [
+  "သုည",
+  "တစ်",
+  "နှစ်",
+  "သုံး",
+  "လေး",
+  "ငါး",
+  "ခြောက်",
+  "ခုနစ်",
+  "ရှစ်",
+  "ကိုး",
+];
+
+/// Place values below a ကုဋေ (ten million) in words, and their creaky forms
+/// used when more digits follow, e.g. နှစ်ဆယ့်ငါး.
+const PLACES: [(u64, &str, &str); 6] = [
+  (1_000_000, "သန်း", "သန်း"),
+  (100_000, "သိန်း", "သိန်း"),
+  (10_000, "သောင်း", "သောင်း"),
+  (1_000, "ထောင်", "ထောင့်"),
+  (100, "ရာ", "ရာ့"),
+  (10, "ဆယ်", "ဆယ့်"),
+];
+
+/// ကုဋေ (ten million)
+const KUTAY: u64 = 10_000_000;
+
+/// Pali ordinals from first to tenth.
+const ORDINALS: [&str; 10] = [
+  "ပထမ",
+  "ဒုတိယ",
+  "တတိယ",
+  "စတုတ္ထ",
+  "ပဉ္စမ",
+  "ဆဋ္ဌမ",
+  "သတ္တမ",
+  "အဋ္ဌမ",
+  "နဝမ",
+  "ဒသမ",
+];
+
+/// Check if a word is a numeral classifier.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::lexical::is_classifier;
+///
+/// assert!(is_classifier("ယောက်"));
+/// assert!(!is_classifier("ကျောင်း"));
+/// ```
+///
+/// # Arguments
+///
+/// * `word` - The Myanmar word.
+///
+/// # Returns
+///
+/// `true` if the word is in [`CLASSIFIERS`], `false` otherwise.
+pub fn is_classifier(word: &str) -> bool
+{
+  let word = normalize_myanmar(word.trim());
+  CLASSIFIERS.iter().any(|classifier| *classifier == word)
+}
+
+/// Write a number in Myanmar words.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::lexical::number_words;
+///
+/// assert_eq!(number_words(0), "သုည");
+/// assert_eq!(number_words(25), "နှစ်ဆယ့်ငါး");
+/// assert_eq!(number_words(1948), "တစ်ထောင့်ကိုးရာ့လေးဆယ့်ရှစ်");
+/// assert_eq!(number_words(20_000_000), "နှစ်ကုဋေ");
+/// ```
+///
+/// # Arguments
+///
+/// * `n` - The number.
+///
+/// # Returns
+///
+/// The number in words.
+pub fn number_words(n: u64) -> String
+{
+  if n == 0
+  {
+    return DIGITS[0].to_string();
+  }
+
+  let mut words = String::new();
+  if n >= KUTAY
+  {
+    words.push_str(&number_words(n / KUTAY));
+    words.push_str("ကုဋေ");
+  }
+
+  let mut rest = n % KUTAY;
+  for (place, word, creaky) in PLACES
+  {
+    if rest >= place
+    {
+      words.push_str(DIGITS[(rest / place) as usize]);
+      rest %= place;
+      words.push_str(if rest > 0 { creaky } else { word });
+    }
+  }
+  if rest > 0
+  {
+    words.push_str(DIGITS[rest as usize]);
+  }
+  words
+}
+
+/// Write an ordinal in Myanmar words: the Pali ordinals up to the tenth,
+/// and the number with ခုမြောက် after that.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::lexical::ordinal_words;
+///
+/// assert_eq!(ordinal_words(1), "ပထမ");
+/// assert_eq!(ordinal_words(11), "တစ်ဆယ့်တစ်ခုမြောက်");
+/// ```
+///
+/// # Arguments
+///
+/// * `n` - The number, from 1.
+///
+/// # Returns
+///
+/// The ordinal in words.
+pub fn ordinal_words(n: u64) -> String
+{
+  match n
+  {
+    1 ..= 10 => ORDINALS[n as usize - 1].to_string(),
+    _ => format!("{}ခုမြောက်", number_words(n)),
+  }
+}
+
+/// Write the numbers followed by a classifier out in words, so that they
+/// are read as they are spoken. Other numbers are kept.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::lexical::expand_classifier_phrases;
+/// use mlcts_generator::mlcts_from_myanmar;
+///
+/// let expanded = expand_classifier_phrases("ကျောင်းသား ၂၅ ယောက် ၂၀၂၄");
+/// assert_eq!(expanded, "ကျောင်းသား နှစ်ဆယ့်ငါးယောက် ၂၀၂၄");
+/// assert_eq!(
+///   mlcts_from_myanmar("ကျောင်းသား ၅ ယောက်"),
+///   "kyaung: sa:   ၅   yauk"
+/// );
+/// assert_eq!(
+///   mlcts_from_myanmar(&expand_classifier_phrases("ကျောင်းသား ၅ ယောက်")),
+///   "kyaung: sa:   nga: yauk"
+/// );
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text, expected to be normalized with
+///   [`normalize_myanmar`].
+///
+/// # Returns
+///
+/// The text with the numbers of classifier phrases in words.
+pub fn expand_classifier_phrases(input: &str) -> String
+{
+  let syllables = split_syllables(input);
+  let mut output = String::with_capacity(input.len());
+  let mut i = 0;
+  while i < syllables.len()
+  {
+    let first = i;
+    let mut value = Some(0u64);
+    while i < syllables.len() && is_number(syllables[i].0)
+    {
+      value = syllables[i].0.chars().fold(value, |value, c| {
+        value?.checked_mul(10)?.checked_add(u64::from(digit(c)))
+      });
+      i += 1;
+    }
+    if i == first
+    {
+      output.push_str(syllables[i].0);
+      i += 1;
+      continue;
+    }
+
+    let mut next = i;
+    while next < syllables.len() && syllables[next].0.trim().is_empty()
+    {
+      next += 1;
+    }
+    match (value, syllables.get(next))
+    {
+      (Some(value), Some((classifier, ..))) if is_classifier(classifier) =>
+      {
+        // the spaces between the number and the classifier are dropped
+        output.push_str(&number_words(value));
+        i = next;
+      }
+      _ => syllables[first .. i]
+        .iter()
+        .for_each(|(text, ..)| output.push_str(text)),
+    }
+  }
+  output
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::{expand_classifier_phrases, number_words, CLASSIFIERS};
+  use crate::mlcts_from_myanmar;
+  use crate::normalize::normalize_myanmar;
+
+  #[test]
+  fn test_number_words()
+  {
+    let cases = [
+      (5, "ငါး"),
+      (10, "တစ်ဆယ်"),
+      (11, "တစ်ဆယ့်တစ်"),
+      (101, "တစ်ရာ့တစ်"),
+      (125, "တစ်ရာ့နှစ်ဆယ့်ငါး"),
+      (1_000, "တစ်ထောင်"),
+      (1_386, "တစ်ထောင့်သုံးရာ့ရှစ်ဆယ့်ခြောက်"),
+      (30_000, "သုံးသောင်း"),
+      (250_000, "နှစ်သိန်းငါးသောင်း"),
+      (7_000_000, "ခုနစ်သန်း"),
+      (12_000_005, "တစ်ကုဋေနှစ်သန်းငါး"),
+    ];
+    for (n, expected) in cases
+    {
+      assert_eq!(number_words(n), expected, "{}", n);
+      // every number word is read
+      assert!(
+        mlcts_from_myanmar(expected).is_ascii(),
+        "{}",
+        mlcts_from_myanmar(expected)
+      );
+    }
+  }
+
+  #[test]
+  fn test_expand_classifier_phrases()
+  {
+    let cases = [
+      ("၅ယောက်", "ငါးယောက်"),
+      ("ခွေး ၃ ကောင်", "ခွေး သုံးကောင်"),
+      ("၁၀ ကြိမ်မြောက်", "တစ်ဆယ်ကြိမ်မြောက်"),
+      // not followed by a classifier
+      ("၅ ကျောင်း", "၅ ကျောင်း"),
+      ("အခန်း ၁၂", "အခန်း ၁၂"),
+      ("၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉ ခု", "၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉ ခု"),
+    ];
+    for (input, expected) in cases
+    {
+      assert_eq!(expand_classifier_phrases(input), expected, "{}", input);
+    }
+
+    // every classifier is read and compared without normalizing it
+    for classifier in CLASSIFIERS
+    {
+      assert!(mlcts_from_myanmar(classifier).is_ascii(), "{}", classifier);
+      assert_eq!(normalize_myanmar(classifier), *classifier);
+    }
+  }
+}
@@ -3,14 +3,79 @@
 //! This crate provides function to generate MLCTS text from Myanmar text.
 //! Moreover, this also provides utilities functions such as syllable breaker,
 //! etc.
+//!
+//! # API stability
+//!
+//! The [`prelude`] is the stable core: the conversion functions, the
+//! syllable types, [`ParseError`] and the [`converter::Converter`]. They
+//! only break with a major release. The experimental modules `diff`,
+//! `fuzzy`, `grammar`, `lexical`, `metrics`, `ocr`, `phonetic`, `quantity`
+//! and `subtitle` are `#[doc(hidden)]`: they stay usable but may change in
+//! any release while the parser keeps changing. Parser internals are not
+//! public at all.
+//!
+//! # Thread safety
+//!
+//! Conversion never mutates the options, dictionaries or pipelines it uses
+//! (everything is taken by `&self` or `&`), and all of them are `Send` and
+//! `Sync`. A server can build them once and share them between threads with
+//! an `Arc`. Custom [`pipeline::Stage`]s and [`segment::Segmenter`]s must be
+//! `Send` and `Sync` as well.
+
+use std::borrow::Cow;
+use std::str::Chars;
+#[cfg(feature = "regex")]
+use std::sync::OnceLock;
+use std::{fmt, io};
 
+#[cfg(feature = "regex")]
 use fancy_regex::Regex;
 use mlcts_core::*;
 
+pub mod autofix;
+pub mod converter;
+pub mod dictionary;
+#[doc(hidden)]
+pub mod diff;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod fixtures;
+#[doc(hidden)]
+pub mod fuzzy;
+#[doc(hidden)]
+pub mod grammar;
+mod join;
+#[doc(hidden)]
+pub mod lexical;
+#[doc(hidden)]
+pub mod metrics;
+pub mod names;
+pub mod normalize;
+#[doc(hidden)]
+pub mod ocr;
+#[doc(hidden)]
+pub mod phonetic;
+pub mod pipeline;
+pub mod prelude;
+#[doc(hidden)]
+pub mod quantity;
+pub mod segment;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+pub mod stats;
+pub mod stream;
+#[doc(hidden)]
+pub mod subtitle;
+pub mod validate;
+
 /// Convert Myanmar text to MLCTS text.
+/// The input is normalized with [`normalize::normalize_myanmar`] first.
 /// This function internally uses [`split_syllables`] and [`get_token`].
 /// So, splitting semantics are the same as [`split_syllables`].
 ///
+/// Text between `{{` and `}}` on the same line, e.g. a placeholder of a
+/// template or a code snippet, is passed through untouched without the
+/// markers.
+///
 /// # Examples
 ///
 /// ```
@@ -25,6 +90,11 @@ use mlcts_core::*;
 /// let mlcts = mlcts_from_myanmar(input);
 ///
 /// assert_eq!(mlcts, "mangga. la pa   赵 露 思 .");
+///
+/// let input = "{{name}} မင်္ဂလာပါ";
+/// let mlcts = mlcts_from_myanmar(input);
+///
+/// assert_eq!(mlcts, "name   mangga. la pa");
 /// ```
 ///
 /// # Arguments
@@ -36,23 +106,640 @@ use mlcts_core::*;
 /// Space-separated syllables in MLCTS.
 pub fn mlcts_from_myanmar<'i>(input: &'i str) -> String
 {
-  get_token(input)
-    .map(|t| t.to_mlcts(input))
-    .collect::<Vec<_>>()
-    .join(" ")
+  mlcts_from_myanmar_with_options(input, &ConvertOptions::default())
+}
+
+/// Options for [`mlcts_from_myanmar_with_options`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ConvertOptions
+{
+  /// How zero-width characters in the input are handled.
+  pub zero_width: normalize::ZeroWidth,
+  /// If the input should be corrected with [`autofix::autofix_myanmar`]
+  /// before the conversion.
+  pub autofix: bool,
+  /// Capitalize the first letter of each sentence.
+  pub capitalize_sentences: bool,
+  /// Attach the mapped ၊ (`,`) and ။ (`.`) to the previous syllable and
+  /// collapse spaces between words into one, instead of separating every
+  /// token with a space.
+  pub western_punctuation: bool,
+  /// Terms which are passed through or replaced instead of being converted.
+  pub dictionary: dictionary::UserDictionary,
+  /// How line breaks are written to the output.
+  pub newline: normalize::Newline,
+  /// How tokens with Myanmar characters which are not syllables are written.
+  pub fallback: Fallback,
+  /// How letters which are romanized like other letters (e.g. ဋ like တ)
+  /// are written.
+  pub homophones: Homophones,
+}
+
+/// How tokens with Myanmar characters which are not syllables (e.g. a
+/// dangling sign) are written to the output.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Fallback
+{
+  /// Pass them through as they are.
+  #[default]
+  Keep,
+  /// Write the given text instead, e.g. `?`.
+  Replace(String),
+}
+
+/// How letters which share the MLCTS of another letter are written: ဉ
+/// (like ည), the retroflex row ဋ ဌ ဍ ဎ ဏ (like တ ထ ဒ ဓ န) and ဠ (like လ).
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::{mlcts_from_myanmar_with_options, ConvertOptions};
+/// use mlcts_generator::Homophones;
+///
+/// let mut options = ConvertOptions::default();
+/// options.homophones = Homophones::Mark;
+/// assert_eq!(mlcts_from_myanmar_with_options("ဂုဏ်", &options), "guṇ");
+/// assert_eq!(mlcts_from_myanmar_with_options("ဉာဏ်", &options), "ñaṇ");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Homophones
+{
+  /// Write them like the letters they sound like, as MLCTS does.
+  #[default]
+  Fold,
+  /// Write the retroflex row and ဠ with a dot below (`ṭ`, `hṭ`, `ḍ`, `ḍh`,
+  /// `ṇ`, `ḷ`) and ဉ as `ñ`, as in the transliteration of Pali.
+  Mark,
+}
+
+/// Counters of what happened during conversions, e.g. to export them to
+/// monitoring. Counters of several conversions can be added up with `+=`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConvertCounters
+{
+  /// Number of syllables converted to MLCTS.
+  pub syllables: usize,
+  /// Number of corrections applied by [`autofix::autofix_myanmar`].
+  pub repaired: usize,
+  /// Number of terms of the user dictionary.
+  pub terms: usize,
+  /// Number of tokens with Myanmar characters which are not syllables and
+  /// are passed through as they are, e.g. a dangling sign.
+  pub fallbacks: usize,
+}
+
+impl std::ops::AddAssign for ConvertCounters
+{
+  fn add_assign(&mut self, other: Self)
+  {
+    self.syllables += other.syllables;
+    self.repaired += other.repaired;
+    self.terms += other.terms;
+    self.fallbacks += other.fallbacks;
+  }
+}
+
+impl ConvertCounters
+{
+  /// Count a converted token.
+  ///
+  /// # Arguments
+  ///
+  /// * `token` - The token.
+  /// * `text` - The text of the token.
+  /// * `is_term` - If the token is a term of the user dictionary.
+  fn count(&mut self, token: &Token, text: &str, is_term: bool)
+  {
+    match &token.kind
+    {
+      _ if is_term => self.terms += 1,
+      TokenKind::Syllable(_) => self.syllables += 1,
+      _ if is_fallback(token, text) => self.fallbacks += 1,
+      _ => (),
+    }
+  }
+}
+
+/// Check if a token has Myanmar characters but is not a syllable.
+///
+/// # Arguments
+///
+/// * `token` - The token.
+/// * `text` - The text of the token.
+///
+/// # Returns
+///
+/// `true` if the token is passed through as a fallback, `false` otherwise.
+fn is_fallback(token: &Token, text: &str) -> bool
+{
+  token.kind == TokenKind::Other
+    && text.contains(|c| matches!(c, '\u{1000}' ..= '\u{109F}'))
+}
+
+/// Convert Myanmar text to MLCTS text with the given options.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::normalize::ZeroWidth;
+/// use mlcts_generator::{mlcts_from_myanmar_with_options, ConvertOptions};
+///
+/// let input = "မြန်မာ\u{200B}စာ";
+/// let mut options = ConvertOptions::default();
+/// options.zero_width = ZeroWidth::Strip;
+///
+/// assert_eq!(
+///   mlcts_from_myanmar_with_options(input, &options),
+///   "mran ma ca"
+/// );
+///
+/// let input = "မင်္ဂလာပါ။ နေကောင်းလား၊ ဗျာ။";
+/// let mut options = ConvertOptions::default();
+/// options.capitalize_sentences = true;
+/// options.western_punctuation = true;
+///
+/// assert_eq!(
+///   mlcts_from_myanmar_with_options(input, &options),
+///   "Mangga. la pa. Ne kaung: la:, bya."
+/// );
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to convert.
+/// * `options` - The conversion options.
+///
+/// # Returns
+///
+/// Space-separated syllables in MLCTS.
+pub fn mlcts_from_myanmar_with_options(
+  input: &str,
+  options: &ConvertOptions,
+) -> String
+{
+  let mut joiner = join::TokenJoiner::new(options);
+  convert_into(input, options, &[], None, &mut joiner, |_| ());
+  joiner.take_output()
+}
+
+/// Convert Myanmar text to MLCTS text with the given options and count what
+/// happened during the conversion.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::{mlcts_from_myanmar_with_counters, ConvertOptions};
+///
+/// let mut options = ConvertOptions::default();
+/// options.autofix = true;
+/// let (mlcts, counters) =
+///   mlcts_from_myanmar_with_counters("ကေျာင်းသားး", &options);
+///
+/// assert_eq!(mlcts, "kyaung: sa:");
+/// assert_eq!(counters.syllables, 2);
+/// assert_eq!(counters.repaired, 2);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to convert.
+/// * `options` - The conversion options.
+///
+/// # Returns
+///
+/// Space-separated syllables in MLCTS and the counters of the conversion.
+pub fn mlcts_from_myanmar_with_counters(
+  input: &str,
+  options: &ConvertOptions,
+) -> (String, ConvertCounters)
+{
+  let mut joiner = join::TokenJoiner::new(options);
+  let counters = convert_into(input, options, &[], None, &mut joiner, |_| ());
+  (joiner.take_output(), counters)
+}
+
+/// Convert Myanmar text to MLCTS text and write it to the given writer.
+/// The output is written token by token instead of being collected into a
+/// string first.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::{mlcts_from_myanmar_to, ConvertOptions};
+///
+/// let mut output = String::from("MLCTS: ");
+/// mlcts_from_myanmar_to("မင်္ဂလာပါ", &ConvertOptions::default(), &mut output)
+///   .unwrap();
+///
+/// assert_eq!(output, "MLCTS: mangga. la pa");
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to convert.
+/// * `options` - The conversion options.
+/// * `w` - The writer to write to.
+///
+/// # Returns
+///
+/// The result of writing.
+pub fn mlcts_from_myanmar_to<W: fmt::Write>(
+  input: &str,
+  options: &ConvertOptions,
+  w: &mut W,
+) -> fmt::Result
+{
+  let mut result = Ok(());
+  let mut joiner = join::TokenJoiner::new(options);
+  convert_into(input, options, &[], None, &mut joiner, |joiner| {
+    if result.is_ok()
+    {
+      result = joiner.flush(|output| w.write_str(output));
+    }
+  });
+  result
+}
+
+/// Convert Myanmar text to MLCTS text and write it to the given
+/// [`io::Write`] writer, e.g. a file or the standard output.
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to convert.
+/// * `options` - The conversion options.
+/// * `w` - The writer to write to.
+///
+/// # Returns
+///
+/// The result of writing.
+pub fn mlcts_from_myanmar_to_io<W: io::Write>(
+  input: &str,
+  options: &ConvertOptions,
+  w: &mut W,
+) -> io::Result<()>
+{
+  let mut result = Ok(());
+  let mut joiner = join::TokenJoiner::new(options);
+  convert_into(input, options, &[], None, &mut joiner, |joiner| {
+    if result.is_ok()
+    {
+      result = joiner.flush(|output| w.write_all(output.as_bytes()));
+    }
+  });
+  result
+}
+
+/// Convert Myanmar text and join the converted tokens with the given joiner.
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to convert.
+/// * `options` - The conversion options.
+/// * `stages` - The custom stages to run before joining.
+/// * `segmenter` - The segmenter splitting runs of syllables into words.
+/// * `joiner` - The joiner to join the converted tokens with.
+/// * `joined` - Called with the joiner after every joined token.
+///
+/// # Returns
+///
+/// The counters of the conversion.
+fn convert_into(
+  input: &str,
+  options: &ConvertOptions,
+  stages: &[Box<dyn pipeline::Stage>],
+  segmenter: Option<&dyn segment::Segmenter>,
+  joiner: &mut join::TokenJoiner,
+  mut joined: impl FnMut(&mut join::TokenJoiner),
+) -> ConvertCounters
+{
+  let mut counters = ConvertCounters::default();
+  // a byte order mark is only expected at the start of a file
+  let input = if joiner.is_empty()
+  {
+    normalize::strip_bom(input)
+  }
+  else
+  {
+    input
+  };
+  let input = prepare_input(input, options, &mut counters);
+  if stages.is_empty()
+  {
+    // the MLCTS of every token is written into the same buffer
+    let mut mlcts = String::new();
+    for_each_token(&input, &options.dictionary, segmenter, |token, term, _| {
+      let text = &input[token.start .. token.start + token.len];
+      counters.count(token, text, term.is_some());
+      match replacement(options, token, text, term)
+      {
+        Some(term) => joiner.push(token, term),
+        None =>
+        {
+          mlcts.clear();
+          let _ = token.write_mlcts(&input, &mut mlcts);
+          mark_homophones(options, token, text, &mut mlcts);
+          joiner.push(token, &mlcts);
+        }
+      }
+      joined(joiner);
+    });
+    return counters;
+  }
+
+  let mut tokens = Vec::new();
+  let dictionary = &options.dictionary;
+  for_each_token(&input, dictionary, segmenter, |token, term, word_start| {
+    let text = &input[token.start .. token.start + token.len];
+    counters.count(token, text, term.is_some());
+    let output = match replacement(options, token, text, term)
+    {
+      Some(term) => term.to_string(),
+      None =>
+      {
+        let mut mlcts = token.to_mlcts(&input);
+        mark_homophones(options, token, text, &mut mlcts);
+        mlcts
+      }
+    };
+    tokens.push(pipeline::StageToken {
+      token: token.clone(),
+      text,
+      output,
+      word_start,
+    })
+  });
+  for stage in stages
+  {
+    stage.process(&mut tokens);
+  }
+  // tokens whose output is cleared by a stage are left out
+  for token in tokens.iter().filter(|t| !t.output.is_empty())
+  {
+    joiner.push(&token.token, &token.output);
+    joined(joiner);
+  }
+  counters
+}
+
+/// Correct the input if [`ConvertOptions::autofix`] is set and normalize it.
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to convert.
+/// * `options` - The conversion options.
+/// * `counters` - The counters the corrections are counted in.
+///
+/// # Returns
+///
+/// The corrected and normalized text.
+fn prepare_input<'a>(
+  input: &'a str,
+  options: &ConvertOptions,
+  counters: &mut ConvertCounters,
+) -> Cow<'a, str>
+{
+  if !options.autofix
+  {
+    return map_unescaped(input, |part| {
+      normalize::normalize_myanmar_with(part, options.zero_width)
+    });
+  }
+
+  let fixed = map_unescaped(input, |part| {
+    let (fixed, fixes) = autofix::autofix_myanmar(part);
+    counters.repaired += fixes.len();
+    Cow::Owned(fixed)
+  });
+  let normalized = map_unescaped(&fixed, |part| {
+    normalize::normalize_myanmar_with(part, options.zero_width)
+  })
+  .into_owned();
+  Cow::Owned(normalized)
+}
+
+/// Transform the parts of the input outside `{{...}}` escapes, which are
+/// kept as they are.
+///
+/// # Arguments
+///
+/// * `input` - The input text.
+/// * `f` - Called with every part outside the escapes.
+///
+/// # Returns
+///
+/// The transformed text.
+fn map_unescaped<'a>(
+  input: &'a str,
+  mut f: impl FnMut(&'a str) -> Cow<'a, str>,
+) -> Cow<'a, str>
+{
+  let mut output = String::new();
+  let mut copied = 0;
+  let mut pos = 0;
+  while let Some(found) = input[pos ..].find("{{")
+  {
+    let open = pos + found;
+    match block_len(&input[open ..], "{{", "}}")
+    {
+      Some(len) =>
+      {
+        output.push_str(&f(&input[copied .. open]));
+        output.push_str(&input[open .. open + len]);
+        copied = open + len;
+        pos = copied;
+      }
+      None => pos = open + 1,
+    }
+  }
+
+  match copied
+  {
+    0 => f(input),
+    _ =>
+    {
+      output.push_str(&f(&input[copied ..]));
+      Cow::Owned(output)
+    }
+  }
+}
+
+/// Get the text written instead of the MLCTS of a token.
+///
+/// # Arguments
+///
+/// * `options` - The conversion options.
+/// * `token` - The token.
+/// * `text` - The text of the token.
+/// * `term` - The replacement of the term of the user dictionary.
+///
+/// # Returns
+///
+/// The replacement of the term or of the fallback, `None` if the token is
+/// written in MLCTS.
+fn replacement<'o>(
+  options: &'o ConvertOptions,
+  token: &Token,
+  text: &str,
+  term: Option<&'o str>,
+) -> Option<&'o str>
+{
+  match &options.fallback
+  {
+    Fallback::Replace(r) if term.is_none() && is_fallback(token, text) =>
+    {
+      Some(r)
+    }
+    _ => term,
+  }
+}
+
+/// Mark the letters of a syllable which are folded into another letter,
+/// see [`Homophones::Mark`].
+///
+/// The consonants of a syllable are romanized in the order they are
+/// written, so the MLCTS of every consonant is searched after the one
+/// before it.
+///
+/// # Arguments
+///
+/// * `options` - The conversion options.
+/// * `token` - The token.
+/// * `text` - The text of the token.
+/// * `mlcts` - The MLCTS of the token, marked in place.
+fn mark_homophones(
+  options: &ConvertOptions,
+  token: &Token,
+  text: &str,
+  mlcts: &mut String,
+)
+{
+  if options.homophones == Homophones::Fold
+    || !matches!(token.kind, TokenKind::Syllable(_))
+  {
+    return;
+  }
+
+  let mut pos = 0;
+  for c in text.chars()
+  {
+    let Ok(consonant) = BasicConsonant::from_myanmar_alphabet(c)
+    else
+    {
+      continue;
+    };
+    let folded = consonant.to_mlcts();
+    let Some(found) = mlcts[pos ..].find(folded)
+    else
+    {
+      continue;
+    };
+    let start = pos + found;
+    let marked = match c
+    {
+      'ဉ' => "ñ",
+      'ဋ' => "ṭ",
+      'ဌ' => "hṭ",
+      'ဍ' => "ḍ",
+      'ဎ' => "ḍh",
+      'ဏ' => "ṇ",
+      'ဠ' => "ḷ",
+      _ =>
+      {
+        pos = start + folded.len();
+        continue;
+      }
+    };
+    mlcts.replace_range(start .. start + folded.len(), marked);
+    pos = start + marked.len();
+  }
+}
+
+/// Visit the tokens of normalized Myanmar text. Terms of the user dictionary
+/// are visited as a single token with their replacement instead of their
+/// syllables. With a segmenter, terms are only matched if they start and end
+/// at word boundaries.
+///
+/// # Arguments
+///
+/// * `input` - The normalized Myanmar text.
+/// * `dictionary` - The user dictionary.
+/// * `segmenter` - The segmenter splitting runs of syllables into words, every
+///   syllable is a word without one.
+/// * `visit` - Called with every token, the replacement of the term and whether
+///   the token starts a word.
+pub(crate) fn for_each_token<'i>(
+  input: &'i str,
+  dictionary: &dictionary::UserDictionary,
+  segmenter: Option<&dyn segment::Segmenter>,
+  mut visit: impl FnMut(&Token<'i>, Option<&str>, bool),
+)
+{
+  let tokens = get_token(input).collect::<Vec<_>>();
+  let word_starts = match segmenter
+  {
+    Some(segmenter) => segment::word_starts(input, &tokens, segmenter),
+    None => vec![true; tokens.len()],
+  };
+  let is_boundary = |pos: usize| {
+    pos == input.len()
+      || tokens
+        .binary_search_by_key(&pos, |t| t.start)
+        .is_ok_and(|i| word_starts[i])
+  };
+
+  let mut i = 0;
+  while i < tokens.len()
+  {
+    let token = &tokens[i];
+    let term = match word_starts[i]
+    {
+      true => dictionary.longest_match(&input[token.start ..], |len| {
+        is_boundary(token.start + len)
+      }),
+      false => None,
+    };
+
+    match term
+    {
+      Some((len, replacement)) =>
+      {
+        let end = token.start + len;
+        visit(
+          &Token::new(TokenKind::Other, token.start, len),
+          Some(replacement),
+          word_starts[i],
+        );
+        while i < tokens.len() && tokens[i].start < end
+        {
+          i += 1;
+        }
+      }
+      None =>
+      {
+        visit(token, None, word_starts[i]);
+        i += 1;
+      }
+    }
+  }
 }
 
 /// Represents the kind of a token generated by the Myanmar script
 /// tokenizer/parser. Token kind can be a syllable or other types of tokens.
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum TokenKind<'i>
 {
   /// A syllable token.
   Syllable(Syllable),
   /// Special mapped string
   SpecialMapped(&'i str),
-  /// An other token.
+  /// An other token, e.g. foreign text, an emoji or a stray mark.
   Other,
+  /// Text escaped with `{{...}}`, passed through without the markers.
+  Escaped(&'i str),
 }
 
 /// Represents a token generated by the Myanmar script tokenizer/parser.
@@ -91,22 +778,191 @@ impl<'i> Token<'i>
   ///
   /// The MLCTS string.
   pub fn to_mlcts(&self, input: &str) -> String
+  {
+    let mut result = String::new();
+    let _ = self.write_mlcts(input, &mut result);
+    result
+  }
+
+  /// Write the MLCTS string of the token without allocating.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The input the token is taken from.
+  /// * `w` - The writer to write to.
+  ///
+  /// # Returns
+  ///
+  /// The result of writing.
+  pub fn write_mlcts<W: fmt::Write>(
+    &self,
+    input: &str,
+    w: &mut W,
+  ) -> fmt::Result
   {
     match &self.kind
     {
-      TokenKind::Syllable(s) => s.to_mlcts(),
-      TokenKind::SpecialMapped(s) => s.to_string(),
-      _ => input[self.start .. self.start + self.len].to_string(),
+      TokenKind::Syllable(s) => s.write_mlcts(w),
+      TokenKind::SpecialMapped(s) | TokenKind::Escaped(s) => w.write_str(s),
+      // a token of another input must not panic
+      _ => w.write_str(
+        input
+          .get(self.start .. self.start + self.len)
+          .unwrap_or_default(),
+      ),
     }
   }
 }
 
 const EOF_CHAR: char = '\0';
 
+/// The maximum length of a syllable in bytes. Longer input is not parsed,
+/// see [`ParseError::TooLong`].
+pub const MAX_SYLLABLE_LEN: usize = 128;
+
+/// The maximum number of syllables stacked on each other, e.g. with ္ or ဿ,
+/// see [`ParseError::TooDeep`].
+pub const MAX_STACK_DEPTH: usize = 8;
+
+/// An error while parsing a Myanmar syllable.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError
+{
+  /// The input ends in the middle of a syllable, e.g. after ္.
+  UnexpectedEnd,
+  /// The character at the byte position cannot appear there.
+  UnexpectedChar
+  {
+    position: usize, found: char
+  },
+  /// The input is longer than [`MAX_SYLLABLE_LEN`] bytes.
+  TooLong
+  {
+    len: usize
+  },
+  /// More than [`MAX_STACK_DEPTH`] syllables are stacked on each other.
+  TooDeep,
+}
+
+impl ParseError
+{
+  /// Creates an error for the character at the byte position.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The input being parsed.
+  /// * `position` - The byte position of the character.
+  ///
+  /// # Returns
+  ///
+  /// [`ParseError::UnexpectedEnd`] if there is no character at the position,
+  /// [`ParseError::UnexpectedChar`] otherwise.
+  fn at(input: &str, position: usize) -> Self
+  {
+    match input.get(position ..).and_then(|rest| rest.chars().next())
+    {
+      Some(found) => Self::UnexpectedChar { position, found },
+      None => Self::UnexpectedEnd,
+    }
+  }
+
+  /// Shift the position of an error found in a part of the input.
+  ///
+  /// # Arguments
+  ///
+  /// * `by` - The byte position of the part in the input.
+  ///
+  /// # Returns
+  ///
+  /// The error with the position in the whole input.
+  fn offset(self, by: usize) -> Self
+  {
+    match self
+    {
+      Self::UnexpectedChar { position, found } => Self::UnexpectedChar {
+        position: position + by,
+        found,
+      },
+      other => other,
+    }
+  }
+}
+
+impl fmt::Display for ParseError
+{
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result
+  {
+    match self
+    {
+      Self::UnexpectedEnd => write!(f, "unexpected end of syllable"),
+      Self::UnexpectedChar { position, found } =>
+      {
+        write!(f, "unexpected {:?} at byte {}", found, position)
+      }
+      Self::TooLong { len } =>
+      {
+        write!(f, "syllable of {} bytes, at most {}", len, MAX_SYLLABLE_LEN)
+      }
+      Self::TooDeep =>
+      {
+        write!(f, "more than {} stacked syllables", MAX_STACK_DEPTH)
+      }
+    }
+  }
+}
+
+impl std::error::Error for ParseError
+{
+}
+
+/// Parse a Myanmar syllable, as split by [`split_syllables`]. The parser
+/// never panics, any input it cannot read is reported as an error.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::{syllable_from_myanmar, ParseError};
+///
+/// let (syllable, len) = syllable_from_myanmar("မင်္ဂ").unwrap();
+/// assert_eq!(syllable.to_mlcts(), "mangga.");
+/// assert_eq!(len, "မင်္ဂ".len());
+///
+/// assert_eq!(syllable_from_myanmar("ကင်္"), Err(ParseError::UnexpectedEnd));
+/// assert_eq!(
+///   syllable_from_myanmar("ာ"),
+///   Err(ParseError::UnexpectedChar {
+///     position: 0,
+///     found: 'ာ'
+///   })
+/// );
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar syllable.
+///
+/// # Returns
+///
+/// The syllable and the length of bytes used by it, or the reason it cannot
+/// be parsed.
+pub fn syllable_from_myanmar(
+  input: &str,
+) -> Result<(Syllable, usize), ParseError>
+{
+  parse_syllable(input).map(|r| (r.syllable, r.consumed_len))
+}
+
 /// Get the token iterator from the input.
 /// This function internally uses [`split_syllables`].
 /// So, splitting semantics are the same as [`split_syllables`].
 ///
+/// A split syllable may hold several syllables, e.g. ကက်က် or a stack. When
+/// parsing fails inside it, the longest part which parses up to the next
+/// consonant starting a syllable is kept, or else the bytes up to that
+/// consonant are skipped as a [`TokenKind::Other`] token. Parsing resumes
+/// there.
+///
 /// # Arguments
 ///
 /// * `input` - The input to split.
@@ -118,32 +974,97 @@ fn get_token<'i>(input: &'i str) -> impl Iterator<Item = Token<'i>> + use<'i>
 {
   split_syllables(input)
     .into_iter()
-    .map(|(input, start, len)| {
-      let r = match input
+    .flat_map(|(input, start, len)| match input
+    {
+      "။" => vec![Token::new(TokenKind::SpecialMapped("."), start, len)],
+      "၊" => vec![Token::new(TokenKind::SpecialMapped(","), start, len)],
+      _ if input.starts_with("<<") =>
       {
-        "။" =>
-        {
-          return Token::new(TokenKind::SpecialMapped("."), start, len);
-        }
-        "၊" =>
-        {
-          return Token::new(TokenKind::SpecialMapped(","), start, len);
-        }
-        _ => parse_syllable(input),
-      };
+        vec![Token::new(TokenKind::Other, start, len)]
+      }
+      _ => match input.strip_prefix("{{").and_then(|s| s.strip_suffix("}}"))
+      {
+        Some("") => vec![],
+        Some(text) => vec![Token::new(TokenKind::Escaped(text), start, len)],
+        None => parse_tokens(input, start),
+      },
+    })
+}
 
-      if r.is_err()
+/// Parse the syllables of a split syllable, skipping what cannot be parsed.
+///
+/// # Arguments
+///
+/// * `input` - The split syllable.
+/// * `start` - The start position of the split syllable in the input.
+///
+/// # Returns
+///
+/// The syllable tokens and the skipped spans as [`TokenKind::Other`].
+fn parse_tokens<'i>(input: &str, start: usize) -> Vec<Token<'i>>
+{
+  let mut tokens = Vec::new();
+  let mut pos = 0;
+  while pos < input.len()
+  {
+    let rest = &input[pos ..];
+    let parsed = parse_syllable(rest)
+      .ok()
+      .filter(|syl| syl.consumed_len > 0)
+      .or_else(|| {
+        // the longest part which is a whole syllable
+        let ends = syllable_boundaries(rest)
+          .take_while(|&end| end <= MAX_SYLLABLE_LEN)
+          .collect::<Vec<_>>();
+        ends.into_iter().rev().find_map(|end| {
+          parse_syllable(&rest[.. end])
+            .ok()
+            .filter(|syl| syl.consumed_len == end)
+        })
+      });
+
+    match parsed
+    {
+      Some(syl) =>
       {
-        return Token::new(TokenKind::Other, start, len);
+        tokens.push(Token::new(
+          TokenKind::Syllable(syl.syllable),
+          start + pos,
+          syl.consumed_len,
+        ));
+        pos += syl.consumed_len;
       }
+      None =>
+      {
+        let len = syllable_boundaries(rest).next().unwrap_or(rest.len());
+        tokens.push(Token::new(TokenKind::Other, start + pos, len));
+        pos += len;
+      }
+    }
+  }
+  tokens
+}
 
-      let syl = r.unwrap();
-      return Token::new(
-        TokenKind::Syllable(syl.syllable),
-        start,
-        syl.consumed_len,
-      );
+/// Find the consonants inside a split syllable which may start another
+/// syllable, i.e. which are not the bottom of a stack.
+///
+/// # Arguments
+///
+/// * `input` - The rest of the split syllable.
+///
+/// # Returns
+///
+/// The byte positions of the consonants, after the first character.
+fn syllable_boundaries(input: &str) -> impl Iterator<Item = usize> + '_
+{
+  input
+    .char_indices()
+    .scan(EOF_CHAR, |prev, (i, c)| {
+      let is_start = i > 0 && matches!(c, 'က' ..= 'အ') && *prev != '္';
+      *prev = c;
+      Some(is_start.then_some(i))
     })
+    .flatten()
 }
 
 /// Parse the consonant from the input.
@@ -155,18 +1076,16 @@ fn get_token<'i>(input: &'i str) -> impl Iterator<Item = Token<'i>> + use<'i>
 /// # Returns
 ///
 /// The parsed consonant and the length of bytes used by the consonant.
-fn parse_consonant(input: &str) -> Result<(Consonant, usize), &str>
+fn parse_consonant(input: &str) -> Result<(Consonant, usize), ParseError>
 {
   let mut cursor = input.chars();
   let current = cursor.next().unwrap_or_else(|| EOF_CHAR);
 
-  let consonant = BasicConsonant::from_myanmar_alphabet(current);
-  if consonant.is_err()
+  let Ok(consonant) = BasicConsonant::from_myanmar_alphabet(current)
+  else
   {
-    return Err(input);
-  }
-
-  let consonant = consonant.unwrap();
+    return Err(ParseError::at(input, 0));
+  };
   let consonant = if consonant == BasicConsonant::A
   {
     consonant!(A)
@@ -278,7 +1197,7 @@ enum ParseSpecialStartCharResult
 
 fn parse_special_start_char(
   input: &str,
-) -> Result<(ParseSpecialStartCharResult, usize), &str>
+) -> Result<(ParseSpecialStartCharResult, usize), ParseError>
 {
   let mut cursor = input.chars();
   let first_char = cursor.next().unwrap_or_else(|| EOF_CHAR);
@@ -342,7 +1261,7 @@ fn parse_special_start_char(
       }
       else
       {
-        Err(input)
+        Err(ParseError::at(input, 0))
       }
     }
     'ဣ' =>
@@ -392,8 +1311,7 @@ fn parse_special_start_char(
         input.len() - cursor.as_str().len(),
       ))
     }
-    '။' => Err("."),
-    '၊' => Err(","),
+    '။' | '၊' => Err(ParseError::at(input, 0)),
     _ => parse_consonant(input)
       .map(|(c, l)| (ParseSpecialStartCharResult::Consonant(c), l)),
   }
@@ -406,16 +1324,36 @@ struct ParseSyllableResult
   consumed_len: usize,
 }
 
+/// A level of a stacked syllable parsed by [`parse_level`].
+#[derive(Debug)]
+enum Level
+{
+  /// The syllable ends at this level.
+  Done(ParseSyllableResult),
+  /// A bottom syllable is stacked under the consonant and the vowel.
+  Stacked
+  {
+    consonant: Consonant,
+    vowel: Vowel,
+    /// The byte position where the bottom syllable starts.
+    bottom: usize,
+    /// The onset of the bottom syllable which is not written in the input,
+    /// e.g. သ for ဿ.
+    onset: Option<char>,
+  },
+}
+
 macro_rules! parse_syl_result {
   ($syllable:expr, $consumed_len:expr) => {
-    Ok(ParseSyllableResult {
+    Ok(Level::Done(ParseSyllableResult {
       syllable: $syllable,
       consumed_len: $consumed_len,
-    })
+    }))
   };
 }
 
-/// Parse the syllable from the input.
+/// Parse the syllable from the input. The syllables stacked under each
+/// other are parsed one level after another.
 ///
 /// # Arguments
 ///
@@ -424,19 +1362,112 @@ macro_rules! parse_syl_result {
 /// # Returns
 ///
 /// The parsed syllable and the length of bytes used by the syllable.
-fn parse_syllable<'i>(syllable: &'i str)
-  -> Result<ParseSyllableResult, &'i str>
+fn parse_syllable(syllable: &str) -> Result<ParseSyllableResult, ParseError>
 {
-  let (first, consumed_bytes_by_first) = parse_special_start_char(syllable)?;
+  if syllable.len() > MAX_SYLLABLE_LEN
+  {
+    return Err(ParseError::TooLong {
+      len: syllable.len(),
+    });
+  }
 
-  if let ParseSpecialStartCharResult::Syllable(s) = first
+  // the consonants and vowels above the bottom syllable, from the top
+  let mut tops = Vec::new();
+  let mut start = 0;
+  let mut onset = None;
+  let mut buf = [0; MAX_SYLLABLE_LEN + 4];
+  loop
   {
-    return parse_syl_result!(s, consumed_bytes_by_first);
+    let input = with_onset(&mut buf, onset, &syllable[start ..]);
+    // positions in `input` are shifted by the onset
+    let shift = start - onset.map_or(0, char::len_utf8);
+    match parse_level(input).map_err(|e| e.offset(shift))?
+    {
+      Level::Done(bottom) =>
+      {
+        let syllable = tops
+          .into_iter()
+          .rev()
+          .fold(bottom.syllable, |stacked, (consonant, vowel)| {
+            syllable!(consonant, vowel, stacked)
+          });
+        return Ok(ParseSyllableResult {
+          syllable,
+          consumed_len: shift + bottom.consumed_len,
+        });
+      }
+      Level::Stacked {
+        consonant,
+        vowel,
+        bottom,
+        onset: next,
+      } =>
+      {
+        if tops.len() == MAX_STACK_DEPTH
+        {
+          return Err(ParseError::TooDeep);
+        }
+        tops.push((consonant, vowel));
+        start = shift + bottom;
+        onset = next;
+      }
+    }
   }
+}
+
+/// Put the onset which is not written in the input before the rest of it,
+/// without allocating.
+///
+/// # Arguments
+///
+/// * `buf` - The buffer to write into, at least 4 bytes longer than `rest`.
+/// * `onset` - The onset.
+/// * `rest` - The rest of the input.
+///
+/// # Returns
+///
+/// The input starting with the onset.
+fn with_onset<'b>(
+  buf: &'b mut [u8],
+  onset: Option<char>,
+  rest: &'b str,
+) -> &'b str
+{
+  let Some(onset) = onset
+  else
+  {
+    return rest;
+  };
+  let len = onset.encode_utf8(buf).len() + rest.len();
+  buf[onset.len_utf8() .. len].copy_from_slice(rest.as_bytes());
+  std::str::from_utf8(&buf[.. len]).unwrap_or_default()
+}
+
+// `rhyme_from_signs` and `RHYMES`, generated from rhymes.csv by build.rs
+include!(concat!(env!("OUT_DIR"), "/rhymes.rs"));
+
+/// Parse one level of the syllable from the input: a whole syllable, or the
+/// consonant and the vowel a bottom syllable is stacked under.
+///
+/// # Arguments
+///
+/// * `syllable` - Possible syllable input
+///
+/// # Returns
+///
+/// The parsed level.
+fn parse_level(syllable: &str) -> Result<Level, ParseError>
+{
+  let (first, consumed_bytes_by_first) = parse_special_start_char(syllable)?;
 
   // skip the consumed bytes
   let input_len_left = syllable.len();
-  let mut cursor = syllable[consumed_bytes_by_first ..].chars();
+  let mut cursor = syllable
+    .get(consumed_bytes_by_first ..)
+    .unwrap_or_default()
+    .chars();
+  // the bytes consumed before the cursor, used to locate errors
+  let consumed = |cursor: &Chars<'_>| input_len_left - cursor.as_str().len();
 
   // if the consumed length is equal to the syllable length,
   if cursor.as_str().is_empty()
@@ -457,7 +1488,10 @@ fn parse_syllable<'i>(syllable: &'i str)
           input_len_left - cursor.as_str().len()
         );
       }
-      _ => unreachable!(),
+      ParseSpecialStartCharResult::Syllable(s) =>
+      {
+        return parse_syl_result!(s, consumed_bytes_by_first);
+      }
     }
   }
 
@@ -466,9 +1500,25 @@ fn parse_syllable<'i>(syllable: &'i str)
 
   let (consonant, mut vowel) = match first
   {
+    // e.g. ဦး
+    ParseSpecialStartCharResult::Vowel(mut v) if cursor.as_str() == "း" =>
+    {
+      cursor.next();
+      v.tone = Some(Tone::High);
+      return parse_syl_result!(
+        syllable!(consonant!(A), v),
+        input_len_left - cursor.as_str().len()
+      );
+    }
     ParseSpecialStartCharResult::Vowel(v) => (consonant!(A), v),
     ParseSpecialStartCharResult::Consonant(consonant) =>
     {
+      // the whole rhymes of rhymes.csv
+      if let Some(vowel) = rhyme_from_signs(cursor.as_str())
+      {
+        return parse_syl_result!(syllable!(consonant, vowel), input_len_left);
+      }
+
       let v_pos_1 = cursor.clone().next().unwrap_or_else(|| EOF_CHAR);
       let v_pos_2 = cursor.clone().nth(1).unwrap_or_else(|| EOF_CHAR);
       let v_pos_3 = cursor.clone().nth(2).unwrap_or_else(|| EOF_CHAR);
@@ -476,64 +1526,24 @@ fn parse_syllable<'i>(syllable: &'i str)
       const VOW_E: char = 'ေ';
       const VOW_A: char = 'ာ';
       const VOW_A_ALT: char = 'ါ';
-      const VOW_AI_H: char = 'ဲ';
       const TONE_CREAKY: char = '့';
       const TONE_HIGH: char = 'း';
-      const VOW_I: char = 'ီ';
       const VOW_I_CREAKY: char = 'ိ';
       const VOW_U_CREAKY: char = 'ု';
-      const VOW_U: char = 'ူ';
       const VOW_M: char = 'ံ';
 
       let used_vowel = match (v_pos_1, v_pos_2, v_pos_3)
       {
-        // e.g. ား
-        (VOW_A | VOW_A_ALT, TONE_HIGH, EOF_CHAR) =>
-        {
-          cursor.next();
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(A; High)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
-        // e.g. ာ EOF
-        (VOW_A | VOW_A_ALT, EOF_CHAR, EOF_CHAR) =>
-        {
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(A)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
         // e.g. ာ
         (VOW_A | VOW_A_ALT, ..) =>
         {
           cursor.next();
           vowel!(A)
         }
-        // e.g. ံ
-        (VOW_M, EOF_CHAR, EOF_CHAR) =>
-        {
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(A, M)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
-        // e.g. ယ်
-        ('ယ', ASAT, EOF_CHAR) =>
+        // e.g. ယ့်
+        ('ယ', ASAT, TONE_CREAKY) =>
         {
           cursor.next();
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(Ai)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
-        // e.g. ဲ့
-        (VOW_AI_H, TONE_CREAKY, EOF_CHAR) =>
-        {
           cursor.next();
           cursor.next();
           return parse_syl_result!(
@@ -541,15 +1551,6 @@ fn parse_syllable<'i>(syllable: &'i str)
             input_len_left - cursor.as_str().len()
           );
         }
-        // e.g. ဲ
-        (VOW_AI_H, EOF_CHAR, EOF_CHAR) =>
-        {
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(Ai; High)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
         // e.g. ော်
         (VOW_E, VOW_A | VOW_A_ALT, ASAT) =>
         {
@@ -572,16 +1573,6 @@ fn parse_syllable<'i>(syllable: &'i str)
             input_len_left - cursor.as_str().len()
           );
         }
-        // e.g. ော EOF
-        (VOW_E, VOW_A | VOW_A_ALT, EOF_CHAR) =>
-        {
-          cursor.next();
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(Au; High)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
         // e.g. ော
         (VOW_E, VOW_A | VOW_A_ALT, ..) =>
         {
@@ -592,34 +1583,6 @@ fn parse_syllable<'i>(syllable: &'i str)
                      // stacked
                      // consonant
         }
-        // e.g. ူ
-        (VOW_U, EOF_CHAR, EOF_CHAR) =>
-        {
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(U)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
-        // e.g. ူး
-        (VOW_U, TONE_HIGH, EOF_CHAR) =>
-        {
-          cursor.next();
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(U; High)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
-        // e.g. ု EOF
-        (VOW_U_CREAKY, EOF_CHAR, EOF_CHAR) =>
-        {
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(U; Creaky)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
         // e.g. ုံး
         (VOW_U_CREAKY, VOW_M, TONE_HIGH) =>
         {
@@ -642,16 +1605,6 @@ fn parse_syllable<'i>(syllable: &'i str)
             input_len_left - cursor.as_str().len()
           );
         }
-        // e.g. ုံ EOF
-        (VOW_U_CREAKY, VOW_M, EOF_CHAR) =>
-        {
-          cursor.next();
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(Au, M)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
         // e.g. ု
         (VOW_U_CREAKY, ..) =>
         {
@@ -681,16 +1634,6 @@ fn parse_syllable<'i>(syllable: &'i str)
             input_len_left - cursor.as_str().len()
           );
         }
-        // e.g. ို EOF
-        (VOW_I_CREAKY, VOW_U_CREAKY, EOF_CHAR) =>
-        {
-          cursor.next();
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(Ui)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
         // e.g. ို
         (VOW_I_CREAKY, VOW_U_CREAKY, ..) =>
         {
@@ -701,34 +1644,6 @@ fn parse_syllable<'i>(syllable: &'i str)
                      // stacked
                      // consonant
         }
-        // e.g. ီ
-        (VOW_I, EOF_CHAR, EOF_CHAR) =>
-        {
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(I)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
-        // e.g. ီး
-        (VOW_I, TONE_HIGH, EOF_CHAR) =>
-        {
-          cursor.next();
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(I; High)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
-        // e.g. ိ EOF
-        (VOW_I_CREAKY, EOF_CHAR, EOF_CHAR) =>
-        {
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(I; Creaky)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
         // e.g. ိ
         (VOW_I_CREAKY, ..) =>
         {
@@ -738,35 +1653,6 @@ fn parse_syllable<'i>(syllable: &'i str)
                     // stacked
                     // consonant
         }
-        // e.g. ေး
-        (VOW_E, TONE_HIGH, EOF_CHAR) =>
-        {
-          cursor.next();
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(E; High)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
-        // e.g. ေ့
-        (VOW_E, TONE_CREAKY, EOF_CHAR) =>
-        {
-          cursor.next();
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(E; Creaky)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
-        // e.g. ေ EOF
-        (VOW_E, EOF_CHAR, EOF_CHAR) =>
-        {
-          cursor.next();
-          return parse_syl_result!(
-            syllable!(consonant, vowel!(E)),
-            input_len_left - cursor.as_str().len()
-          );
-        }
         // e.g. ေ
         (VOW_E, ..) =>
         {
@@ -783,12 +1669,16 @@ fn parse_syllable<'i>(syllable: &'i str)
 
       (consonant, used_vowel)
     }
-    ParseSpecialStartCharResult::Syllable(_) => unreachable!(),
+    ParseSpecialStartCharResult::Syllable(s) =>
+    {
+      return parse_syl_result!(s, consumed_bytes_by_first);
+    }
   };
 
   // almost all possible ending of a syllable
   // is already handled above, so it's safe to assume
   // that next character is a consonant.
+  let top_position = consumed(&cursor);
   let top_consonant = cursor.next().unwrap_or_else(|| EOF_CHAR);
   let sign = cursor.clone().next().unwrap_or_else(|| EOF_CHAR);
   let virama_sign_or_bottom_consonant =
@@ -815,34 +1705,41 @@ fn parse_syllable<'i>(syllable: &'i str)
     {
       cursor.next();
       vowel.virama = Some(Virama::K);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(r) => r,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
+    }
+    // e.g. ယောက်ျား, the final က is also the onset of ကျား
+    ('က', ASAT, 'ျ', ..) =>
+    {
+      cursor.next();
+      vowel.virama = Some(Virama::K);
+      // the bottom syllable starts with the final က
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: Some('က'),
+      });
     }
     ('ဂ', STACK_SIGN, 'ဂ' | 'ဃ', ..) =>
     {
       vowel.virama = Some(Virama::G);
       cursor.next();
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('င', ASAT, STACK_SIGN, EOF_CHAR) =>
     {
       // this is invalid
-      return Err(syllable);
+      return Err(ParseError::UnexpectedEnd);
     }
     ('င', ASAT, STACK_SIGN, ..) =>
     {
@@ -850,15 +1747,12 @@ fn parse_syllable<'i>(syllable: &'i str)
       cursor.next();
       cursor.next();
 
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('င', ASAT, EOF_CHAR, EOF_CHAR) =>
     {
@@ -904,29 +1798,23 @@ fn parse_syllable<'i>(syllable: &'i str)
     {
       cursor.next();
       vowel.virama = Some(Virama::C);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('ဇ', STACK_SIGN, 'ဇ' | 'ဈ', ..) =>
     {
       cursor.next();
       vowel.virama = Some(Virama::J);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('ည' | 'ဉ', ASAT, EOF_CHAR, EOF_CHAR) =>
     {
@@ -963,15 +1851,12 @@ fn parse_syllable<'i>(syllable: &'i str)
     {
       cursor.next();
       vowel.virama = Some(Virama::Ny);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('ဋ', ASAT, EOF_CHAR, EOF_CHAR) =>
     {
@@ -986,29 +1871,23 @@ fn parse_syllable<'i>(syllable: &'i str)
     {
       cursor.next();
       vowel.virama = Some(Virama::T);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('ဍ', STACK_SIGN, 'ဍ' | 'ဎ', ..) =>
     {
       cursor.next();
       vowel.virama = Some(Virama::D);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('ဏ', ASAT, EOF_CHAR, EOF_CHAR) =>
     {
@@ -1023,15 +1902,12 @@ fn parse_syllable<'i>(syllable: &'i str)
     {
       cursor.next();
       vowel.virama = Some(Virama::N);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('တ', ASAT, EOF_CHAR, EOF_CHAR) =>
     {
@@ -1046,43 +1922,34 @@ fn parse_syllable<'i>(syllable: &'i str)
     {
       cursor.next();
       vowel.virama = Some(Virama::T);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('ထ', STACK_SIGN, 'ထ', ..) =>
     {
       cursor.next();
       vowel.virama = Some(Virama::Ht);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
-    ('ဒ', STACK_SIGN, 'ဒ', ..) =>
+    ('ဒ', STACK_SIGN, 'ဒ' | 'ဓ', ..) =>
     {
       cursor.next();
       vowel.virama = Some(Virama::D);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('န', ASAT, EOF_CHAR, EOF_CHAR) =>
     {
@@ -1119,15 +1986,12 @@ fn parse_syllable<'i>(syllable: &'i str)
     {
       cursor.next();
       vowel.virama = Some(Virama::N);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('ပ', ASAT, EOF_CHAR, EOF_CHAR) =>
     {
@@ -1142,29 +2006,23 @@ fn parse_syllable<'i>(syllable: &'i str)
     {
       cursor.next();
       vowel.virama = Some(Virama::P);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('ဗ', STACK_SIGN, 'ဗ' | 'ဘ', ..) =>
     {
       cursor.next();
       vowel.virama = Some(Virama::B);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('မ', ASAT, EOF_CHAR, EOF_CHAR) =>
     {
@@ -1201,31 +2059,23 @@ fn parse_syllable<'i>(syllable: &'i str)
     {
       cursor.next();
       vowel.virama = Some(Virama::M);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
     ('ဿ', ..) =>
     {
       vowel.virama = Some(Virama::S);
-      let new_str = format!("သ{}", cursor.as_str());
-      let mut c = match parse_syllable(&new_str)
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      c.consumed_len -= "သ".len();
-
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      // the bottom syllable starts with သ, which ဿ stands for
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: Some('သ'),
+      });
     }
     ('လ', ASAT, EOF_CHAR, EOF_CHAR) =>
     {
@@ -1238,19 +2088,16 @@ fn parse_syllable<'i>(syllable: &'i str)
     }
     ('လ', STACK_SIGN, 'လ', ..) =>
     {
-      cursor.next();
-      vowel.virama = Some(Virama::L);
-      let c = match parse_syllable(cursor.as_str())
-      {
-        Ok(c) => c,
-        Err(_) => return Err(syllable),
-      };
-      return parse_syl_result!(
-        syllable!(consonant, vowel, c.syllable),
-        input_len_left - (cursor.as_str().len() - c.consumed_len)
-      );
+      cursor.next();
+      vowel.virama = Some(Virama::L);
+      return Ok(Level::Stacked {
+        consonant,
+        vowel,
+        bottom: consumed(&cursor),
+        onset: None,
+      });
     }
-    _ => Err(syllable),
+    _ => Err(ParseError::at(syllable, top_position)),
   }
 }
 
@@ -1261,6 +2108,8 @@ fn parse_syllable<'i>(syllable: &'i str)
 ///   top consonant. (E.g. တက္ကသိုလ် is split as တက္က သိုလ်)
 /// * Any other characters apart from Myanmar consonants, vowel related symbols
 ///   and ဿ are considered as a single syllable.
+/// * The input is expected to be normalized with
+///   [`normalize::normalize_myanmar`].
 ///
 /// # Example
 ///
@@ -1282,41 +2131,203 @@ fn parse_syllable<'i>(syllable: &'i str)
 /// A vector of syllables.
 pub fn split_syllables<'i>(input: &'i str) -> Vec<(&'i str, usize, usize)>
 {
-  // this regex is borrowed from https://github.com/ye-kyaw-thu/myWord.
-  // original regex:
-  // let p = format!(
-  //   r"((?<!္)([က-အ])(?![်္])|[a-zA-Z0-9{}])",
-  //   "ဣဤဥဦဧဩဪ၌၍၏၎၀-၉၊။!-/:-@\\[-`{-~\\s"
-  // );
-
-  // modified regex:
-  let p = format!(
-    r"(<<.*?>>)|((?<!္)([က-အ])(?![်္])|{})",
-    "[^\u{102b}-\u{1032}\u{1036}-\u{103e}က-အဿ]"
-  );
-
-  let matches = Regex::new(&p)
-    .unwrap()
-    .find_iter(input)
-    .filter_map(Result::ok)
-    .map(|m| m.start())
-    .collect::<Vec<usize>>();
+  let matches = syllable_starts(input);
 
   matches
     .windows(2)
     .map(|w| {
       let start = w[0];
       let len = w[1] - start;
-      (input.get(start .. w[1]).unwrap(), start, len)
+      (input.get(start .. w[1]).unwrap_or_default(), start, len)
     })
     .chain(matches.last().map(|&l| {
       let start = l;
       let len = input.len() - start;
-      (input.get(start ..).unwrap(), start, len)
+      (input.get(start ..).unwrap_or_default(), start, len)
     }))
     .collect()
 }
 
+/// Characters continuing the previous emoji: the zero width joiner, variation
+/// selectors, the keycap, skin tone modifiers and tags.
+#[cfg(feature = "regex")]
+const EMOJI_JOINERS: &str =
+  "\u{200d}\u{fe00}-\u{fe0f}\u{20e3}\u{1f3fb}-\u{1f3ff}\u{e0020}-\u{e007f}";
+
+/// Find the start position of every syllable with the regex.
+///
+/// # Arguments
+///
+/// * `input` - The input text to split.
+///
+/// # Returns
+///
+/// Start positions of the syllables.
+#[cfg(feature = "regex")]
+fn syllable_starts(input: &str) -> Vec<usize>
+{
+  splitter()
+    .find_iter(input)
+    .filter_map(Result::ok)
+    .map(|m| m.start())
+    .collect()
+}
+
+/// Get the syllable splitting regex, compiled on the first use.
+///
+/// # Returns
+///
+/// The compiled regex.
+#[cfg(feature = "regex")]
+fn splitter() -> &'static Regex
+{
+  static SPLITTER: OnceLock<Regex> = OnceLock::new();
+  SPLITTER.get_or_init(|| {
+    // this regex is borrowed from https://github.com/ye-kyaw-thu/myWord.
+    // original regex:
+    // let p = format!(
+    //   r"((?<!္)([က-အ])(?![်္])|[a-zA-Z0-9{}])",
+    //   "ဣဤဥဦဧဩဪ၌၍၏၎၀-၉၊။!-/:-@\\[-`{-~\\s"
+    // );
+
+    // modified regex, a mark not following a Myanmar character, an emoji
+    // with its joiners, CRLF and `{{...}}` escapes are also single syllables:
+    let p = format!(
+      r"(<<.*?>>)|(\{{\{{.*?\}}\}})|((?<!္)([က-အ])(?![်္])|{}|{}|\r\n|{})",
+      "(?<![\u{1000}-\u{109f}])[\u{102b}-\u{1032}\u{1036}-\u{103e}ဿ]",
+      format_args!("(?<![^\u{1000}-\u{109f}])[{}]", EMOJI_JOINERS),
+      format_args!(
+        "(?<!\u{200d})[^\u{102b}-\u{1032}\u{1036}-\u{103e}က-အဿ{}]",
+        EMOJI_JOINERS
+      )
+    );
+
+    Regex::new(&p).unwrap()
+  })
+}
+
+/// Find the start position of every syllable without the regex.
+///
+/// # Arguments
+///
+/// * `input` - The input text to split.
+///
+/// # Returns
+///
+/// Start positions of the syllables.
+#[cfg(not(feature = "regex"))]
+fn syllable_starts(input: &str) -> Vec<usize>
+{
+  scan_syllable_starts(input)
+}
+
+/// Hand-written equivalent of the syllable splitting regex used when the
+/// `regex` feature is disabled.
+///
+/// # Arguments
+///
+/// * `input` - The input text to split.
+///
+/// # Returns
+///
+/// Start positions of the syllables.
+#[cfg(any(test, not(feature = "regex")))]
+fn scan_syllable_starts(input: &str) -> Vec<usize>
+{
+  let mut starts = Vec::new();
+  let mut prev = EOF_CHAR;
+  let mut cursor = input.char_indices().peekable();
+
+  while let Some((pos, current)) = cursor.next()
+  {
+    // <<...>> and {{...}} on a single line are single syllables
+    if let Some(len) = protected_len(&input[pos ..])
+    {
+      starts.push(pos);
+      while cursor.next_if(|(p, _)| *p < pos + len).is_some()
+      {}
+      prev = '>';
+      continue;
+    }
+
+    let next = cursor.peek().map_or(EOF_CHAR, |(_, c)| *c);
+    let is_start = match current
+    {
+      'က' ..= 'အ' => prev != '္' && next != '်' && next != '္',
+      '\u{102b}' ..= '\u{1032}' | '\u{1036}' ..= '\u{103e}' | 'ဿ' =>
+      {
+        !is_myanmar(prev)
+      }
+      '\u{200d}'
+      | '\u{fe00}' ..= '\u{fe0f}'
+      | '\u{20e3}'
+      | '\u{1f3fb}' ..= '\u{1f3ff}'
+      | '\u{e0020}' ..= '\u{e007f}' => pos == 0 || is_myanmar(prev),
+      // CRLF is a single line break
+      '\n' => prev != '\u{200d}' && prev != '\r',
+      _ => prev != '\u{200d}',
+    };
+    if is_start
+    {
+      starts.push(pos);
+    }
+    prev = current;
+  }
+
+  starts
+}
+
+/// Check if a character is in the Myanmar block.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is a Myanmar character, `false` otherwise.
+fn is_myanmar(c: char) -> bool
+{
+  matches!(c, '\u{1000}' ..= '\u{109f}')
+}
+
+/// Get the length of the `<<...>>` or `{{...}}` block at the start of the
+/// input.
+///
+/// # Arguments
+///
+/// * `input` - The input starting with the block.
+///
+/// # Returns
+///
+/// The length of the block in bytes or `None` if the input does not start
+/// with a closed block.
+#[cfg(any(test, not(feature = "regex")))]
+fn protected_len(input: &str) -> Option<usize>
+{
+  block_len(input, "<<", ">>").or_else(|| block_len(input, "{{", "}}"))
+}
+
+/// Get the length of a block closed on the same line at the start of the
+/// input.
+///
+/// # Arguments
+///
+/// * `input` - The input starting with the block.
+/// * `open` - The marker opening the block.
+/// * `close` - The marker closing the block.
+///
+/// # Returns
+///
+/// The length of the block in bytes or `None` if the input does not start
+/// with a closed block.
+fn block_len(input: &str, open: &str, close: &str) -> Option<usize>
+{
+  let content = input.strip_prefix(open)?;
+  let line = &content[.. content.find('\n').unwrap_or(content.len())];
+  line.find(close).map(|end| end + open.len() + close.len())
+}
+
 #[cfg(test)]
 mod tests
 {
@@ -1383,6 +2394,51 @@ mod tests
       "a", "u", "t", "o", "g", "r", "a", "p", "h", "?"
     ];
     assert_eq!(syllables.iter().map(|s| s.0).collect::<Vec<_>>(), expected);
+
+    let input = "❤️ချစ်တယ်😀ာ 👍🏽 👨\u{200d}👩\u{200d}👧 “ကို” café";
+    let syllables = super::split_syllables(input);
+    #[rustfmt::skip]
+    let expected = vec![
+      "❤️", "ချစ်", "တယ်", "😀", "ာ", " ", "👍🏽", " ",
+      "👨\u{200d}👩\u{200d}👧", " ", "“", "ကို", "”", " ", "c", "a", "f", "é",
+    ];
+    assert_eq!(syllables.iter().map(|s| s.0).collect::<Vec<_>>(), expected);
+
+    // nothing before the first syllable is dropped
+    let input = "ာက";
+    let syllables = super::split_syllables(input);
+    assert_eq!(
+      syllables.iter().map(|s| s.0).collect::<Vec<_>>(),
+      vec!["ာ", "က"]
+    );
+  }
+
+  #[test]
+  #[cfg(feature = "regex")]
+  fn test_scan_syllable_starts()
+  {
+    let inputs = [
+      "",
+      "ကျွန်တော်ကသုတေသနသမားပါ။\nနေ့ရောညရော မြန်မာစာ",
+      "ပိဿာ သန္နိဋ္ဌာန် မင်္ဂလာပါ赵露思။",
+      "ာက္ ်က ္က က္",
+      "<<abc>> <<a\nb>> <<>> <<<x>>> <<< >> << ",
+      "{{abc}} {{a\nb}} {{}} {{{x}}} {{{ }} {{ <<{{>>}} {{<<}}>>",
+      "Can I get your autograph? 👍🏽\r\n",
+      "ာ😀ာ ❤️ချစ်တယ် 👨\u{200d}👩\u{200d}👧 1\u{fe0f}\u{20e3}\u{fe0f}",
+      "“ကို” café ကား\u{200d}ာ",
+      "a\r\n\n\r\rb\r\u{200d}\n",
+    ];
+
+    for input in inputs
+    {
+      assert_eq!(
+        super::scan_syllable_starts(input),
+        super::syllable_starts(input),
+        "{:?}",
+        input
+      );
+    }
   }
 
   #[test]
@@ -1412,6 +2468,43 @@ mod tests
     let mlcts = super::mlcts_from_myanmar(input);
     assert_eq!(mlcts, "lany:kaung: sany");
 
+    // dot below and asat in both orders
+    let input = "မြင\u{1037}\u{103A} မြင\u{103A}\u{1037}";
+    let mlcts = super::mlcts_from_myanmar(input);
+    assert_eq!(mlcts, "mrang.   mrang.");
+
+    let input = "ကောင\u{1037}\u{103A}ကိုင\u{1037}\u{103A}ပြည\u{1037}\u{103A}";
+    let mlcts = super::mlcts_from_myanmar(input);
+    assert_eq!(mlcts, "kaung. kuing. prany.");
+
+    let input = "ကန\u{1037}\u{103A}ကုန\u{1037}\u{103A}ကမ\u{1037}\u{103A}";
+    let mlcts = super::mlcts_from_myanmar(input);
+    assert_eq!(mlcts, "kan. kun. kam.");
+
+    // composed and decomposed sequences
+    let composed = super::mlcts_from_myanmar("ဦး ဪ ၎င်း");
+    let decomposed = super::mlcts_from_myanmar(
+      "\u{1025}\u{102E}း \u{1029}\u{103A} \u{1044}\u{1004}\u{103A}\u{1038}",
+    );
+    assert_eq!(composed, "u:   au   lany:kaung:");
+    assert_eq!(decomposed, composed);
+
+    // emoji are single syllables with their joiners
+    let input = "ဟုတ်ကဲ့ 👍🏽 မနက်ဖြန်တွေ့မယ်နော် 😀❤️";
+    let mlcts = super::mlcts_from_myanmar(input);
+    assert_eq!(mlcts, "hut kai.   👍🏽   ma. nak hpran twe. mai nau   😀 ❤️");
+    let input = "မိသားစု 👨\u{200D}👩\u{200D}👧";
+    let mlcts = super::mlcts_from_myanmar(input);
+    assert_eq!(mlcts, "mi. sa: cu.   👨\u{200D}👩\u{200D}👧");
+
+    // autofix before the conversion
+    let options = super::ConvertOptions {
+      autofix: true,
+      ..Default::default()
+    };
+    let mlcts = super::mlcts_from_myanmar_with_options("ကေျာင်း", &options);
+    assert_eq!(mlcts, "kyaung:");
+
     let input = "ကျွန်တော်ကသုတေသနသမားပါ။\n\
   နေ့ရောညရောမြန်မာစာနဲ့ကွန်ပျူတာနဲ့ပဲအလုပ် များ ပါ တယ်။\n\
   မင်းကကောဘာအလုပ်လုပ်တာလဲ။\n\
@@ -1428,4 +2521,422 @@ mod tests
     kau hpi lany: htap sauk hkyang rang prau: kwa"
     );
   }
+
+  #[test]
+  fn test_medial_w_rhymes()
+  {
+    for (input, expected) in [
+      ("မွန်", "mwan"),
+      ("ကွန်း", "kwan:"),
+      ("ကွန့်", "kwan."),
+      ("ထွတ်", "htwat"),
+      ("ကွက်", "kwak"),
+      ("ကွပ်", "kwap"),
+      ("ကွစ်", "kwac"),
+      ("လွင်", "lwang"),
+      ("ကွင်း", "kwang:"),
+      ("ကွင့်", "kwang."),
+      ("ကွည့်", "kwany."),
+      ("ကွမ်း", "kwam:"),
+      ("ကွမ့်", "kwam."),
+      ("ကွံ", "kwam"),
+      ("ကွံ့", "kwam."),
+      ("ခံ့", "hkam."),
+      ("ကျွန်", "kywan"),
+      ("ကြွက်", "krwak"),
+      ("မြွှင့်", "hmrwang."),
+    ]
+    {
+      assert_eq!(super::mlcts_from_myanmar(input), expected, "{}", input);
+    }
+  }
+
+  #[test]
+  fn test_contracted_syllables()
+  {
+    for (input, expected) in [
+      ("ယောက်ျား", "yaukkya:"),
+      ("ယောက်ျားလေး", "yaukkya: le:"),
+      ("ဗုဒ္ဓ", "buddha."),
+      ("ဗုဒ္ဓံ", "buddham"),
+      ("ဗုဒ္ဓဘာသာ", "buddha. bha sa"),
+    ]
+    {
+      assert_eq!(super::mlcts_from_myanmar(input), expected, "{}", input);
+    }
+
+    let (_, len) = super::syllable_from_myanmar("ယောက်ျား").unwrap();
+    assert_eq!(len, "ယောက်ျား".len());
+    assert_eq!(
+      super::syllable_from_myanmar("ယောက်ျ😀"),
+      Err(super::ParseError::UnexpectedChar {
+        position: "ယောက်ျ".len(),
+        found: '😀'
+      })
+    );
+  }
+
+  #[test]
+  fn test_line_breaks()
+  {
+    use super::normalize::Newline;
+    use super::stream::StreamConverter;
+
+    let input = "\u{FEFF}မင်္ဂလာပါ\r\nခင်ဗျာ\nပါ\r\n";
+    let syllables = super::split_syllables(input);
+    assert!(syllables.iter().any(|s| s.0 == "\r\n"));
+    assert!(!syllables.iter().any(|s| s.0 == "\r"));
+
+    // the byte order mark is removed, CRLF and LF are kept per line
+    let mlcts = super::mlcts_from_myanmar(input);
+    assert_eq!(mlcts, "mangga. la pa \r\n hkang bya \n pa \r\n");
+
+    let mut converter = StreamConverter::default();
+    converter.push_chunk("\u{FEFF}");
+    converter.push_chunk(&input["\u{FEFF}".len() ..]);
+    assert_eq!(converter.finish(), mlcts);
+
+    for (newline, expected) in [
+      (Newline::Lf, "mangga. la pa \n hkang bya \n pa \n"),
+      (Newline::CrLf, "mangga. la pa \r\n hkang bya \r\n pa \r\n"),
+    ]
+    {
+      let options = super::ConvertOptions {
+        newline,
+        ..Default::default()
+      };
+      assert_eq!(
+        super::mlcts_from_myanmar_with_options(input, &options),
+        expected
+      );
+    }
+
+    let options = super::ConvertOptions {
+      western_punctuation: true,
+      newline: Newline::Lf,
+      ..Default::default()
+    };
+    assert_eq!(
+      super::mlcts_from_myanmar_with_options("ပါ။\r\nပါ။\r\n", &options),
+      "pa.\npa.\n"
+    );
+  }
+
+  #[test]
+  fn test_mlcts_from_myanmar_to()
+  {
+    let input = "ကျွန်တော်ကသုတေသနသမားပါ။\nမင်္ဂလာပါ";
+    let options = super::ConvertOptions {
+      western_punctuation: true,
+      capitalize_sentences: true,
+      ..Default::default()
+    };
+    let expected = super::mlcts_from_myanmar_with_options(input, &options);
+
+    let mut output = String::new();
+    super::mlcts_from_myanmar_to(input, &options, &mut output).unwrap();
+    assert_eq!(output, expected);
+
+    let mut output = Vec::new();
+    super::mlcts_from_myanmar_to_io(input, &options, &mut output).unwrap();
+    assert_eq!(String::from_utf8(output).unwrap(), expected);
+
+    // writing stops at the first error
+    let mut output = [0; 8];
+    let err =
+      super::mlcts_from_myanmar_to_io(input, &options, &mut &mut output[..]);
+    assert!(err.is_err());
+    assert_eq!(&output, b"Kywan ta");
+  }
+
+  #[test]
+  fn test_convert_counters()
+  {
+    let mut dictionary = super::dictionary::UserDictionary::new();
+    dictionary.protect("ပိဋကတ်");
+    let options = super::ConvertOptions {
+      dictionary,
+      ..Default::default()
+    };
+
+    let (_, counters) =
+      super::mlcts_from_myanmar_with_counters("ပိဋကတ် ာ ကား abc", &options);
+    assert_eq!(
+      counters,
+      super::ConvertCounters {
+        syllables: 1,
+        repaired: 0,
+        terms: 1,
+        fallbacks: 1,
+      }
+    );
+
+    let mut total = super::ConvertCounters::default();
+    total += counters;
+    total += counters;
+    assert_eq!(total.terms, 2);
+  }
+
+  #[test]
+  fn test_error_recovery()
+  {
+    // only the bytes which cannot be parsed are passed through
+    let cases = [
+      ("မြန်န်မာ", "mran န် ma"),
+      ("တက္ကသ္", "takka. သ္"),
+      ("ကို့ို", "kui. ို"),
+      ("ကာာ", "ကာာ"),
+    ];
+    for (input, expected) in cases
+    {
+      assert_eq!(super::mlcts_from_myanmar(input), expected, "{}", input);
+    }
+
+    let (_, counters) =
+      super::mlcts_from_myanmar_with_counters("မြန်န်မာ", &Default::default());
+    assert_eq!((counters.syllables, counters.fallbacks), (2, 1));
+  }
+
+  #[test]
+  fn test_rhyme_table()
+  {
+    // every rhyme of rhymes.csv after simple and complex onsets
+    for (signs, vowel) in super::RHYMES
+    {
+      for onset in ["က", "မ", "ကျ", "မြွှ", "ယ"]
+      {
+        let input = format!("{}{}", onset, signs);
+        let (syllable, len) = super::syllable_from_myanmar(&input).unwrap();
+        assert_eq!(syllable.vowel, *vowel, "{}", input);
+        assert_eq!(syllable.stacked, None, "{}", input);
+        assert_eq!(len, input.len(), "{}", input);
+      }
+    }
+  }
+
+  #[test]
+  fn test_packed_syllables()
+  {
+    use mlcts_core::Syllable;
+
+    let consonants = ('က' ..= 'အ').collect::<Vec<_>>();
+    let signs = ["", "ိ", "ု", "ေ", "ော", "ို", "ွ"];
+    let tones = ["", "့", "း", "့်", "်း"];
+    let bottoms = ["", "ာ", "ါ", "ီး", "ု", "ေ့"];
+
+    let mut inputs = Vec::new();
+    // the rhymes of rhymes.csv and every final after simple onsets
+    for (rhyme, _) in super::RHYMES
+    {
+      inputs.extend(["က", "ကျ", "မြွှ"].map(|onset| format!("{onset}{rhyme}")));
+    }
+    for onset in ["က", "မ", "ပြ"]
+    {
+      for sign in signs
+      {
+        for final_ in &consonants
+        {
+          for tone in tones
+          {
+            inputs.push(format!("{onset}{sign}{final_}်{tone}"));
+            inputs.push(format!("{onset}{sign}{final_}{tone}"));
+          }
+          // stacked and kinzi syllables
+          for bottom in &consonants
+          {
+            for rhyme in bottoms
+            {
+              inputs.push(format!("{onset}{sign}{final_}္{bottom}{rhyme}"));
+              inputs.push(format!("{onset}{sign}{final_}်္{bottom}{rhyme}"));
+            }
+          }
+        }
+      }
+    }
+
+    let mut stacked = 0;
+    for input in &inputs
+    {
+      let Ok((syllable, len)) = super::syllable_from_myanmar(input)
+      else
+      {
+        continue;
+      };
+      if len != input.len()
+      {
+        continue;
+      }
+
+      if let Some(packed) = syllable.to_packed()
+      {
+        assert_eq!(Syllable::from_packed(packed).as_ref(), Some(&syllable));
+      }
+      if let Some(packed) = syllable.to_packed_u64()
+      {
+        assert_eq!(
+          Syllable::from_packed_u64(packed).as_ref(),
+          Some(&syllable),
+          "{}",
+          input
+        );
+        stacked += syllable.stacked.is_some() as usize;
+      }
+    }
+    assert!(stacked > 100);
+  }
+
+  #[test]
+  fn test_homophones()
+  {
+    let options = super::ConvertOptions {
+      homophones: super::Homophones::Mark,
+      ..Default::default()
+    };
+    let convert =
+      |input| super::mlcts_from_myanmar_with_options(input, &options);
+
+    // onsets, finals and stacked consonants
+    assert_eq!(convert("ကဏ္ဍ"), "kaṇḍa.");
+    assert_eq!(convert("ပိဋကတ်"), "pi. ṭa. kat");
+    assert_eq!(convert("ဠ"), "ḷa.");
+    assert_eq!(convert("ဉာဏ်"), "ñaṇ");
+    assert_eq!(convert("ဍ"), "ḍa.");
+    // letters which are not folded are kept
+    assert_eq!(convert("ညာ တန်"), "nya   tan");
+
+    // the policy is applied with stages as well
+    let converter = super::converter::Converter::builder()
+      .homophones(super::Homophones::Mark)
+      .stage(|_: &mut [super::pipeline::StageToken]| ())
+      .build()
+      .unwrap();
+    assert_eq!(converter.convert("ဂုဏ်"), "guṇ");
+  }
+
+  #[test]
+  fn test_escapes()
+  {
+    let cases = [
+      ("{{ကခ}}", "ကခ"),
+      ("က{{ {{ x }}ခ", "ka.  {{ x  hka."),
+      ("{{}}က{{}}", "ka."),
+      // not closed on the same line
+      ("{{က\nခ}}", "{ { ka. \n hka. } }"),
+      // escaped text is not normalized
+      ("{{\u{200B}ကင့်}}", "\u{200B}ကင့်"),
+      // protected blocks are passed through as a whole
+      ("<<ကခ>>", "<<ကခ>>"),
+    ];
+    for (input, expected) in cases
+    {
+      assert_eq!(super::mlcts_from_myanmar(input), expected, "{:?}", input);
+    }
+
+    // only the text outside escapes is corrected
+    let (mlcts, counters) = super::mlcts_from_myanmar_with_counters(
+      "{{ကေျာင်း}} ကေျာင်း",
+      &super::ConvertOptions {
+        autofix: true,
+        ..Default::default()
+      },
+    );
+    assert_eq!(mlcts, "ကေျာင်း   kyaung:");
+    assert_eq!((counters.repaired, counters.fallbacks), (1, 0));
+  }
+
+  #[test]
+  fn test_limits()
+  {
+    use super::{syllable_from_myanmar, ParseError, MAX_STACK_DEPTH};
+
+    // every ကက္ stacks the rest of the syllable under it
+    let stacked = format!("{}က", "ကက္".repeat(MAX_STACK_DEPTH));
+    assert!(syllable_from_myanmar(&stacked).is_ok());
+    let stacked = format!("{}က", "ကက္".repeat(MAX_STACK_DEPTH + 1));
+    assert_eq!(syllable_from_myanmar(&stacked), Err(ParseError::TooDeep));
+
+    let long = format!("{}က", "ကက္".repeat(10_000));
+    assert_eq!(
+      syllable_from_myanmar(&long),
+      Err(ParseError::TooLong { len: long.len() })
+    );
+    // hostile input is parsed as deep as allowed, the rest is passed through
+    let mlcts = super::mlcts_from_myanmar(&long);
+    let deepest = format!("{}ka. ", "kak".repeat(MAX_STACK_DEPTH));
+    assert!(mlcts.starts_with(&deepest), "{}", &mlcts[.. 64]);
+  }
+
+  #[test]
+  fn test_syllable_from_myanmar_never_panics()
+  {
+    use super::{syllable_from_myanmar, ParseError};
+
+    // every Myanmar code point with some foreign characters
+    let alphabet = ('\u{1000}' ..= '\u{104F}')
+      .chain(['a', ' ', '\0', '\u{200C}', '😀'])
+      .collect::<Vec<_>>();
+
+    let mut input = String::new();
+    for &a in &alphabet
+    {
+      for &b in &alphabet
+      {
+        for &c in &alphabet
+        {
+          for len in 1 ..= 3
+          {
+            input.clear();
+            input.extend([a, b, c].into_iter().take(len));
+            match syllable_from_myanmar(&input)
+            {
+              Ok((_, consumed)) =>
+              {
+                assert!(input.is_char_boundary(consumed), "{:?}", input)
+              }
+              Err(ParseError::UnexpectedChar { position, found }) =>
+              {
+                assert_eq!(input[position ..].chars().next(), Some(found))
+              }
+              Err(ParseError::UnexpectedEnd) => (),
+              // three characters are within the limits
+              Err(e) => panic!("{:?}: {}", input, e),
+            }
+          }
+        }
+      }
+    }
+
+    assert_eq!(syllable_from_myanmar(""), Err(ParseError::UnexpectedEnd));
+    assert_eq!(syllable_from_myanmar("ကင်္"), Err(ParseError::UnexpectedEnd));
+    assert_eq!(
+      syllable_from_myanmar("ကက္ကာ😀"),
+      Err(ParseError::UnexpectedChar {
+        position: "ကက္ကာ".len(),
+        found: '😀'
+      })
+    );
+  }
+
+  #[test]
+  fn test_mlcts_from_myanmar_never_panics()
+  {
+    for input in [
+      "ာ",
+      "္",
+      "်",
+      "ွ",
+      "က္",
+      "ကင်္",
+      "ဿ",
+      "ပိဿ",
+      "၎",
+      "၎င",
+      "\u{200C}က\u{200D}ာ",
+      "😀ကို😀",
+      "\0ာ\0",
+    ]
+    {
+      super::mlcts_from_myanmar(input);
+    }
+  }
 }
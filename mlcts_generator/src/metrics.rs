@@ -0,0 +1,198 @@
+//! Metrics of Myanmar verse.
+//!
+//! Syllables are counted as they are read: a syllable with stacked
+//! consonants like မင်္ဂ (mangga.) counts as two syllables.
+
+use mlcts_core::{Syllable, Vowel};
+
+use crate::normalize::normalize_myanmar;
+use crate::{get_token, TokenKind};
+
+/// Represents the position of a syllable in a poem.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyllablePosition
+{
+  /// The zero-based line number.
+  pub line: usize,
+  /// The zero-based position of the syllable in the line.
+  pub position: usize,
+}
+
+/// Represents syllables linked by the same rime.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RhymeChain
+{
+  /// The shared rime.
+  pub rime: Vowel,
+  /// Positions of the rhyming syllables in the order they appear.
+  pub positions: Vec<SyllablePosition>,
+}
+
+/// Split Myanmar text into lines of syllables as they are read.
+/// Tokens which are not syllables (spaces, punctuation, etc.) are skipped.
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text.
+///
+/// # Returns
+///
+/// Syllables of every line.
+pub fn syllable_lines(input: &str) -> Vec<Vec<Syllable>>
+{
+  input
+    .lines()
+    .map(|line| {
+      let line = normalize_myanmar(line);
+      get_token(&line)
+        .filter_map(|token| match token.kind
+        {
+          TokenKind::Syllable(s) => Some(s),
+          _ => None,
+        })
+        .flat_map(unstack)
+        .collect()
+    })
+    .collect()
+}
+
+/// Count the syllables of every line.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::metrics::syllables_per_line;
+///
+/// let input = "မင်္ဂလာပါ\nခင်ဗျာ။";
+/// assert_eq!(syllables_per_line(input), vec![4, 2]);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text.
+///
+/// # Returns
+///
+/// The number of syllables of every line.
+pub fn syllables_per_line(input: &str) -> Vec<usize>
+{
+  syllable_lines(input).iter().map(Vec::len).collect()
+}
+
+/// Find the climbing rhymes of four-syllable verse (လေးလုံးစပ်). The last
+/// syllable of a line rhymes with the third syllable of the next line and
+/// the second syllable of the line after that.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::metrics::climbing_rhymes;
+///
+/// let input = "ရွှေပြည်တော်မှာ\nနေကြသာလို့\nလာပါကြမယ်";
+/// let chains = climbing_rhymes(input);
+///
+/// assert_eq!(chains.len(), 1);
+/// assert_eq!(chains[0].positions.len(), 3);
+/// assert_eq!(chains[0].rime.to_mlcts(), "a");
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar verse, one line per verse line.
+///
+/// # Returns
+///
+/// Rhyme chains linking at least two lines.
+pub fn climbing_rhymes(input: &str) -> Vec<RhymeChain>
+{
+  let lines = syllable_lines(input);
+  let rime_at = |line: usize, position: usize| {
+    lines.get(line)?.get(position).map(Syllable::rime)
+  };
+
+  let mut chains = Vec::new();
+  for line in 0 .. lines.len()
+  {
+    let rime = match rime_at(line, 3)
+    {
+      Some(rime) => rime,
+      None => continue,
+    };
+
+    let positions = (0 .. 3)
+      .map(|step| SyllablePosition {
+        line: line + step,
+        position: 3 - step,
+      })
+      .take_while(|p| rime_at(p.line, p.position) == Some(rime))
+      .collect::<Vec<_>>();
+
+    if positions.len() > 1
+    {
+      chains.push(RhymeChain { rime, positions });
+    }
+  }
+
+  chains
+}
+
+/// Split a syllable with stacked syllables into the syllables as they are
+/// read.
+///
+/// # Arguments
+///
+/// * `syllable` - The syllable to split.
+///
+/// # Returns
+///
+/// The syllable and its stacked syllables without stacking.
+fn unstack(syllable: Syllable) -> Vec<Syllable>
+{
+  let mut syllables = Vec::new();
+  let mut current = Some(syllable);
+  while let Some(syllable) = current
+  {
+    syllables.push(Syllable::unstacked(syllable.consonant, syllable.vowel));
+    current = syllable.stacked.map(|s| *s);
+  }
+  syllables
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::SyllablePosition;
+
+  #[test]
+  fn test_climbing_rhymes()
+  {
+    let input = "တက္ကသိုလ် ကျောင်းသား\n\
+      မင်္ဂလာပါ ခင်ဗျာ";
+    assert_eq!(super::syllables_per_line(input), vec![5, 6]);
+
+    // a rhyme climbs two lines, another one starts on the third line
+    let input = "ရွှေပြည်တော်မှာ\n\
+      နေကြမှာမို့\n\
+      လာလည်ကြမယ်\n\
+      ပန်းတွေဝယ်မယ်";
+    let chains = super::climbing_rhymes(input);
+    assert_eq!(chains.len(), 2);
+    assert_eq!(
+      chains[0].positions,
+      vec![
+        SyllablePosition {
+          line: 0,
+          position: 3
+        },
+        SyllablePosition {
+          line: 1,
+          position: 2
+        },
+      ]
+    );
+    assert_eq!(chains[1].rime.to_mlcts(), "ai");
+    assert_eq!(chains[1].positions.len(), 2);
+
+    assert!(super::climbing_rhymes("ကောင်းကင်ပြာ\nကြယ်တွေ").is_empty());
+  }
+}
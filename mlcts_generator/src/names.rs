@@ -0,0 +1,486 @@
+//! Conventional spellings of Myanmar personal and place names.
+//!
+//! This is not MLCTS. Names are usually spelled the way they sound with
+//! English spelling conventions, e.g. ကျော်ထွန်း is Kyaw Htun instead of
+//! kyau htwan:. Tones are not written and every syllable is capitalized.
+//!
+//! Some syllables have several spellings in use (မြင့် as Myin or Myint).
+//! The most common one is picked from a built-in list, a user dictionary can
+//! override it.
+//!
+//! Honorifics and titles at the start of a name are written as one word by
+//! convention, e.g. ဆရာမ is Sayama instead of Sa Ya Ma.
+
+use mlcts_core::{BasicConsonant, BasicVowel, Consonant, MedialDiacritic, Syllable, Tone, Virama, Vowel};
+
+use crate::dictionary::UserDictionary;
+use crate::normalize::normalize_myanmar;
+use crate::{for_each_token, map_unescaped, split_syllables, ConvertCounters, Token, TokenKind};
+
+/// Syllables whose spelling is settled by usage rather than by the rules.
+const TIE_BREAKS: &[(&str, &str)] = &[
+  ("အေး", "Aye"),
+  ("ဝေ", "Wai"),
+  ("မြင့်", "Myint"),
+  ("သန့်", "Thant"),
+  ("ညွန့်", "Nyunt"),
+  ("ပြည့်", "Pyae"),
+  ("ဖြိုး", "Phyo"),
+  ("ဗိုလ်", "Bo"),
+];
+
+/// Honorifics and titles in front of names and their conventional spellings.
+const HONORIFICS: &[(&str, &str)] = &[
+  ("ဦး", "U"),
+  ("ဒေါ်", "Daw"),
+  ("ကို", "Ko"),
+  ("မ", "Ma"),
+  ("မောင်", "Maung"),
+  ("ဆရာ", "Saya"),
+  ("ဆရာမ", "Sayama"),
+  ("ဆရာတော်", "Sayadaw"),
+  ("ဒေါက်တာ", "Dr."),
+];
+
+/// Spell Myanmar names with the conventional English spelling.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::dictionary::UserDictionary;
+/// use mlcts_generator::names::conventional_from_myanmar;
+///
+/// let mut dictionary = UserDictionary::new();
+/// assert_eq!(
+///   conventional_from_myanmar("အောင်ဆန်းစုကြည်", &dictionary),
+///   "Aung San Su Kyi"
+/// );
+///
+/// dictionary.substitute("ရန်ကုန်", "Yangon");
+/// assert_eq!(
+///   conventional_from_myanmar("ရန်ကုန် နေပြည်တော်", &dictionary),
+///   "Yangon Nay Pyi Taw"
+/// );
+///
+/// // honorifics at the start of a name
+/// assert_eq!(
+///   conventional_from_myanmar("ဆရာမဒေါ်ခင်ကြည် ဒေါက်တာအေးမြ", &dictionary),
+///   "Sayama Daw Khin Kyi Dr. Aye Mya"
+/// );
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar names.
+/// * `dictionary` - Spellings overriding the built-in ones.
+///
+/// # Returns
+///
+/// Space-separated syllables in the conventional spelling.
+pub fn conventional_from_myanmar(
+  input: &str,
+  dictionary: &UserDictionary,
+) -> String
+{
+  let input = map_unescaped(input, normalize_myanmar);
+  conventional_into(&input, dictionary, &mut ConvertCounters::default())
+}
+
+/// Spell normalized Myanmar names with the conventional English spelling and
+/// count the converted tokens.
+///
+/// # Arguments
+///
+/// * `input` - The normalized Myanmar names.
+/// * `dictionary` - Spellings overriding the built-in ones.
+/// * `counters` - The counters the converted tokens are counted in.
+///
+/// # Returns
+///
+/// Space-separated syllables in the conventional spelling.
+pub(crate) fn conventional_into(
+  input: &str,
+  dictionary: &UserDictionary,
+  counters: &mut ConvertCounters,
+) -> String
+{
+  let mut words = Vec::<String>::new();
+  // a name starts at the start of the input and after spaces, punctuation
+  // and honorifics
+  let mut name_start = true;
+  // the end of the honorific being written
+  let mut honorific_end = 0;
+
+  for_each_token(input, dictionary, None, |token, term, _| {
+    let text = &input[token.start .. token.start + token.len];
+    counters.count(token, text, term.is_some());
+    if token.start < honorific_end
+    {
+      return;
+    }
+    let starts_name = std::mem::replace(&mut name_start, false);
+
+    match (&token.kind, term)
+    {
+      (_, Some(term)) => words.push(term.to_string()),
+      (TokenKind::Syllable(_), None) if starts_name =>
+      {
+        match honorific_prefix(&input[token.start ..])
+        {
+          Some((len, spelling)) =>
+          {
+            words.push(spelling.to_string());
+            honorific_end = token.start + len;
+            name_start = true;
+          }
+          None => words.push(spell_name_syllable(token, text)),
+        }
+      }
+      (TokenKind::Syllable(_), None) =>
+      {
+        words.push(spell_name_syllable(token, text))
+      }
+      // punctuation sticks to the previous name
+      (TokenKind::SpecialMapped(s), None) =>
+      {
+        name_start = true;
+        match words.last_mut()
+        {
+          Some(last) => last.push_str(s),
+          None => words.push(s.to_string()),
+        }
+      }
+      (TokenKind::Other, None) if text.trim().is_empty() => name_start = true,
+      (TokenKind::Other, None) => words.push(text.to_string()),
+      (TokenKind::Escaped(s), None) => words.push(s.to_string()),
+    }
+  });
+
+  words.join(" ")
+}
+
+/// Get the conventional spelling of an honorific or a title, e.g. ဦး is U
+/// and ဆရာမ is Sayama.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::names::honorific;
+///
+/// assert_eq!(honorific("ဒေါ်"), Some("Daw"));
+/// assert_eq!(honorific("ဆရာတော်"), Some("Sayadaw"));
+/// assert_eq!(honorific("ကျော်"), None);
+/// ```
+///
+/// # Arguments
+///
+/// * `word` - The Myanmar word.
+///
+/// # Returns
+///
+/// The spelling or `None` if the word is not an honorific.
+pub fn honorific(word: &str) -> Option<&'static str>
+{
+  let word = normalize_myanmar(word.trim());
+  HONORIFICS
+    .iter()
+    .find(|(honorific, _)| normalize_myanmar(honorific) == word)
+    .map(|(_, spelling)| *spelling)
+}
+
+/// Find the longest honorific at the start of a name. The honorific must end
+/// at a syllable boundary, so မောင် doesn't start with မ.
+///
+/// # Arguments
+///
+/// * `name` - The normalized name and the text after it.
+///
+/// # Returns
+///
+/// The length and the spelling of the honorific, or `None` if the name
+/// doesn't start with one.
+fn honorific_prefix(name: &str) -> Option<(usize, &'static str)>
+{
+  let word = name.split(char::is_whitespace).next().unwrap_or_default();
+  let ends = split_syllables(word)
+    .into_iter()
+    .map(|(_, start, len)| start + len)
+    .collect::<Vec<_>>();
+
+  HONORIFICS
+    .iter()
+    .map(|(honorific, spelling)| (normalize_myanmar(honorific), *spelling))
+    .filter(|(honorific, _)| {
+      word.starts_with(honorific.as_ref()) && ends.contains(&honorific.len())
+    })
+    .map(|(honorific, spelling)| (honorific.len(), spelling))
+    .max_by_key(|(len, _)| *len)
+}
+
+/// Spell a syllable of a name.
+///
+/// # Arguments
+///
+/// * `token` - The syllable token.
+/// * `text` - The normalized text of the syllable.
+///
+/// # Returns
+///
+/// The spelling of the syllable, capitalized.
+fn spell_name_syllable(token: &Token<'_>, text: &str) -> String
+{
+  match &token.kind
+  {
+    TokenKind::Syllable(syllable) => TIE_BREAKS
+      .iter()
+      .find(|(syllable, _)| normalize_myanmar(syllable) == text)
+      .map(|(_, spelling)| spelling.to_string())
+      .unwrap_or_else(|| capitalize(&spell_syllable(syllable))),
+    _ => text.to_string(),
+  }
+}
+
+/// Spell a syllable and its stacked syllables without capitalization.
+///
+/// # Arguments
+///
+/// * `syllable` - The syllable to spell.
+///
+/// # Returns
+///
+/// The conventional spelling of the syllable.
+fn spell_syllable(syllable: &Syllable) -> String
+{
+  let mut spelling = String::new();
+  let mut current = Some(syllable);
+  while let Some(syllable) = current
+  {
+    let rime = rime(&syllable.consonant, &syllable.vowel);
+    let mut initial = initial(&syllable.consonant);
+    // ွန် and ွတ် swallow the w of the medial, e.g. ထွန်း is Htun
+    if has_w_medial(&syllable.consonant) && rime.starts_with('u')
+    {
+      initial.pop();
+    }
+    spelling.push_str(&initial);
+    spelling.push_str(&rime);
+    current = syllable.stacked.as_deref();
+  }
+  spelling
+}
+
+/// Spell the initial consonant with its medials.
+///
+/// # Arguments
+///
+/// * `consonant` - The consonant to spell.
+///
+/// # Returns
+///
+/// The spelling of the initial.
+fn initial(consonant: &Consonant) -> String
+{
+  use BasicConsonant as C;
+  use MedialDiacritic as M;
+
+  let basic = match consonant.basic
+  {
+    C::K => "k",
+    C::Hk => "kh",
+    C::G | C::Gh => "g",
+    C::Ng => "ng",
+    C::C | C::Hc => "s",
+    C::J | C::Jh => "z",
+    C::Ny => "ny",
+    C::T => "t",
+    C::Ht => "ht",
+    C::D | C::Dh => "d",
+    C::N => "n",
+    C::P => "p",
+    C::Hp => "ph",
+    C::B | C::Bh => "b",
+    C::M => "m",
+    C::Y | C::R => "y",
+    C::L => "l",
+    C::W => "w",
+    C::S => "th",
+    C::H => "h",
+    C::A => "",
+  };
+
+  let glided = || match consonant.basic
+  {
+    C::K => "ky".to_string(),
+    C::Hk => "ch".to_string(),
+    C::G | C::Gh => "gy".to_string(),
+    C::Ng => "ny".to_string(),
+    C::Y | C::R => "y".to_string(),
+    _ => format!("{}y", basic),
+  };
+  let aspirated = || match consonant.basic
+  {
+    C::Y | C::R => "sh".to_string(),
+    _ => format!("h{}", basic),
+  };
+  let aspirated_glided = || match consonant.basic
+  {
+    C::Y | C::R | C::L | C::S => "sh".to_string(),
+    _ => format!("h{}", glided()),
+  };
+
+  match consonant.medial
+  {
+    None => basic.to_string(),
+    Some(M::Y | M::R) => glided(),
+    Some(M::H) => aspirated(),
+    Some(M::Hy | M::Hr) => aspirated_glided(),
+    Some(M::W) => format!("{}w", basic),
+    Some(M::Yw | M::Rw) => glided() + "w",
+    Some(M::Hw) => aspirated() + "w",
+    Some(M::Hyw | M::Hrw) => aspirated_glided() + "w",
+  }
+}
+
+/// Spell the rime of a syllable. Tones are not written except that ိုး is
+/// spelled oe.
+///
+/// # Arguments
+///
+/// * `consonant` - The consonant of the syllable, its medials change some
+///   rimes.
+/// * `vowel` - The vowel to spell.
+///
+/// # Returns
+///
+/// The spelling of the rime.
+fn rime(consonant: &Consonant, vowel: &Vowel) -> String
+{
+  use BasicVowel as V;
+  use Virama as F;
+
+  let has_w = has_w_medial(consonant);
+  let spelling = match (vowel.basic, vowel.virama)
+  {
+    (V::A, Some(F::N | F::M)) if has_w => "un",
+    (V::A, Some(F::T | F::P)) if has_w => "ut",
+    (V::A, None) => "a",
+    (V::I, None) => "i",
+    (V::U, None) => "u",
+    (V::E, None) if has_w => "e",
+    (V::E | V::Ei, None) => "ay",
+    (V::Ai, None) => "e",
+    (V::Au, None) => "aw",
+    (V::Ui, None) if vowel.tone == Some(Tone::High) => "oe",
+    (V::Ui, None) => "o",
+    (V::A, Some(F::K)) => "et",
+    (V::A, Some(F::Ng)) => "in",
+    (V::A, Some(F::C)) => "it",
+    (V::A, Some(F::Ny)) => "i",
+    (V::A, Some(F::T | F::P)) => "at",
+    (V::A, Some(F::N | F::M)) => "an",
+    (V::I, Some(F::T | F::P)) => "eik",
+    (V::I, Some(F::N | F::M)) => "ein",
+    (V::U, Some(F::T | F::P)) => "oke",
+    (V::U, Some(F::N | F::M)) => "on",
+    (V::Au, Some(F::K)) => "auk",
+    (V::Au, Some(F::Ng)) => "aung",
+    (V::Ui, Some(F::K)) => "aik",
+    (V::Ui, Some(F::Ng)) => "aing",
+    (V::Ui, Some(F::L)) => "o",
+    // rare rimes are spelled as in MLCTS
+    (basic, virama) => return Vowel::new(basic, virama, None).to_mlcts(),
+  };
+  spelling.to_string()
+}
+
+/// Check if the consonant has the medial ွ.
+///
+/// # Arguments
+///
+/// * `consonant` - The consonant to check.
+///
+/// # Returns
+///
+/// `true` if the consonant has the medial ွ, `false` otherwise.
+fn has_w_medial(consonant: &Consonant) -> bool
+{
+  use MedialDiacritic as M;
+
+  matches!(
+    consonant.medial,
+    Some(M::W | M::Yw | M::Rw | M::Hw | M::Hyw | M::Hrw)
+  )
+}
+
+/// Capitalize the first letter.
+///
+/// # Arguments
+///
+/// * `s` - The string to capitalize.
+///
+/// # Returns
+///
+/// The capitalized string.
+fn capitalize(s: &str) -> String
+{
+  let mut chars = s.chars();
+  match chars.next()
+  {
+    Some(first) => first.to_uppercase().chain(chars).collect(),
+    None => String::new(),
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use crate::dictionary::UserDictionary;
+
+  #[test]
+  fn test_conventional_from_myanmar()
+  {
+    let dictionary = UserDictionary::new();
+    let cases = [
+      ("ကျော်ထွန်း", "Kyaw Htun"),
+      ("မိုးမြင့်", "Moe Myint"),
+      ("ဦးသန့်", "U Thant"),
+      ("ဒေါ်ခင်ကြည်", "Daw Khin Kyi"),
+      ("မောင်ဖြိုးဝေ", "Maung Phyo Wai"),
+      ("ရွှေဆွေ", "Shwe Swe"),
+      ("လှလှဝင်း", "Hla Hla Win"),
+      ("နှင်းယုချစ်", "Hnin Yu Chit"),
+      ("သိန်းဟိန်းထက်", "Thein Hein Htet"),
+      ("ကိုနိုင်လွင်", "Ko Naing Lwin"),
+      ("အေးမြ", "Aye Mya"),
+      ("ဗိုလ်ချုပ်", "Bo Choke"),
+      ("ပုဂံ", "Pu Gan"),
+      ("စန္ဒာ", "Sanda"),
+      ("ညွန့်ဖြူ။", "Nyunt Phyu."),
+      ("ဆရာတော်ဦးဝေ", "Sayadaw U Wai"),
+      ("ဆရာမောင်မောင်", "Saya Maung Maung"),
+      ("ကိုကို ဆရာ", "Ko Ko Saya"),
+      ("ဒေါက်တာမြင့်ဆွေ။ ဆရာမ", "Dr. Myint Swe. Sayama"),
+      // only at the start of a name
+      ("မြင့်ဆရာ", "Myint Sa Ya"),
+    ];
+    for (input, expected) in cases
+    {
+      assert_eq!(
+        super::conventional_from_myanmar(input, &dictionary),
+        expected,
+        "{}",
+        input
+      );
+    }
+
+    let mut dictionary = UserDictionary::new();
+    dictionary
+      .substitute("မြင့်", "Myin")
+      .substitute("မန္တလေး", "Mandalay")
+      .substitute("ဒေါက်တာ", "Dr");
+    assert_eq!(
+      super::conventional_from_myanmar("မြင့်မြင့် မန္တလေး ဒေါက်တာမြင့်", &dictionary),
+      "Myin Myin Mandalay Dr Myin"
+    );
+  }
+}
@@ -0,0 +1,328 @@
+//! Normalization of Myanmar text before it is split into syllables.
+//!
+//! The same visual text can be encoded with different code point sequences
+//! depending on the keyboard or the font used to type it. The parser only
+//! handles one of them, so every variant is rewritten into that form first.
+
+use std::borrow::Cow;
+
+use crate::is_myanmar;
+
+/// ် (U+103A)
+const ASAT: char = '\u{103A}';
+/// ့ (U+1037)
+const DOT_BELOW: char = '\u{1037}';
+
+/// Zero width space (U+200B)
+const ZWSP: char = '\u{200B}';
+/// Zero width non-joiner (U+200C)
+const ZWNJ: char = '\u{200C}';
+/// Zero width joiner (U+200D)
+const ZWJ: char = '\u{200D}';
+/// Byte order mark (U+FEFF)
+const BOM: char = '\u{FEFF}';
+
+/// How zero-width characters (ZWSP, ZWNJ and ZWJ) are handled during
+/// normalization.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum ZeroWidth
+{
+  /// Keep them as they are. Each of them becomes a separate token and might
+  /// break a syllable into pieces.
+  Keep,
+  /// Remove them. ZWNJ and ZWJ are only removed next to Myanmar characters,
+  /// as they are part of emoji sequences and of other scripts.
+  Strip,
+  /// Remove ZWNJ and ZWJ next to Myanmar characters, and treat ZWSP as a word
+  /// boundary by replacing it with a space (unless it is already next to a
+  /// whitespace).
+  #[default]
+  WordBoundary,
+}
+
+/// How line breaks are written to the output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum Newline
+{
+  /// Keep every line break as it is, so CRLF and LF lines stay as they are.
+  #[default]
+  Keep,
+  /// Write every line break as LF.
+  Lf,
+  /// Write every line break as CRLF.
+  CrLf,
+}
+
+impl Newline
+{
+  /// Rewrite a line break.
+  ///
+  /// # Arguments
+  ///
+  /// * `line_break` - The line break of the input (`\n`, `\r\n` or `\r`).
+  ///
+  /// # Returns
+  ///
+  /// The line break to write.
+  pub fn apply(self, line_break: &str) -> &str
+  {
+    match self
+    {
+      Self::Keep => line_break,
+      Self::Lf => "\n",
+      Self::CrLf => "\r\n",
+    }
+  }
+}
+
+/// Remove the UTF-8 byte order mark at the start of a file, if any.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::normalize::strip_bom;
+///
+/// assert_eq!(strip_bom("\u{FEFF}မင်္ဂလာပါ"), "မင်္ဂလာပါ");
+/// assert_eq!(strip_bom("မင်္ဂလာပါ"), "မင်္ဂလာပါ");
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The content of the file.
+///
+/// # Returns
+///
+/// The content without the byte order mark.
+pub fn strip_bom(input: &str) -> &str
+{
+  input.strip_prefix(BOM).unwrap_or(input)
+}
+
+/// Decomposed sequences produced by various keyboards and the single code
+/// points they represent.
+const COMPOSITIONS: &[(&str, &str)] = &[
+  // ဥ + ီ
+  ("\u{1025}\u{102E}", "\u{1026}"),
+  // ဩ + ာ + ်
+  ("\u{1029}\u{102C}\u{103A}", "\u{102A}"),
+  // ဩ + ်
+  ("\u{1029}\u{103A}", "\u{102A}"),
+  // ၄ (digit four) + င် + း
+  (
+    "\u{1044}\u{1004}\u{103A}\u{1038}",
+    "\u{104E}\u{1004}\u{103A}\u{1038}",
+  ),
+];
+
+/// Normalize Myanmar text into the form expected by the syllable parser.
+/// Zero-width characters are handled with [`ZeroWidth::WordBoundary`].
+///
+/// # Notes
+/// * Dot below (့) and asat (်) can be typed in both orders. Canonical Unicode
+///   order puts the dot below first (e.g. င + ့ + ်) but the parser expects the
+///   asat first, so they are swapped.
+/// * Decomposed sequences of ဦ (ဥ + ီ), ဪ (ဩ + ်, ဩ + ာ + ်) and ၎င်း typed with
+///   the digit four (၄င်း) are replaced with their single code point forms. This
+///   might change the byte length of the text.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::normalize::normalize_myanmar;
+///
+/// let input = "မြင\u{1037}\u{103A}";
+/// assert_eq!(normalize_myanmar(input), "မြင\u{103A}\u{1037}");
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to normalize.
+///
+/// # Returns
+///
+/// The normalized text. The input is borrowed if nothing needs to change.
+pub fn normalize_myanmar(input: &str) -> Cow<'_, str>
+{
+  normalize_myanmar_with(input, ZeroWidth::default())
+}
+
+/// Normalize Myanmar text like [`normalize_myanmar`] with the given handling
+/// of zero-width characters.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::normalize::{normalize_myanmar_with, ZeroWidth};
+///
+/// let input = "မင်္ဂလာပါ\u{200B}ခင်ဗျာ";
+/// assert_eq!(normalize_myanmar_with(input, ZeroWidth::Keep), input);
+/// assert_eq!(
+///   normalize_myanmar_with(input, ZeroWidth::Strip),
+///   "မင်္ဂလာပါခင်ဗျာ"
+/// );
+/// assert_eq!(
+///   normalize_myanmar_with(input, ZeroWidth::WordBoundary),
+///   "မင်္ဂလာပါ ခင်ဗျာ"
+/// );
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to normalize.
+/// * `zero_width` - How zero-width characters are handled.
+///
+/// # Returns
+///
+/// The normalized text. The input is borrowed if nothing needs to change.
+pub fn normalize_myanmar_with(
+  input: &str,
+  zero_width: ZeroWidth,
+) -> Cow<'_, str>
+{
+  let output = handle_zero_width(input, zero_width);
+  let output = match output
+  {
+    Cow::Borrowed(input) => reorder_dot_below_and_asat(input),
+    Cow::Owned(o) => Cow::Owned(reorder_dot_below_and_asat(&o).into_owned()),
+  };
+  match output
+  {
+    Cow::Borrowed(input) => compose(input),
+    Cow::Owned(o) => Cow::Owned(compose(&o).into_owned()),
+  }
+}
+
+/// Strip zero-width characters or replace ZWSP with a space.
+///
+/// # Arguments
+///
+/// * `input` - The input text.
+/// * `zero_width` - How zero-width characters are handled.
+///
+/// # Returns
+///
+/// The text without zero-width characters, unless they are kept.
+fn handle_zero_width(input: &str, zero_width: ZeroWidth) -> Cow<'_, str>
+{
+  if zero_width == ZeroWidth::Keep || !input.contains([ZWSP, ZWNJ, ZWJ])
+  {
+    return Cow::Borrowed(input);
+  }
+
+  let mut output = String::with_capacity(input.len());
+  let mut chars = input.chars().peekable();
+  let mut prev = None;
+  while let Some(c) = chars.next()
+  {
+    match c
+    {
+      ZWNJ | ZWJ
+        if prev.is_some_and(is_myanmar)
+          || chars.peek().is_some_and(|&c| is_myanmar(c)) =>
+      {}
+      ZWSP if zero_width == ZeroWidth::Strip => (),
+      ZWSP =>
+      {
+        let after_space =
+          output.chars().last().is_none_or(|c| c.is_whitespace());
+        let before_space = chars.peek().is_none_or(|c| c.is_whitespace());
+        if !after_space && !before_space
+        {
+          output.push(' ');
+        }
+      }
+      _ => output.push(c),
+    }
+    prev = Some(c);
+  }
+  Cow::Owned(output)
+}
+
+/// Move asat (်) in front of dot below (့) when they appear in canonical
+/// Unicode order.
+///
+/// # Arguments
+///
+/// * `input` - The input text.
+///
+/// # Returns
+///
+/// The reordered text.
+fn reorder_dot_below_and_asat(input: &str) -> Cow<'_, str>
+{
+  let canonical = format!("{}{}", DOT_BELOW, ASAT);
+  if !input.contains(&canonical)
+  {
+    return Cow::Borrowed(input);
+  }
+
+  Cow::Owned(input.replace(&canonical, &format!("{}{}", ASAT, DOT_BELOW)))
+}
+
+/// Replace decomposed sequences with their single code point forms.
+///
+/// # Arguments
+///
+/// * `input` - The input text.
+///
+/// # Returns
+///
+/// The composed text.
+fn compose(input: &str) -> Cow<'_, str>
+{
+  let mut output = Cow::Borrowed(input);
+  for (decomposed, composed) in COMPOSITIONS
+  {
+    if output.contains(decomposed)
+    {
+      output = Cow::Owned(output.replace(decomposed, composed));
+    }
+  }
+  output
+}
+
+#[cfg(test)]
+mod tests
+{
+  use std::borrow::Cow;
+
+  #[test]
+  fn test_normalize_myanmar()
+  {
+    let input = "မင်္ဂလာပါ";
+    assert!(matches!(super::normalize_myanmar(input), Cow::Borrowed(_)));
+
+    let input = "မြင\u{1037}\u{103A}မား နဲ့ ကောင\u{1037}\u{103A}";
+    let normalized = super::normalize_myanmar(input);
+    assert_eq!(normalized, "မြင\u{103A}\u{1037}မား နဲ့ ကောင\u{103A}\u{1037}");
+    assert_eq!(normalized.len(), input.len());
+
+    let input = "\u{1025}\u{102E}း \u{1029}\u{103A} \u{1029}\u{102C}\u{103A} \
+      \u{1044}\u{1004}\u{103A}\u{1038} ၄";
+    assert_eq!(super::normalize_myanmar(input), "ဦး ဪ ဪ ၎င်း ၄");
+  }
+
+  #[test]
+  fn test_normalize_zero_width()
+  {
+    use super::ZeroWidth;
+
+    let input = "\u{200B}မြန်\u{200D}မာ\u{200B}\u{200B}စာ \u{200B}ပေ\u{200C}";
+    assert_eq!(super::normalize_myanmar(input), "မြန်မာ စာ ပေ");
+    assert_eq!(
+      super::normalize_myanmar_with(input, ZeroWidth::Strip),
+      "မြန်မာစာ ပေ"
+    );
+    assert_eq!(super::normalize_myanmar_with(input, ZeroWidth::Keep), input);
+
+    // joiners outside Myanmar text are kept
+    let input = "👨\u{200D}👩\u{200D}👧 a\u{200D}b می\u{200C}خواهم";
+    assert_eq!(super::normalize_myanmar(input), input);
+    assert_eq!(
+      super::normalize_myanmar_with(input, ZeroWidth::Strip),
+      input
+    );
+  }
+}
@@ -0,0 +1,352 @@
+//! Post-correction helpers for OCR output of Myanmar text.
+//!
+//! An [`OcrChecker`] knows the syllables of a word list. It scores lines by
+//! how many of their syllables are structurally valid (see
+//! [`crate::validate`]) and known, and proposes corrections for the other
+//! syllables from look-alike characters and from the closest known syllables
+//! (see [`crate::fuzzy`]).
+
+use std::collections::BTreeSet;
+
+use crate::autofix::autofix_myanmar;
+use crate::fuzzy::FuzzyIndex;
+use crate::normalize::normalize_myanmar;
+use crate::validate::validate_myanmar;
+use crate::{split_syllables, syllable_from_myanmar};
+
+/// Characters which OCR engines often read as each other.
+const LOOK_ALIKES: &[(char, char)] = &[
+  ('ဝ', '၀'),
+  ('ဝ', 'ဂ'),
+  ('ရ', '၇'),
+  ('ဉ', 'ဥ'),
+  ('ပ', 'ဟ'),
+  ('ယ', 'ဃ'),
+  ('ဒ', 'ဓ'),
+  ('ိ', 'ီ'),
+  ('ု', 'ူ'),
+  ('ံ', 'ိ'),
+];
+
+/// Represents the score of a line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineScore
+{
+  /// Number of Myanmar syllables in the line.
+  pub syllables: usize,
+  /// Number of structurally valid syllables.
+  pub valid: usize,
+  /// Number of syllables in the word list.
+  pub known: usize,
+}
+
+impl LineScore
+{
+  /// Get the fraction of structurally valid syllables.
+  ///
+  /// # Returns
+  ///
+  /// The fraction or `0.0` if there are no syllables.
+  pub fn validity(&self) -> f64
+  {
+    self.fraction(self.valid)
+  }
+
+  /// Get the fraction of syllables in the word list.
+  ///
+  /// # Returns
+  ///
+  /// The fraction or `0.0` if there are no syllables.
+  pub fn hit_rate(&self) -> f64
+  {
+    self.fraction(self.known)
+  }
+
+  /// Get the overall score of the line, the mean of the validity and the hit
+  /// rate.
+  ///
+  /// # Returns
+  ///
+  /// The score between `0.0` and `1.0`, or `0.0` if there are no syllables.
+  pub fn score(&self) -> f64
+  {
+    (self.validity() + self.hit_rate()) / 2.0
+  }
+
+  /// Get the fraction of the syllables of the line.
+  ///
+  /// # Arguments
+  ///
+  /// * `count` - The number of syllables.
+  ///
+  /// # Returns
+  ///
+  /// The fraction or `0.0` if there are no syllables.
+  fn fraction(&self, count: usize) -> f64
+  {
+    match self.syllables
+    {
+      0 => 0.0,
+      syllables => count as f64 / syllables as f64,
+    }
+  }
+}
+
+/// Represents a syllable which is invalid or unknown, with the proposed
+/// replacements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Correction
+{
+  /// The start position of the syllable in the line.
+  pub start: usize,
+  /// The length of the syllable in the line.
+  pub len: usize,
+  /// If the syllable is structurally valid.
+  pub valid: bool,
+  /// Known syllables to replace the syllable with, the most likely first.
+  /// It is empty if nothing close is known.
+  pub candidates: Vec<String>,
+}
+
+/// Scores OCR output and proposes corrections from the syllables of a word
+/// list.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::ocr::OcrChecker;
+///
+/// let checker = OcrChecker::new(["ကျောင်းသား", "ပုဝါ", "စာအုပ်"]);
+///
+/// let score = checker.score("ကျောင်းသားစာအုပ်");
+/// assert_eq!(score.syllables, 4);
+/// assert_eq!(score.score(), 1.0);
+///
+/// // ၀ (zero) read instead of ဝ (wa)
+/// let corrections = checker.corrections("ပု၀ါ");
+/// assert_eq!(corrections.len(), 1);
+/// assert_eq!(corrections[0].start, "ပု".len());
+/// assert_eq!(corrections[0].candidates, ["ဝါ"]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OcrChecker
+{
+  /// Normalized syllables of the word list.
+  syllables: BTreeSet<String>,
+  /// The syllables with their MLCTS forms to find the closest ones.
+  index: FuzzyIndex,
+}
+
+impl OcrChecker
+{
+  /// Create a checker from a word list.
+  ///
+  /// # Arguments
+  ///
+  /// * `words` - The words in Myanmar script.
+  ///
+  /// # Returns
+  ///
+  /// A new checker.
+  pub fn new<'w>(words: impl IntoIterator<Item = &'w str>) -> Self
+  {
+    let mut checker = Self::default();
+    for word in words
+    {
+      let word = normalize_myanmar(word.trim());
+      checker.syllables.extend(
+        split_syllables(&word)
+          .into_iter()
+          .filter(|(text, ..)| is_myanmar_syllable(text))
+          .map(|(text, ..)| text.to_string()),
+      );
+    }
+    checker.index = FuzzyIndex::new(&checker.syllables);
+    checker
+  }
+
+  /// Score a line of OCR output.
+  ///
+  /// # Arguments
+  ///
+  /// * `line` - The line, expected to be normalized with [`normalize_myanmar`].
+  ///
+  /// # Returns
+  ///
+  /// The score of the line. Text other than Myanmar syllables is not
+  /// counted.
+  pub fn score(&self, line: &str) -> LineScore
+  {
+    let mut score = LineScore::default();
+    for (text, _, valid) in checked_syllables(line)
+    {
+      score.syllables += 1;
+      score.valid += usize::from(valid);
+      score.known += usize::from(self.syllables.contains(text));
+    }
+    score
+  }
+
+  /// Find the syllables of a line of OCR output which are invalid or not in
+  /// the word list, and propose replacements for them:
+  ///
+  /// * the syllable with the safe corrections of
+  ///   [`crate::autofix::autofix_myanmar`]
+  /// * the syllable with a look-alike character replaced, e.g. ၀ (zero) with ဝ
+  ///   (wa) or ိ with ီ
+  /// * the known syllables within one edit, see [`crate::fuzzy::fuzzy_match`]
+  ///
+  /// # Arguments
+  ///
+  /// * `line` - The line, expected to be normalized with [`normalize_myanmar`].
+  ///
+  /// # Returns
+  ///
+  /// The corrections in the order they appear in the line.
+  pub fn corrections(&self, line: &str) -> Vec<Correction>
+  {
+    let mut corrections = Vec::new();
+    for (text, start, valid) in checked_syllables(line)
+    {
+      if valid && self.syllables.contains(text)
+      {
+        continue;
+      }
+
+      let mut candidates = Vec::new();
+      let mut propose = |candidate: String| {
+        if candidate != text
+          && self.syllables.contains(&candidate)
+          && !candidates.contains(&candidate)
+        {
+          candidates.push(candidate);
+        }
+      };
+      propose(autofix_myanmar(text).0);
+      for (i, c) in text.char_indices()
+      {
+        for replacement in look_alikes(c)
+        {
+          let mut candidate = text.to_string();
+          candidate.replace_range(
+            i .. i + c.len_utf8(),
+            replacement.encode_utf8(&mut [0; 4]),
+          );
+          propose(candidate);
+        }
+      }
+
+      for m in self.index.fuzzy_match(text, 1)
+      {
+        propose(m.candidate.to_string());
+      }
+
+      corrections.push(Correction {
+        start,
+        len: text.len(),
+        valid,
+        candidates,
+      });
+    }
+    corrections
+  }
+}
+
+/// Split a line into its Myanmar syllables and check their structure.
+///
+/// # Arguments
+///
+/// * `line` - The line to split.
+///
+/// # Returns
+///
+/// The Myanmar syllables of the line with their start positions and if
+/// they are structurally valid.
+fn checked_syllables(line: &str) -> impl Iterator<Item = (&str, usize, bool)>
+{
+  let violations = validate_myanmar(line);
+  split_syllables(line)
+    .into_iter()
+    .filter(|(text, ..)| is_myanmar_syllable(text))
+    .map(move |(text, start, len)| {
+      let violated = violations
+        .iter()
+        .any(|v| v.start >= start && v.start < start + len);
+      let parsed =
+        matches!(syllable_from_myanmar(text), Ok((_, l)) if l == len);
+      (text, start, !violated && parsed)
+    })
+}
+
+/// Get the look-alikes of a character.
+///
+/// # Arguments
+///
+/// * `c` - The character.
+///
+/// # Returns
+///
+/// The characters which are often read instead of the character.
+fn look_alikes(c: char) -> impl Iterator<Item = char>
+{
+  LOOK_ALIKES.iter().filter_map(move |&(a, b)| match c
+  {
+    _ if c == a => Some(b),
+    _ if c == b => Some(a),
+    _ => None,
+  })
+}
+
+/// Check if a syllable is a Myanmar syllable, i.e. not a digit, punctuation
+/// or foreign text.
+///
+/// # Arguments
+///
+/// * `text` - The syllable to check.
+///
+/// # Returns
+///
+/// `true` if the syllable has a Myanmar letter or sign, `false`
+/// otherwise.
+fn is_myanmar_syllable(text: &str) -> bool
+{
+  text
+    .chars()
+    .any(|c| matches!(c, '\u{1000}' ..= '\u{103F}' | '\u{1050}' ..= '\u{109F}'))
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::OcrChecker;
+
+  #[test]
+  fn test_ocr_checker()
+  {
+    let checker = OcrChecker::new(["ကျောင်းသား", "ဂီတ", "ဝါ", "ကူ"]);
+
+    let score = checker.score("ကေျာင်းသား ABC ၁၂၃။");
+    assert_eq!(score.syllables, 2);
+    assert_eq!(score.valid, 1);
+    assert_eq!(score.known, 1);
+    assert_eq!(score.score(), 0.5);
+    assert_eq!(checker.score("ABC").score(), 0.0);
+
+    let corrections = checker.corrections("ကေျာင်းသား ဝီတ ကျောင်");
+    let candidates = corrections
+      .iter()
+      .map(|c| (c.start, c.valid, c.candidates.clone()))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      candidates,
+      [
+        // autofix
+        (0, false, vec!["ကျောင်း".to_string()]),
+        // look-alike first, then within one edit
+        (31, true, vec!["ဂီ".to_string(), "ဝါ".to_string()]),
+        // within one edit
+        (41, true, vec!["ကျောင်း".to_string()]),
+      ]
+    );
+  }
+}
@@ -0,0 +1,166 @@
+//! Phonetic keys of Myanmar names for matching names spelled differently.
+//!
+//! Myanmar names are spelled with [`crate::names`] first, romanized names
+//! are used as they are. The spelling is then folded: tones, spaces and
+//! aspiration are dropped, voiced and voiceless consonants are merged and
+//! the vowel spellings of MLCTS and the conventional spellings are merged
+//! where they agree (au and aw, ui and oe, etc.).
+
+use crate::dictionary::UserDictionary;
+use crate::names::conventional_from_myanmar;
+
+/// Spellings and their keys, the longest spelling at a position wins.
+/// Letters not listed are kept.
+const FOLDINGS: &[(&str, &str)] = &[
+  // three letters
+  ("hky", "c"),
+  ("hng", "n"),
+  ("hny", "y"),
+  ("wan", "un"),
+  ("wam", "un"),
+  ("wat", "ut"),
+  ("wap", "ut"),
+  // two letters
+  ("ky", "c"),
+  ("gy", "c"),
+  ("ch", "c"),
+  ("sh", "x"),
+  ("hr", "x"),
+  ("hy", "x"),
+  ("hk", "k"),
+  ("kh", "k"),
+  ("gh", "k"),
+  ("hc", "s"),
+  ("hs", "s"),
+  ("th", "s"),
+  ("jh", "s"),
+  ("ht", "t"),
+  ("dh", "t"),
+  ("hp", "p"),
+  ("ph", "p"),
+  ("bh", "p"),
+  ("hm", "m"),
+  ("hn", "n"),
+  ("hl", "l"),
+  ("hw", "w"),
+  ("ng", "n"),
+  ("ny", "y"),
+  ("aw", "o"),
+  ("au", "o"),
+  ("oo", "u"),
+  ("oe", "v"),
+  ("ui", "v"),
+  ("ay", "e"),
+  ("ai", "e"),
+  ("ae", "e"),
+  ("ei", "e"),
+  ("ee", "i"),
+  // one letter
+  ("g", "k"),
+  ("c", "s"),
+  ("z", "s"),
+  ("j", "s"),
+  ("d", "t"),
+  ("b", "p"),
+  ("r", "y"),
+  ("o", "v"),
+  ("h", ""),
+];
+
+/// Get the phonetic key of a Myanmar or romanized name. Names which sound
+/// alike get the same key.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::phonetic::phonetic_key;
+///
+/// let key = phonetic_key("ကျော်");
+/// assert_eq!(phonetic_key("Kyaw"), key);
+/// assert_eq!(phonetic_key("kyau"), key);
+/// assert_ne!(phonetic_key("Kyi"), key);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The name in Myanmar script or romanized.
+///
+/// # Returns
+///
+/// The key of the name. The key is only meant to be compared with other
+/// keys.
+pub fn phonetic_key(input: &str) -> String
+{
+  let spelling = if input.contains(|c| matches!(c, '\u{1000}' ..= '\u{109F}'))
+  {
+    conventional_from_myanmar(input, &UserDictionary::new())
+  }
+  else
+  {
+    input.to_string()
+  };
+
+  let letters = spelling
+    .chars()
+    .filter(char::is_ascii_alphabetic)
+    .map(|c| c.to_ascii_lowercase())
+    .collect::<String>();
+
+  let mut key = String::new();
+  let mut rest = letters.as_str();
+  while !rest.is_empty()
+  {
+    let (len, folded) = (1 ..= 3)
+      .rev()
+      .filter_map(|len| rest.get(.. len))
+      .find_map(|prefix| {
+        FOLDINGS
+          .iter()
+          .find(|(spelling, _)| *spelling == prefix)
+          .map(|(_, folded)| (prefix.len(), *folded))
+      })
+      .unwrap_or((1, &rest[.. 1]));
+
+    // doubled letters are folded too, e.g. Thett and Thet
+    for c in folded.chars()
+    {
+      if !key.ends_with(c)
+      {
+        key.push(c);
+      }
+    }
+    rest = &rest[len ..];
+  }
+
+  key
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::phonetic_key;
+
+  #[test]
+  fn test_phonetic_key()
+  {
+    let groups: [&[&str]; 5] = [
+      &["ထွန်း", "Htun", "Tun", "htwan:"],
+      &["အောင်ဆန်း", "Aung San", "Aungsan", "aung hcan:"],
+      &["မိုး", "Moe", "Mo", "mui:"],
+      &["မြ", "Mya", "mra.", "Mya."],
+      &["သန့်", "Thant", "Thantt", "sant."],
+    ];
+    for names in groups
+    {
+      let key = phonetic_key(names[0]);
+      for name in names
+      {
+        assert_eq!(phonetic_key(name), key, "{}", name);
+      }
+    }
+
+    assert_ne!(phonetic_key("Moe"), phonetic_key("Mu"));
+    assert_ne!(phonetic_key("Hla"), phonetic_key("Hla Hla"));
+    assert_eq!(phonetic_key(""), "");
+  }
+}
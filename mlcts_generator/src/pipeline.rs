@@ -0,0 +1,305 @@
+//! Conversion with custom stages between parsing and rendering.
+//!
+//! The conversion runs in this order:
+//!
+//! 1. autofix and normalize the input (see [`crate::ConvertOptions`])
+//! 2. split the input into syllables and parse them into tokens
+//! 3. segment the runs of syllables into words with the
+//!    [`Segmenter`](crate::segment::Segmenter) of the pipeline, every syllable
+//!    is a word without one
+//! 4. replace terms of the user dictionary
+//! 5. run the custom stages in the order they are added
+//! 6. join the output of the tokens
+//!
+//! A stage sees the tokens with their MLCTS output and the word they start,
+//! and can change the output, e.g. to mask words or to pass named entities
+//! through.
+
+use crate::join::TokenJoiner;
+use crate::segment::Segmenter;
+use crate::{convert_into, ConvertCounters, ConvertOptions, Token};
+
+/// Represents a token passed to the stages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StageToken<'i>
+{
+  /// The token. A term of the user dictionary is a single token of the kind
+  /// [`crate::TokenKind::Other`].
+  pub token: Token<'i>,
+  /// The normalized text of the token.
+  pub text: &'i str,
+  /// The output of the token, MLCTS or the replacement of the term. A token
+  /// with an empty output is left out of the text.
+  pub output: String,
+  /// Whether the token starts a word. Tokens which are not syllables and
+  /// terms of the user dictionary are always words of their own.
+  pub word_start: bool,
+}
+
+/// A custom conversion stage. Stages are shared with the pipeline, so they
+/// must be usable from several threads at once.
+pub trait Stage: Send + Sync
+{
+  /// Process the tokens of the input.
+  ///
+  /// # Arguments
+  ///
+  /// * `tokens` - The tokens of the input in order.
+  fn process(&self, tokens: &mut [StageToken<'_>]);
+}
+
+impl<F> Stage for F
+where
+  F: Fn(&mut [StageToken<'_>]) + Send + Sync,
+{
+  fn process(&self, tokens: &mut [StageToken<'_>])
+  {
+    self(tokens)
+  }
+}
+
+/// Converts Myanmar text to MLCTS with custom stages.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::pipeline::{Pipeline, StageToken};
+/// use mlcts_generator::ConvertOptions;
+///
+/// let pipeline = Pipeline::new(ConvertOptions::default()).stage(
+///   |tokens: &mut [StageToken]| {
+///     for token in tokens.iter_mut().filter(|t| t.text == "ခွေး")
+///     {
+///       token.output = "***".to_string();
+///     }
+///   },
+/// );
+///
+/// assert_eq!(pipeline.convert("ခွေးကောင်"), "*** kaung");
+/// ```
+#[derive(Default)]
+pub struct Pipeline
+{
+  pub(crate) options: ConvertOptions,
+  pub(crate) stages: Vec<Box<dyn Stage>>,
+  pub(crate) segmenter: Option<Box<dyn Segmenter>>,
+}
+
+impl Pipeline
+{
+  /// Create a pipeline without custom stages.
+  ///
+  /// # Arguments
+  ///
+  /// * `options` - The conversion options.
+  ///
+  /// # Returns
+  ///
+  /// A new pipeline.
+  pub fn new(options: ConvertOptions) -> Self
+  {
+    Self {
+      options,
+      stages: Vec::new(),
+      segmenter: None,
+    }
+  }
+
+  /// Add a stage after the stages added so far.
+  ///
+  /// # Arguments
+  ///
+  /// * `stage` - The stage to add.
+  ///
+  /// # Returns
+  ///
+  /// The pipeline with the stage.
+  pub fn stage(mut self, stage: impl Stage + 'static) -> Self
+  {
+    self.stages.push(Box::new(stage));
+    self
+  }
+
+  /// Set the segmenter splitting runs of syllables into words. Every
+  /// syllable is a word by default.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_generator::pipeline::{Pipeline, StageToken};
+  /// use mlcts_generator::segment::DictionarySegmenter;
+  /// use mlcts_generator::ConvertOptions;
+  ///
+  /// // join the syllables of every word
+  /// let pipeline = Pipeline::new(ConvertOptions::default())
+  ///   .segmenter(DictionarySegmenter::new(["မြန်မာ", "ကျောင်းသား"]))
+  ///   .stage(|tokens: &mut [StageToken]| {
+  ///     for token in tokens.iter_mut().filter(|t| !t.word_start)
+  ///     {
+  ///       token.output.insert(0, '-');
+  ///     }
+  ///   });
+  ///
+  /// assert_eq!(pipeline.convert("မြန်မာကျောင်းသား"), "mran -ma kyaung: -sa:");
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `segmenter` - The segmenter.
+  ///
+  /// # Returns
+  ///
+  /// The pipeline with the segmenter.
+  pub fn segmenter(mut self, segmenter: impl Segmenter + 'static) -> Self
+  {
+    self.segmenter = Some(Box::new(segmenter));
+    self
+  }
+
+  /// Convert Myanmar text to MLCTS.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The Myanmar text to convert.
+  ///
+  /// # Returns
+  ///
+  /// Space-separated syllables in MLCTS.
+  pub fn convert(&self, input: &str) -> String
+  {
+    let mut joiner = TokenJoiner::new(&self.options);
+    self.convert_into(input, &mut joiner, |_| ());
+    joiner.take_output()
+  }
+
+  /// Convert Myanmar text and join the converted tokens with the given
+  /// joiner.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The Myanmar text to convert.
+  /// * `joiner` - The joiner to join the converted tokens with.
+  /// * `joined` - Called with the joiner after every joined token.
+  ///
+  /// # Returns
+  ///
+  /// The counters of the conversion.
+  pub(crate) fn convert_into(
+    &self,
+    input: &str,
+    joiner: &mut TokenJoiner,
+    joined: impl FnMut(&mut TokenJoiner),
+  ) -> ConvertCounters
+  {
+    convert_into(
+      input,
+      &self.options,
+      &self.stages,
+      self.segmenter.as_deref(),
+      joiner,
+      joined,
+    )
+  }
+}
+
+impl std::fmt::Debug for Pipeline
+{
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result
+  {
+    f.debug_struct("Pipeline")
+      .field("options", &self.options)
+      .field("stages", &self.stages.len())
+      .field("segmenter", &self.segmenter.is_some())
+      .finish()
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::{Pipeline, StageToken};
+  use crate::dictionary::UserDictionary;
+  use crate::segment::DictionarySegmenter;
+  use crate::{ConvertOptions, TokenKind};
+
+  #[test]
+  fn test_pipeline()
+  {
+    let mut dictionary = UserDictionary::new();
+    dictionary.substitute("ရန်ကုန်", "Yangon");
+    let options = ConvertOptions {
+      dictionary,
+      western_punctuation: true,
+      ..Default::default()
+    };
+
+    // stages run in order and see dictionary terms as single tokens
+    let pipeline = Pipeline::new(options)
+      .stage(|tokens: &mut [StageToken]| {
+        for token in tokens.iter_mut()
+        {
+          if token.token.kind == TokenKind::Other && token.output == "Yangon"
+          {
+            token.output = token.text.to_string();
+          }
+        }
+      })
+      .stage(|tokens: &mut [StageToken]| {
+        for token in tokens.iter_mut()
+        {
+          token.output = token.output.to_uppercase();
+        }
+      });
+    assert_eq!(pipeline.convert("ရန်ကုန်မြို့။"), "ရန်ကုန် MRUI..");
+
+    assert_eq!(Pipeline::default().convert("ကား"), "ka:");
+
+    // terms are only matched at the word boundaries of the segmenter
+    let mut dictionary = UserDictionary::new();
+    dictionary.substitute("ကျောင်း", "school");
+    let options = ConvertOptions {
+      dictionary,
+      ..Default::default()
+    };
+    let input = "ကျောင်းသား ကျောင်းကို";
+    assert_eq!(
+      Pipeline::new(options.clone()).convert(input),
+      "school sa:   school kui"
+    );
+    let pipeline =
+      Pipeline::new(options).segmenter(DictionarySegmenter::new(["ကျောင်းသား"]));
+    assert_eq!(pipeline.convert(input), "kyaung: sa:   school kui");
+  }
+
+  #[test]
+  fn test_pipeline_shared_between_threads()
+  {
+    fn assert_send_sync<T: Send + Sync>()
+    {
+    }
+    assert_send_sync::<Pipeline>();
+    assert_send_sync::<ConvertOptions>();
+    assert_send_sync::<UserDictionary>();
+    assert_send_sync::<crate::segment::DictionarySegmenter>();
+    assert_send_sync::<crate::stream::StreamConverter>();
+
+    let pipeline = std::sync::Arc::new(Pipeline::default().stage(
+      |tokens: &mut [StageToken]| {
+        for token in tokens.iter_mut()
+        {
+          token.output = token.output.to_uppercase();
+        }
+      },
+    ));
+    let threads = (0 .. 4)
+      .map(|_| {
+        let pipeline = pipeline.clone();
+        std::thread::spawn(move || pipeline.convert("မင်္ဂလာပါ"))
+      })
+      .collect::<Vec<_>>();
+    for thread in threads
+    {
+      assert_eq!(thread.join().unwrap(), "MANGGA. LA PA");
+    }
+  }
+}
@@ -0,0 +1,25 @@
+//! The stable core of the crate.
+//!
+//! Everything re-exported here follows semantic versioning: it only changes
+//! in a breaking way with a major release. The enums of this crate and
+//! [`ConvertOptions`] are `#[non_exhaustive]`, so new variants and options
+//! are added in minor releases. The letter enums of `mlcts_core`
+//! ([`BasicConsonant`], [`BasicVowel`], [`MedialDiacritic`], [`Virama`] and
+//! [`Tone`]) are exhaustive and can be matched completely, so adding a
+//! variant to them is a breaking change. Bring it in with a glob import.
+//!
+//! ```
+//! use mlcts_generator::prelude::*;
+//!
+//! assert_eq!(mlcts_from_myanmar("မြန်မာ"), "mran ma");
+//!
+//! let (syllable, len) = syllable_from_myanmar("မြန်").unwrap();
+//! assert_eq!(syllable.to_mlcts(), "mran");
+//! assert_eq!(len, "မြန်".len());
+//! ```
+
+pub use mlcts_core::{BasicConsonant, BasicVowel, Consonant, MedialDiacritic, Syllable, Tone, Virama, Vowel};
+
+pub use crate::converter::{Converter, ConverterBuilder, InputTooLarge, Scheme, UnsupportedOption};
+pub use crate::normalize::normalize_myanmar;
+pub use crate::{mlcts_from_myanmar, mlcts_from_myanmar_with_options, split_syllables, syllable_from_myanmar, ConvertOptions, Fallback, Homophones, ParseError, Token, TokenKind};
@@ -0,0 +1,479 @@
+//! Dates, times and measurements written with Myanmar digits.
+//!
+//! A quantity is a number in Myanmar digits followed by a unit word, e.g.
+//! ၁၃၈၆ ခုနှစ် (the year 1386) or ၅ မိနစ် (5 minutes). Digits are converted
+//! one by one otherwise, so [`QuantityStage`] can be added to a
+//! [`crate::pipeline::Pipeline`] to write every quantity with a formatter
+//! instead.
+
+use crate::pipeline::{Stage, StageToken};
+use crate::{mlcts_from_myanmar, split_syllables};
+
+/// Represents the unit of a quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Unit
+{
+  /// ခုနှစ်, a calendar year.
+  Year,
+  /// လ
+  Month,
+  /// ရက်
+  Day,
+  /// နာရီ
+  Hour,
+  /// မိနစ်
+  Minute,
+  /// စက္ကန့်
+  Second,
+  /// ကျပ်, the currency.
+  Kyat,
+  /// ပြား, a hundredth of a kyat.
+  Pya,
+  /// ကျပ်သား, the weight.
+  Tical,
+  /// ပိဿာ
+  Viss,
+  /// လက်မ
+  Inch,
+  /// ပေ
+  Foot,
+  /// ကိုက်
+  Yard,
+  /// မိုင်
+  Mile,
+  /// မီတာ
+  Metre,
+  /// ကီလိုမီတာ
+  Kilometre,
+  /// ကီလိုဂရမ်
+  Kilogram,
+  /// ရာခိုင်နှုန်း
+  Percent,
+}
+
+/// Unit words and their units, normalized with
+/// [`crate::normalize::normalize_myanmar`]. Words
+/// made of other unit words (ကျပ်သား) are found since the longest word wins.
+const UNITS: &[(&str, Unit)] = &[
+  ("ခုနှစ်", Unit::Year),
+  ("လ", Unit::Month),
+  ("ရက်", Unit::Day),
+  ("နာရီ", Unit::Hour),
+  ("မိနစ်", Unit::Minute),
+  ("စက္ကန့်", Unit::Second),
+  ("ကျပ်", Unit::Kyat),
+  ("ပြား", Unit::Pya),
+  ("ကျပ်သား", Unit::Tical),
+  ("ပိဿာ", Unit::Viss),
+  ("လက်မ", Unit::Inch),
+  ("ပေ", Unit::Foot),
+  ("ကိုက်", Unit::Yard),
+  ("မိုင်", Unit::Mile),
+  ("မီတာ", Unit::Metre),
+  ("ကီလိုမီတာ", Unit::Kilometre),
+  ("ကီလိုဂရမ်", Unit::Kilogram),
+  ("ရာခိုင်နှုန်း", Unit::Percent),
+];
+
+/// The most syllables of a unit word.
+const MAX_UNIT_SYLLABLES: usize = 4;
+
+impl Unit
+{
+  /// Get the Myanmar word of the unit.
+  ///
+  /// # Returns
+  ///
+  /// The unit word in Myanmar script.
+  pub fn myanmar(self) -> &'static str
+  {
+    UNITS
+      .iter()
+      .find(|(_, unit)| *unit == self)
+      .map(|(word, _)| *word)
+      .unwrap_or_default()
+  }
+
+  /// Get the English name of the unit.
+  ///
+  /// # Arguments
+  ///
+  /// * `value` - The number of units, to choose between the singular and the
+  ///   plural.
+  ///
+  /// # Returns
+  ///
+  /// The English name of the unit.
+  pub fn english(self, value: u64) -> &'static str
+  {
+    let (singular, plural) = match self
+    {
+      Unit::Year => ("year", "years"),
+      Unit::Month => ("month", "months"),
+      Unit::Day => ("day", "days"),
+      Unit::Hour => ("hour", "hours"),
+      Unit::Minute => ("minute", "minutes"),
+      Unit::Second => ("second", "seconds"),
+      Unit::Kyat => ("kyat", "kyat"),
+      Unit::Pya => ("pya", "pya"),
+      Unit::Tical => ("tical", "ticals"),
+      Unit::Viss => ("viss", "viss"),
+      Unit::Inch => ("inch", "inches"),
+      Unit::Foot => ("foot", "feet"),
+      Unit::Yard => ("yard", "yards"),
+      Unit::Mile => ("mile", "miles"),
+      Unit::Metre => ("metre", "metres"),
+      Unit::Kilometre => ("kilometre", "kilometres"),
+      Unit::Kilogram => ("kilogram", "kilograms"),
+      Unit::Percent => ("percent", "percent"),
+    };
+    match value
+    {
+      1 => singular,
+      _ => plural,
+    }
+  }
+}
+
+/// Represents a number followed by a unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Quantity
+{
+  /// The number.
+  pub value: u64,
+  /// The unit.
+  pub unit: Unit,
+}
+
+/// Represents a quantity found in text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuantityMatch
+{
+  /// The quantity.
+  pub quantity: Quantity,
+  /// The start position of the number in the input.
+  pub start: usize,
+  /// The length of the number and the unit in the input.
+  pub len: usize,
+}
+
+/// Writes quantities. Formatters are shared with the pipeline, so they must
+/// be usable from several threads at once.
+pub trait QuantityFormatter: Send + Sync
+{
+  /// Write a quantity.
+  ///
+  /// # Arguments
+  ///
+  /// * `quantity` - The quantity to write.
+  ///
+  /// # Returns
+  ///
+  /// The text written instead of the number and the unit.
+  fn format(&self, quantity: &Quantity) -> String;
+}
+
+impl<F> QuantityFormatter for F
+where
+  F: Fn(&Quantity) -> String + Send + Sync,
+{
+  fn format(&self, quantity: &Quantity) -> String
+  {
+    self(quantity)
+  }
+}
+
+/// Writes the number with western digits and the unit in MLCTS, e.g.
+/// `1386 hku. hnac`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RomanizedFormatter;
+
+impl QuantityFormatter for RomanizedFormatter
+{
+  fn format(&self, quantity: &Quantity) -> String
+  {
+    format!(
+      "{} {}",
+      quantity.value,
+      mlcts_from_myanmar(quantity.unit.myanmar())
+    )
+  }
+}
+
+/// Writes quantities in English, e.g. `year 1386`, `5 minutes` or `20%`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnglishFormatter;
+
+impl QuantityFormatter for EnglishFormatter
+{
+  fn format(&self, quantity: &Quantity) -> String
+  {
+    match quantity.unit
+    {
+      Unit::Year => format!("year {}", quantity.value),
+      Unit::Percent => format!("{}%", quantity.value),
+      unit => format!("{} {}", quantity.value, unit.english(quantity.value)),
+    }
+  }
+}
+
+/// Writes the quantities of the input with a formatter instead of
+/// converting their digits and unit words.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::pipeline::Pipeline;
+/// use mlcts_generator::quantity::{EnglishFormatter, Quantity, QuantityStage};
+///
+/// let pipeline =
+///   Pipeline::default().stage(QuantityStage::new(EnglishFormatter));
+/// assert_eq!(
+///   pipeline.convert("၁၃၈၆ ခုနှစ် ၁၀ နာရီ ၃၀ မိနစ်"),
+///   "year 1386   10 hours   30 minutes"
+/// );
+///
+/// let pipeline = Pipeline::default().stage(QuantityStage::new(
+///   |quantity: &Quantity| format!("{:?}={}", quantity.unit, quantity.value),
+/// ));
+/// assert_eq!(pipeline.convert("ပေ ၅ ပေ"), "pe   Foot=5");
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct QuantityStage<F>
+{
+  formatter: F,
+}
+
+impl<F: QuantityFormatter> QuantityStage<F>
+{
+  /// Create a stage writing quantities with the formatter.
+  ///
+  /// # Arguments
+  ///
+  /// * `formatter` - The formatter of the quantities.
+  ///
+  /// # Returns
+  ///
+  /// A new quantity stage.
+  pub fn new(formatter: F) -> Self
+  {
+    Self { formatter }
+  }
+}
+
+impl<F: QuantityFormatter> Stage for QuantityStage<F>
+{
+  fn process(&self, tokens: &mut [StageToken<'_>])
+  {
+    let texts = tokens.iter().map(|t| t.text).collect::<Vec<_>>();
+    for (quantity, first, end) in find(&texts)
+    {
+      tokens[first].output = self.formatter.format(&quantity);
+      for token in &mut tokens[first + 1 .. end]
+      {
+        token.output.clear();
+      }
+    }
+  }
+}
+
+/// Find the quantities in Myanmar text.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::quantity::{find_quantities, Unit};
+///
+/// let input = "၂၀၂၄ ခုနှစ် ဒီဇင်ဘာလ ၂၅ ရက်";
+/// let matches = find_quantities(input);
+///
+/// assert_eq!(matches.len(), 2);
+/// assert_eq!(matches[0].quantity.value, 2024);
+/// assert_eq!(matches[0].quantity.unit, Unit::Year);
+/// assert_eq!(matches[0].len, "၂၀၂၄ ခုနှစ်".len());
+/// assert_eq!(matches[1].quantity.value, 25);
+/// assert_eq!(matches[1].quantity.unit, Unit::Day);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text, expected to be normalized with
+///   [`crate::normalize::normalize_myanmar`].
+///
+/// # Returns
+///
+/// The quantities in the order they appear in the input.
+pub fn find_quantities(input: &str) -> Vec<QuantityMatch>
+{
+  let syllables = split_syllables(input);
+  let texts = syllables.iter().map(|s| s.0).collect::<Vec<_>>();
+  find(&texts)
+    .into_iter()
+    .map(|(quantity, first, end)| {
+      let (_, start, _) = syllables[first];
+      let (_, last_start, last_len) = syllables[end - 1];
+      QuantityMatch {
+        quantity,
+        start,
+        len: last_start + last_len - start,
+      }
+    })
+    .collect()
+}
+
+/// Find the quantities in split text.
+///
+/// # Arguments
+///
+/// * `texts` - The texts of the syllables and the other tokens.
+///
+/// # Returns
+///
+/// The quantities with the index of their first token and the index after
+/// their last token.
+fn find(texts: &[&str]) -> Vec<(Quantity, usize, usize)>
+{
+  let mut found = Vec::new();
+  let mut i = 0;
+  while i < texts.len()
+  {
+    let first = i;
+    let mut value = Some(0u64);
+    while i < texts.len() && is_number(texts[i])
+    {
+      value = texts[i].chars().fold(value, |value, c| {
+        value?.checked_mul(10)?.checked_add(u64::from(digit(c)))
+      });
+      i += 1;
+    }
+    let Some(value) = value.filter(|_| i > first)
+    else
+    {
+      i = i.max(first + 1);
+      continue;
+    };
+
+    let mut unit_start = i;
+    while unit_start < texts.len() && texts[unit_start].trim().is_empty()
+    {
+      unit_start += 1;
+    }
+    if let Some((unit, len)) = unit(&texts[unit_start ..])
+    {
+      found.push((Quantity { value, unit }, first, unit_start + len));
+      i = unit_start + len;
+    }
+  }
+  found
+}
+
+/// Find the longest unit word at the start of the texts.
+///
+/// # Arguments
+///
+/// * `texts` - The texts following a number.
+///
+/// # Returns
+///
+/// The unit and its number of texts, or `None` if the texts don't start
+/// with a unit word.
+fn unit(texts: &[&str]) -> Option<(Unit, usize)>
+{
+  let mut word = String::new();
+  let mut longest = None;
+  for (i, text) in texts.iter().take(MAX_UNIT_SYLLABLES).enumerate()
+  {
+    word.push_str(text);
+    let unit = UNITS.iter().find(|(unit_word, _)| *unit_word == word);
+    if let Some((_, unit)) = unit
+    {
+      longest = Some((*unit, i + 1));
+    }
+  }
+  longest
+}
+
+/// Check if a text is made of Myanmar digits.
+///
+/// # Arguments
+///
+/// * `text` - The text to check.
+///
+/// # Returns
+///
+/// `true` if the text is made of Myanmar digits, `false` otherwise.
+pub(crate) fn is_number(text: &str) -> bool
+{
+  !text.is_empty() && text.chars().all(|c| matches!(c, '၀' ..= '၉'))
+}
+
+/// Get the value of a Myanmar digit.
+///
+/// # Arguments
+///
+/// * `c` - The Myanmar digit.
+///
+/// # Returns
+///
+/// The value of the digit.
+pub(crate) fn digit(c: char) -> u8
+{
+  (c as u32 - '၀' as u32) as u8
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::{find_quantities, EnglishFormatter, QuantityStage, RomanizedFormatter, Unit, UNITS};
+  use crate::normalize::normalize_myanmar;
+  use crate::pipeline::Pipeline;
+  use crate::ConvertOptions;
+
+  #[test]
+  fn test_find_quantities()
+  {
+    let found = |input| {
+      find_quantities(input)
+        .into_iter()
+        .map(|m| (m.quantity.value, m.quantity.unit, m.start, m.len))
+        .collect::<Vec<_>>()
+    };
+
+    assert_eq!(found("၅ ယောက်"), []);
+    assert_eq!(found("ခုနှစ်"), []);
+    assert_eq!(found("၁၀၀ကျပ်"), [(100, Unit::Kyat, 0, "၁၀၀ကျပ်".len())]);
+    // the longest unit word wins
+    assert_eq!(found("၂ ကျပ်သား"), [(2, Unit::Tical, 0, "၂ ကျပ်သား".len())]);
+    assert_eq!(found("၂ ကျပ် သား"), [(2, Unit::Kyat, 0, "၂ ကျပ်".len())]);
+    assert_eq!(
+      found("ငွေ ၅၀ ရာခိုင်နှုန်း"),
+      [(50, Unit::Percent, "ငွေ ".len(), "၅၀ ရာခိုင်နှုန်း".len())]
+    );
+    // too large for a number
+    assert_eq!(found("၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉၉ ပေ"), []);
+
+    // unit words are compared without normalizing them
+    for (word, _) in UNITS
+    {
+      assert_eq!(normalize_myanmar(word), *word);
+    }
+  }
+
+  #[test]
+  fn test_quantity_stage()
+  {
+    let input = "၁၃၈၆ ခုနှစ်တွင် ၁ မိုင် ၂ ပေ";
+
+    let pipeline =
+      Pipeline::default().stage(QuantityStage::new(EnglishFormatter));
+    assert_eq!(pipeline.convert(input), "year 1386 twang   1 mile   2 feet");
+
+    let pipeline = Pipeline::new(ConvertOptions {
+      western_punctuation: true,
+      ..Default::default()
+    })
+    .stage(QuantityStage::new(RomanizedFormatter));
+    assert_eq!(pipeline.convert(input), "1386 hku. hnac twang 1 muing 2 pe");
+  }
+}
@@ -0,0 +1,552 @@
+//! Word segmentation of Myanmar text.
+//!
+//! Myanmar text is written without spaces between words. A [`Segmenter`]
+//! splits a run of syllables (the syllables between spaces and punctuation)
+//! into words. The built-in segmenters are a dictionary segmenter and one
+//! taking every run as a single word, other models (CRF, neural, etc.) can
+//! be plugged in by implementing the trait.
+
+use std::collections::BTreeSet;
+
+use crate::normalize::normalize_myanmar;
+use crate::{get_token, split_syllables, Token, TokenKind};
+
+/// Splits runs of syllables into words. Segmenters must be usable from
+/// several threads at once.
+pub trait Segmenter: Send + Sync
+{
+  /// Split a run of syllables into words.
+  ///
+  /// # Arguments
+  ///
+  /// * `syllables` - The normalized syllables of the run.
+  ///
+  /// # Returns
+  ///
+  /// The end of every word as a syllable index, in increasing order. The
+  /// last one is the number of syllables. Ends which break this are skipped
+  /// by the callers in this crate, and the syllables after the last valid
+  /// end are taken as one word.
+  fn segment(&self, syllables: &[&str]) -> Vec<usize>;
+
+  /// Check if a word is known to the segmenter, e.g. it is in its word
+  /// list. Callers such as [`crate::grammar::tag_particles`] only split
+  /// words further which are not known. By default, no word is known.
+  ///
+  /// # Arguments
+  ///
+  /// * `syllables` - The normalized syllables of the word.
+  ///
+  /// # Returns
+  ///
+  /// `true` if the word is known, `false` otherwise.
+  fn knows(&self, syllables: &[&str]) -> bool
+  {
+    let _ = syllables;
+    false
+  }
+
+  /// Get every plausible segmentation of a run of syllables as a lattice,
+  /// e.g. to defer the choice to a later step. By default, the lattice only
+  /// has the words of [`Segmenter::segment`].
+  ///
+  /// # Arguments
+  ///
+  /// * `syllables` - The normalized syllables of the run.
+  ///
+  /// # Returns
+  ///
+  /// The word lattice of the run.
+  fn lattice(&self, syllables: &[&str]) -> WordLattice
+  {
+    let mut lattice = WordLattice::new(syllables);
+    let mut start = 0;
+    for end in word_ends(self, syllables)
+    {
+      lattice.edges.push(LatticeEdge {
+        start,
+        end,
+        score: 1.0,
+      });
+      start = end;
+    }
+    lattice
+  }
+}
+
+/// The possible words of a run of syllables. Every path of edges from the
+/// first to the last syllable is a segmentation.
+#[derive(
+  Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize,
+)]
+pub struct WordLattice
+{
+  /// The syllables of the run.
+  pub syllables: Vec<String>,
+  /// The possible words, sorted by their start and end.
+  pub edges: Vec<LatticeEdge>,
+}
+
+impl WordLattice
+{
+  /// Create a lattice without edges.
+  ///
+  /// # Arguments
+  ///
+  /// * `syllables` - The syllables of the run.
+  ///
+  /// # Returns
+  ///
+  /// A new word lattice.
+  pub fn new(syllables: &[&str]) -> Self
+  {
+    Self {
+      syllables: syllables.iter().map(|s| s.to_string()).collect(),
+      edges: Vec::new(),
+    }
+  }
+
+  /// Get the word of an edge.
+  ///
+  /// # Arguments
+  ///
+  /// * `edge` - An edge of the lattice.
+  ///
+  /// # Returns
+  ///
+  /// The syllables of the word joined together.
+  pub fn word(&self, edge: &LatticeEdge) -> String
+  {
+    self.syllables[edge.start .. edge.end].concat()
+  }
+}
+
+/// A possible word of a [`WordLattice`].
+#[derive(
+  Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize,
+)]
+pub struct LatticeEdge
+{
+  /// The index of the first syllable of the word.
+  pub start: usize,
+  /// The index after the last syllable of the word.
+  pub end: usize,
+  /// How plausible the word is, from `0.0` to `1.0`.
+  pub score: f64,
+}
+
+/// Takes every run of syllables as a single word. This is the segmenter
+/// used when no other segmenter is given.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RunSegmenter;
+
+impl Segmenter for RunSegmenter
+{
+  fn segment(&self, syllables: &[&str]) -> Vec<usize>
+  {
+    match syllables.len()
+    {
+      0 => vec![],
+      len => vec![len],
+    }
+  }
+}
+
+/// Splits runs of syllables into the longest words of a word list, from left
+/// to right. Syllables not starting a known word are single words.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::segment::{DictionarySegmenter, Segmenter};
+///
+/// let segmenter = DictionarySegmenter::new(["ကျောင်းသား", "ကျောင်း"]);
+/// let syllables = ["ကျောင်း", "သား", "ကျောင်း", "ပါ"];
+///
+/// assert_eq!(segmenter.segment(&syllables), vec![2, 3, 4]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DictionarySegmenter
+{
+  /// Normalized words.
+  words: BTreeSet<String>,
+  /// The number of syllables of the longest word.
+  max_syllables: usize,
+}
+
+impl DictionarySegmenter
+{
+  /// Create a segmenter from a word list.
+  ///
+  /// # Arguments
+  ///
+  /// * `words` - The words in Myanmar script.
+  ///
+  /// # Returns
+  ///
+  /// A new dictionary segmenter.
+  pub fn new<'w>(words: impl IntoIterator<Item = &'w str>) -> Self
+  {
+    let mut segmenter = Self::default();
+    for word in words
+    {
+      let word = normalize_myanmar(word.trim());
+      if word.is_empty()
+      {
+        continue;
+      }
+      segmenter.max_syllables =
+        segmenter.max_syllables.max(split_syllables(&word).len());
+      segmenter.words.insert(word.into_owned());
+    }
+    segmenter
+  }
+}
+
+impl Segmenter for DictionarySegmenter
+{
+  fn segment(&self, syllables: &[&str]) -> Vec<usize>
+  {
+    let mut ends = Vec::new();
+    let mut start = 0;
+    while start < syllables.len()
+    {
+      let longest = (start + 1
+        ..= syllables.len().min(start + self.max_syllables))
+        .rev()
+        .find(|end| self.words.contains(&syllables[start .. *end].concat()));
+      start = longest.unwrap_or(start + 1);
+      ends.push(start);
+    }
+    ends
+  }
+
+  fn knows(&self, syllables: &[&str]) -> bool
+  {
+    self.words.contains(&syllables.concat())
+  }
+
+  /// Every word of the word list in the run is an edge scored `1.0`.
+  /// Syllables not starting a known word are single words scored `0.0`,
+  /// so that every syllable can be reached.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_generator::segment::{DictionarySegmenter, Segmenter};
+  ///
+  /// let segmenter = DictionarySegmenter::new(["ကျောင်းသား", "ကျောင်း"]);
+  /// let lattice = segmenter.lattice(&["ကျောင်း", "သား", "ပါ"]);
+  /// let words = lattice
+  ///   .edges
+  ///   .iter()
+  ///   .map(|e| (lattice.word(e), e.score))
+  ///   .collect::<Vec<_>>();
+  ///
+  /// assert_eq!(
+  ///   words,
+  ///   [
+  ///     ("ကျောင်း".to_string(), 1.0),
+  ///     ("ကျောင်းသား".to_string(), 1.0),
+  ///     ("သား".to_string(), 0.0),
+  ///     ("ပါ".to_string(), 0.0),
+  ///   ]
+  /// );
+  /// ```
+  fn lattice(&self, syllables: &[&str]) -> WordLattice
+  {
+    let mut lattice = WordLattice::new(syllables);
+    for start in 0 .. syllables.len()
+    {
+      let known = (start + 1
+        ..= syllables.len().min(start + self.max_syllables))
+        .filter(|end| self.words.contains(&syllables[start .. *end].concat()))
+        .collect::<Vec<_>>();
+      if known.first() != Some(&(start + 1))
+      {
+        lattice.edges.push(LatticeEdge {
+          start,
+          end: start + 1,
+          score: 0.0,
+        });
+      }
+      lattice
+        .edges
+        .extend(known.into_iter().map(|end| LatticeEdge {
+          start,
+          end,
+          score: 1.0,
+        }));
+    }
+    lattice
+  }
+}
+
+/// Split Myanmar text into words with a segmenter. Spaces, punctuation and
+/// other tokens which are not syllables are left out.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::segment::{segment_words, DictionarySegmenter};
+///
+/// let segmenter = DictionarySegmenter::new(["ကျောင်းသား", "မြန်မာ"]);
+/// let words = segment_words("မြန်မာကျောင်းသားပါ။", &segmenter)
+///   .into_iter()
+///   .map(|w| w.0)
+///   .collect::<Vec<_>>();
+///
+/// assert_eq!(words, ["မြန်မာ", "ကျောင်းသား", "ပါ"]);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text, expected to be normalized with
+///   [`normalize_myanmar`].
+/// * `segmenter` - The segmenter splitting runs of syllables into words.
+///
+/// # Returns
+///
+/// The words with their start positions and lengths in bytes, like
+/// [`split_syllables`].
+pub fn segment_words<'i>(
+  input: &'i str,
+  segmenter: &dyn Segmenter,
+) -> Vec<(&'i str, usize, usize)>
+{
+  let mut words = Vec::new();
+  let mut run = Vec::new();
+  for token in get_token(input)
+  {
+    match token.kind
+    {
+      TokenKind::Syllable(_) => run.push((token.start, token.len)),
+      _ =>
+      {
+        push_words(input, &run, segmenter, &mut words);
+        run.clear();
+      }
+    }
+  }
+  push_words(input, &run, segmenter, &mut words);
+  words
+}
+
+/// Segment a run of syllables and add its words.
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text.
+/// * `run` - The start positions and lengths of the syllables of the run.
+/// * `segmenter` - The segmenter.
+/// * `words` - The words to add to.
+fn push_words<'i>(
+  input: &'i str,
+  run: &[(usize, usize)],
+  segmenter: &dyn Segmenter,
+  words: &mut Vec<(&'i str, usize, usize)>,
+)
+{
+  let syllables = run
+    .iter()
+    .map(|&(start, len)| &input[start .. start + len])
+    .collect::<Vec<_>>();
+  let mut first = 0;
+  for end in word_ends(segmenter, &syllables)
+  {
+    let start = run[first].0;
+    let (last_start, last_len) = run[end - 1];
+    let len = last_start + last_len - start;
+    words.push((&input[start .. start + len], start, len));
+    first = end;
+  }
+}
+
+/// Find the tokens starting a word. Runs of syllables are split with the
+/// segmenter, every other token is a word of its own.
+///
+/// # Arguments
+///
+/// * `input` - The normalized Myanmar text.
+/// * `tokens` - The tokens of the input in order.
+/// * `segmenter` - The segmenter.
+///
+/// # Returns
+///
+/// Whether each token starts a word.
+pub(crate) fn word_starts(
+  input: &str,
+  tokens: &[Token<'_>],
+  segmenter: &dyn Segmenter,
+) -> Vec<bool>
+{
+  let mut starts = vec![true; tokens.len()];
+  let mut run = Vec::new();
+  for (i, token) in tokens.iter().enumerate()
+  {
+    if let TokenKind::Syllable(_) = token.kind
+    {
+      run.push(i);
+      if i + 1 < tokens.len()
+      {
+        continue;
+      }
+    }
+    if let Some(&first) = run.first()
+    {
+      let syllables = run
+        .iter()
+        .map(|&i| &input[tokens[i].start .. tokens[i].start + tokens[i].len])
+        .collect::<Vec<_>>();
+      let ends = word_ends(segmenter, &syllables);
+      for j in 1 .. run.len()
+      {
+        starts[first + j] = ends.contains(&j);
+      }
+      run.clear();
+    }
+  }
+  starts
+}
+
+/// Segment a run of syllables and keep the valid word ends only: ends which
+/// are not increasing or are past the run are skipped, and the run always
+/// ends a word.
+///
+/// # Arguments
+///
+/// * `segmenter` - The segmenter.
+/// * `syllables` - The normalized syllables of the run.
+///
+/// # Returns
+///
+/// The end of every word as a syllable index, in increasing order.
+pub(crate) fn word_ends<S: Segmenter + ?Sized>(
+  segmenter: &S,
+  syllables: &[&str],
+) -> Vec<usize>
+{
+  let mut ends = Vec::new();
+  let mut start = 0;
+  for end in segmenter.segment(syllables)
+  {
+    if end > start && end <= syllables.len()
+    {
+      ends.push(end);
+      start = end;
+    }
+  }
+  if start < syllables.len()
+  {
+    ends.push(syllables.len());
+  }
+  ends
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::{segment_words, word_ends, DictionarySegmenter, LatticeEdge, RunSegmenter, Segmenter};
+
+  #[test]
+  fn test_segmenters()
+  {
+    assert_eq!(RunSegmenter.segment(&[]), Vec::<usize>::new());
+    assert_eq!(RunSegmenter.segment(&["က", "ခ"]), vec![2]);
+
+    let segmenter =
+      DictionarySegmenter::new(["မြန်မာ", "မြန်မာစာ", "ကွန်ပျူတာ", " "]);
+    let syllables = ["မြန်", "မာ", "စာ", "ကွန်", "ပျူ", "တာ", "ပါ"];
+    assert_eq!(segmenter.segment(&syllables), vec![3, 6, 7]);
+    assert_eq!(segmenter.segment(&syllables[1 ..]), vec![1, 2, 5, 6]);
+    assert_eq!(DictionarySegmenter::default().segment(&["က"]), vec![1]);
+  }
+
+  /// Returns the same ends for every run.
+  struct FixedSegmenter(Vec<usize>);
+
+  impl Segmenter for FixedSegmenter
+  {
+    fn segment(&self, _: &[&str]) -> Vec<usize>
+    {
+      self.0.clone()
+    }
+  }
+
+  #[test]
+  fn test_misbehaving_segmenter()
+  {
+    let syllables = ["က", "ခ", "ဂ"];
+    let cases = [
+      (vec![], vec![3]),
+      (vec![1, 1, 0, 2], vec![1, 2, 3]),
+      (vec![2, 1, 9], vec![2, 3]),
+      (vec![0, 3, 4], vec![3]),
+    ];
+    for (ends, expected) in cases
+    {
+      assert_eq!(
+        word_ends(&FixedSegmenter(ends.clone()), &syllables),
+        expected
+      );
+      let words = segment_words("ကခဂ", &FixedSegmenter(ends.clone()));
+      assert_eq!(words.len(), expected.len());
+      assert_eq!(words.iter().map(|(w, ..)| *w).collect::<String>(), "ကခဂ");
+      let tagged = crate::grammar::tag_particles("ကခဂ", &FixedSegmenter(ends));
+      assert_eq!(tagged.iter().map(|w| w.text).collect::<String>(), "ကခဂ");
+    }
+    assert_eq!(
+      word_ends(&FixedSegmenter(vec![1]), &[]),
+      Vec::<usize>::new()
+    );
+
+    let lattice = FixedSegmenter(vec![5]).lattice(&syllables);
+    assert_eq!(lattice.edges.len(), 1);
+    assert_eq!((lattice.edges[0].start, lattice.edges[0].end), (0, 3));
+  }
+
+  #[test]
+  fn test_lattice()
+  {
+    let edge = |start, end, score| LatticeEdge { start, end, score };
+
+    let lattice = RunSegmenter.lattice(&["က", "ခ"]);
+    assert_eq!(lattice.syllables, ["က", "ခ"]);
+    assert_eq!(lattice.edges, [edge(0, 2, 1.0)]);
+    assert!(RunSegmenter.lattice(&[]).edges.is_empty());
+
+    let segmenter =
+      DictionarySegmenter::new(["မြန်", "မြန်မာ", "မြန်မာစာ", "မာစာ", "စာ"]);
+    let lattice = segmenter.lattice(&["မြန်", "မာ", "စာ", "ပါ"]);
+    assert_eq!(
+      lattice.edges,
+      [
+        edge(0, 1, 1.0),
+        edge(0, 2, 1.0),
+        edge(0, 3, 1.0),
+        edge(1, 2, 0.0),
+        edge(1, 3, 1.0),
+        edge(2, 3, 1.0),
+        edge(3, 4, 0.0),
+      ]
+    );
+    assert_eq!(lattice.word(&lattice.edges[4]), "မာစာ");
+  }
+
+  #[test]
+  fn test_segment_words()
+  {
+    let segmenter = DictionarySegmenter::new(["မြန်မာ", "မြန်မာစာ"]);
+    let input = "မြန်မာစာ၊ မြန်မာ abc မြန်";
+    assert_eq!(
+      segment_words(input, &segmenter),
+      [
+        ("မြန်မာစာ", 0, "မြန်မာစာ".len()),
+        ("မြန်မာ", input.find(" မ").unwrap() + 1, "မြန်မာ".len()),
+        ("မြန်", input.rfind("မ").unwrap(), "မြန်".len()),
+      ]
+    );
+    assert_eq!(segment_words(input, &RunSegmenter).len(), 3);
+    assert!(segment_words("", &RunSegmenter).is_empty());
+  }
+}
@@ -0,0 +1,141 @@
+//! SQL functions for Myanmar text stored in SQLite, enabled with the
+//! `sqlite` feature.
+//!
+//! [`register_mlcts_functions`] adds these functions to a connection:
+//!
+//! * `mlcts(text)` converts the text with [`crate::mlcts_from_myanmar`].
+//! * `my_syllables(text)` splits the text into syllables separated by `|`.
+//! * `mlcts_fold(text)` gets the key of [`crate::phonetic::phonetic_key`], e.g.
+//!   to search names spelled differently.
+//!
+//! All of them return `NULL` for `NULL`.
+
+use rusqlite::functions::{Context, FunctionFlags};
+use rusqlite::{Connection, Result};
+
+use crate::normalize::normalize_myanmar;
+use crate::phonetic::phonetic_key;
+use crate::{mlcts_from_myanmar, split_syllables};
+
+/// Add the functions `mlcts`, `my_syllables` and `mlcts_fold` to a
+/// connection.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::sqlite::register_mlcts_functions;
+/// use rusqlite::Connection;
+///
+/// let connection = Connection::open_in_memory().unwrap();
+/// register_mlcts_functions(&connection).unwrap();
+///
+/// let mlcts: String = connection
+///   .query_row("SELECT mlcts('မင်္ဂလာပါ')", [], |row| {
+///     row.get(0)
+///   })
+///   .unwrap();
+/// assert_eq!(mlcts, "mangga. la pa");
+///
+/// // names spelled differently are found with the folded keys
+/// let found: bool = connection
+///   .query_row(
+///     "SELECT mlcts_fold('ကျော်') = mlcts_fold('Kyaw')",
+///     [],
+///     |row| row.get(0),
+///   )
+///   .unwrap();
+/// assert!(found);
+/// ```
+///
+/// # Arguments
+///
+/// * `connection` - The connection to add the functions to.
+///
+/// # Returns
+///
+/// The error of SQLite if a function can't be added.
+pub fn register_mlcts_functions(connection: &Connection) -> Result<()>
+{
+  let flags = FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC;
+  connection.create_scalar_function("mlcts", 1, flags, |ctx| {
+    map_text(ctx, mlcts_from_myanmar)
+  })?;
+  connection.create_scalar_function("my_syllables", 1, flags, |ctx| {
+    map_text(ctx, syllables)
+  })?;
+  connection.create_scalar_function("mlcts_fold", 1, flags, |ctx| {
+    map_text(ctx, phonetic_key)
+  })
+}
+
+/// Apply a function to the text argument of a SQL function.
+///
+/// # Arguments
+///
+/// * `ctx` - The context of the SQL function.
+/// * `f` - The function to apply.
+///
+/// # Returns
+///
+/// The result of the function, `None` if the argument is `NULL`.
+fn map_text(
+  ctx: &Context<'_>,
+  f: impl Fn(&str) -> String,
+) -> Result<Option<String>>
+{
+  Ok(ctx.get::<Option<String>>(0)?.map(|text| f(&text)))
+}
+
+/// Split Myanmar text into syllables, leaving out the spaces.
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text.
+///
+/// # Returns
+///
+/// The normalized syllables separated by `|`.
+fn syllables(input: &str) -> String
+{
+  let input = normalize_myanmar(input);
+  split_syllables(&input)
+    .into_iter()
+    .map(|(syllable, ..)| syllable)
+    .filter(|syllable| !syllable.trim().is_empty())
+    .collect::<Vec<_>>()
+    .join("|")
+}
+
+#[cfg(test)]
+mod tests
+{
+  use rusqlite::Connection;
+
+  use super::register_mlcts_functions;
+
+  #[test]
+  fn test_register_mlcts_functions()
+  {
+    let connection = Connection::open_in_memory().unwrap();
+    register_mlcts_functions(&connection).unwrap();
+
+    let query = |sql: &str| {
+      connection
+        .query_row(sql, [], |row| row.get::<_, Option<String>>(0))
+        .unwrap()
+    };
+    assert_eq!(query("SELECT mlcts('ကျောင်း')").as_deref(), Some("kyaung:"));
+    assert_eq!(
+      query("SELECT my_syllables('ကျွန်တော် တက္ကသိုလ်')").as_deref(),
+      Some("ကျွန်|တော်|တက္က|သိုလ်")
+    );
+    assert_eq!(
+      query("SELECT mlcts_fold('ကျော်')"),
+      query("SELECT mlcts_fold('Kyaw')")
+    );
+    for function in ["mlcts", "my_syllables", "mlcts_fold"]
+    {
+      assert_eq!(query(&format!("SELECT {}(NULL)", function)), None);
+    }
+  }
+}
@@ -0,0 +1,179 @@
+//! Text statistics of Myanmar text.
+//!
+//! Words are the runs of syllables between spaces, zero width spaces and
+//! punctuation unless a [`Segmenter`] is given.
+
+use crate::normalize::{normalize_myanmar_with, ZeroWidth};
+use crate::segment::{word_ends, RunSegmenter, Segmenter};
+use crate::{get_token, TokenKind};
+
+/// Represents statistics of Myanmar text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextStats
+{
+  /// Number of sentences. A sentence ends with ။ or at the end of the text.
+  pub sentences: usize,
+  /// Number of words.
+  pub words: usize,
+  /// Number of syllables as they are read, e.g. မင်္ဂ (mangga.) is two.
+  pub syllables: usize,
+}
+
+impl TextStats
+{
+  /// Get the average number of syllables per word.
+  ///
+  /// # Returns
+  ///
+  /// The average or `0.0` if there are no words.
+  pub fn syllables_per_word(&self) -> f64
+  {
+    match self.words
+    {
+      0 => 0.0,
+      words => self.syllables as f64 / words as f64,
+    }
+  }
+
+  /// Get the average number of words per sentence.
+  ///
+  /// # Returns
+  ///
+  /// The average or `0.0` if there are no sentences.
+  pub fn words_per_sentence(&self) -> f64
+  {
+    match self.sentences
+    {
+      0 => 0.0,
+      sentences => self.words as f64 / sentences as f64,
+    }
+  }
+}
+
+/// Count sentences, words and syllables of Myanmar text.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::stats::text_stats;
+///
+/// let stats = text_stats("မင်္ဂလာပါ ခင်ဗျာ။ နေကောင်းလား");
+///
+/// assert_eq!(stats.sentences, 2);
+/// assert_eq!(stats.words, 3);
+/// assert_eq!(stats.syllables, 9);
+/// assert_eq!(stats.syllables_per_word(), 3.0);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text.
+///
+/// # Returns
+///
+/// The statistics of the text.
+pub fn text_stats(input: &str) -> TextStats
+{
+  text_stats_with(input, &RunSegmenter)
+}
+
+/// Count sentences, words and syllables of Myanmar text with the given word
+/// segmenter.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::segment::DictionarySegmenter;
+/// use mlcts_generator::stats::text_stats_with;
+///
+/// let segmenter = DictionarySegmenter::new(["မင်္ဂလာ", "ခင်ဗျာ"]);
+/// let stats = text_stats_with("မင်္ဂလာပါ ခင်ဗျာ", &segmenter);
+///
+/// assert_eq!(stats.words, 3);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text.
+/// * `segmenter` - The segmenter to split runs of syllables into words.
+///
+/// # Returns
+///
+/// The statistics of the text.
+pub fn text_stats_with(input: &str, segmenter: &dyn Segmenter) -> TextStats
+{
+  let input = normalize_myanmar_with(input, ZeroWidth::WordBoundary);
+  let mut stats = TextStats::default();
+  let mut run = Vec::new();
+  let mut in_sentence = false;
+
+  for token in get_token(&input)
+  {
+    match token.kind
+    {
+      TokenKind::Syllable(syllable) =>
+      {
+        let mut stacked = syllable.stacked.as_deref();
+        stats.syllables += 1;
+        while let Some(s) = stacked
+        {
+          stats.syllables += 1;
+          stacked = s.stacked.as_deref();
+        }
+
+        run.push(&input[token.start .. token.start + token.len]);
+        in_sentence = true;
+      }
+      TokenKind::SpecialMapped(".") =>
+      {
+        stats.words += word_ends(segmenter, &run).len();
+        run.clear();
+        if in_sentence
+        {
+          stats.sentences += 1;
+        }
+        in_sentence = false;
+      }
+      _ =>
+      {
+        stats.words += word_ends(segmenter, &run).len();
+        run.clear();
+      }
+    }
+  }
+
+  stats.words += word_ends(segmenter, &run).len();
+  if in_sentence
+  {
+    stats.sentences += 1;
+  }
+
+  stats
+}
+
+#[cfg(test)]
+mod tests
+{
+  use crate::segment::DictionarySegmenter;
+
+  #[test]
+  fn test_text_stats()
+  {
+    assert_eq!(super::text_stats(""), super::TextStats::default());
+    assert_eq!(super::text_stats("").syllables_per_word(), 0.0);
+
+    let stats = super::text_stats(
+      "ကျွန်တော်က တက္ကသိုလ်ကျောင်းသားပါ။\n\
+      မြန်မာ\u{200B}စာ၊ ကွန်ပျူတာ။။",
+    );
+    assert_eq!(stats.sentences, 2);
+    assert_eq!(stats.words, 5);
+    assert_eq!(stats.syllables, 15);
+    assert_eq!(stats.words_per_sentence(), 2.5);
+
+    let segmenter = DictionarySegmenter::new(["တက္ကသိုလ်", "ကျောင်းသား"]);
+    let stats = super::text_stats_with("ကျွန်တော်က တက္ကသိုလ်ကျောင်းသားပါ။", &segmenter);
+    assert_eq!(stats.words, 6);
+    assert_eq!(stats.syllables, 9);
+  }
+}
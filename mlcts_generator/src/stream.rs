@@ -0,0 +1,371 @@
+//! Incremental conversion of text which arrives in chunks.
+//!
+//! A syllable might span two chunks (e.g. the asat of `က်` arriving with the
+//! next chunk), so the tail of the received text is kept until it is known
+//! that nothing following it can change its conversion.
+
+use std::io;
+
+use crate::autofix::is_confusable;
+use crate::join::TokenJoiner;
+use crate::{convert_into, split_syllables, ConvertOptions};
+
+/// Converts Myanmar text to MLCTS chunk by chunk. The concatenated output is
+/// the same as converting the whole text at once with
+/// [`crate::mlcts_from_myanmar_with_options`].
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::stream::StreamConverter;
+///
+/// let mut converter = StreamConverter::default();
+/// converter.push_chunk("မင်္ဂလာပ");
+/// converter.push_chunk("ါ ခင");
+///
+/// let mut output = converter.take_output();
+/// converter.push_chunk("်ဗျာ");
+/// output.push_str(&converter.finish());
+///
+/// assert_eq!(output, "mangga. la pa   hkang bya");
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamConverter
+{
+  options: ConvertOptions,
+  /// Received text which is not converted yet.
+  pending: String,
+  /// The position in the pending text to look for a split from. Text before
+  /// it was searched already and has no split.
+  scan_from: usize,
+  /// Joins converted text and keeps it until it is taken.
+  joiner: TokenJoiner,
+}
+
+impl Default for StreamConverter
+{
+  fn default() -> Self
+  {
+    Self::new(ConvertOptions::default())
+  }
+}
+
+impl StreamConverter
+{
+  /// Create a new stream converter with the given options.
+  ///
+  /// # Arguments
+  ///
+  /// * `options` - The conversion options.
+  ///
+  /// # Returns
+  ///
+  /// A new stream converter.
+  pub fn new(options: ConvertOptions) -> Self
+  {
+    Self {
+      joiner: TokenJoiner::new(&options),
+      options,
+      pending: String::new(),
+      scan_from: 0,
+    }
+  }
+
+  /// Push a chunk of Myanmar text. Every part of the received text which can
+  /// no longer be affected by following chunks is converted.
+  ///
+  /// # Arguments
+  ///
+  /// * `chunk` - The next chunk of the input.
+  pub fn push_chunk(&mut self, chunk: &str)
+  {
+    self.pending.push_str(chunk);
+
+    let (end, scan_from) =
+      safe_end(&self.pending, self.scan_from, &self.options);
+    self.scan_from = scan_from - end;
+    if end > 0
+    {
+      let rest = self.pending.split_off(end);
+      let complete = std::mem::replace(&mut self.pending, rest);
+      self.convert(&complete);
+    }
+  }
+
+  /// Take the converted text so far.
+  ///
+  /// # Returns
+  ///
+  /// MLCTS text converted since the last call.
+  pub fn take_output(&mut self) -> String
+  {
+    self.joiner.take_output()
+  }
+
+  /// Convert the remaining text, marking the end of the input.
+  ///
+  /// # Returns
+  ///
+  /// MLCTS text which is not taken yet.
+  pub fn finish(mut self) -> String
+  {
+    let pending = std::mem::take(&mut self.pending);
+    self.convert(&pending);
+    self.joiner.take_output()
+  }
+
+  /// Convert everything read from a reader, e.g. the standard input or a
+  /// file, line by line and write it to a writer as soon as it is converted.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_generator::mlcts_from_myanmar;
+  /// use mlcts_generator::stream::StreamConverter;
+  ///
+  /// let input = "မင်္ဂလာပါ\nခင်ဗျာ\n";
+  /// let mut output = Vec::new();
+  /// StreamConverter::default()
+  ///   .convert_reader(input.as_bytes(), &mut output)
+  ///   .unwrap();
+  ///
+  /// assert_eq!(
+  ///   String::from_utf8(output).unwrap(),
+  ///   mlcts_from_myanmar(input)
+  /// );
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `reader` - The reader of the Myanmar text.
+  /// * `w` - The writer to write to.
+  ///
+  /// # Returns
+  ///
+  /// The result of reading and writing. Input which is not valid UTF-8 is an
+  /// error.
+  pub fn convert_reader<R: io::BufRead, W: io::Write>(
+    mut self,
+    mut reader: R,
+    w: &mut W,
+  ) -> io::Result<()>
+  {
+    let mut line = String::new();
+    while reader.read_line(&mut line)? > 0
+    {
+      self.push_chunk(&line);
+      line.clear();
+      w.write_all(self.take_output().as_bytes())?;
+    }
+    w.write_all(self.finish().as_bytes())
+  }
+
+  /// Convert the complete part of the input and append it to the output.
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The complete part of the input.
+  fn convert(&mut self, input: &str)
+  {
+    convert_into(input, &self.options, &[], None, &mut self.joiner, |_| ());
+  }
+}
+
+/// Find the end of the part of the input that can be converted on its own.
+///
+/// The last syllable is always kept since following characters might belong
+/// to it. A split never happens in front of a consonant which ends up in the
+/// previous syllable, e.g. the kinzi `င်္` or a lone consonant at the end
+/// which might still get an asat or a virama. The split has to happen between
+/// two characters which are not zero-width characters, because their
+/// normalization depends on their neighbours, and not inside a `\r\n`.
+/// Whitespace is a safe split, so text with spaces between syllables is
+/// converted as it arrives. The split must not cut an unclosed `<<...>>`
+/// block, `{{...}}` escape (or the start of its opener) or a term of the user
+/// dictionary. With autofix, a look-alike character like ဥ in ဥ် is kept with
+/// the character before it since its correction depends on both of its
+/// neighbours.
+///
+/// Only the syllables from `from` on are searched, so the text is not split
+/// into syllables again on every chunk.
+///
+/// # Arguments
+///
+/// * `input` - The received text.
+/// * `from` - The start of a syllable to search from.
+/// * `options` - The conversion options.
+///
+/// # Returns
+///
+/// The byte position to split at, `0` if nothing can be converted yet, and
+/// the start of a syllable to search from with the next chunk.
+fn safe_end(
+  input: &str,
+  from: usize,
+  options: &ConvertOptions,
+) -> (usize, usize)
+{
+  let limit = [("<<", ">>"), ("{{", "}}")]
+    .into_iter()
+    .filter_map(|(open, close)| {
+      // the first opener after the last closer on the last line
+      let line = input.rfind('\n').map_or(0, |pos| pos + 1);
+      let closed = input[line ..]
+        .rfind(close)
+        .map_or(line, |pos| line + pos + close.len());
+      input[closed ..].find(open).map(|pos| closed + pos)
+    })
+    // the first character of an opener might be followed by the second one
+    .chain(input.ends_with(['<', '{']).then(|| input.len() - 1))
+    .min()
+    .unwrap_or(input.len());
+
+  let is_not_zero_width = |c: Option<char>| {
+    c.is_some_and(|c| !matches!(c, '\u{200B}' | '\u{200C}' | '\u{200D}'))
+  };
+
+  // nothing after an unclosed block can be split, it starts a new syllable
+  // itself
+  let scan_end = limit.max(from);
+  let mut segments = split_syllables(&input[from .. scan_end])
+    .into_iter()
+    .map(|(segment, start, len)| (segment, from + start, len))
+    .collect::<Vec<_>>();
+  if scan_end < input.len()
+  {
+    segments.push(("", scan_end, 0));
+  }
+  let last = segments.len().saturating_sub(1);
+
+  // a consonant followed by an asat or a virama belongs to the previous
+  // syllable (dot below might come before the asat, see `normalize`)
+  let merges_into_previous = |i: usize| {
+    let mut chars = segments[i].0.chars();
+    if !matches!(chars.next(), Some('က' ..= 'အ'))
+    {
+      return false;
+    }
+    match chars.find(|c| *c != '့')
+    {
+      Some(c) => matches!(c, '်' | '္'),
+      None => i == last,
+    }
+  };
+
+  let end = (1 ..= last)
+    .rev()
+    .filter(|i| !merges_into_previous(*i))
+    .map(|i| segments[i].1)
+    .filter(|start| *start <= limit)
+    .filter(|start| !options.dictionary.crosses(input, *start))
+    .filter(|start| {
+      !options.autofix || !input[*start ..].starts_with(is_confusable)
+    })
+    .find(|start| {
+      !input[.. *start].ends_with('\r')
+        && is_not_zero_width(input[.. *start].chars().last())
+        && is_not_zero_width(input[*start ..].chars().next())
+    })
+    .unwrap_or(0);
+
+  // the last syllables and an unclosed block might still change with the
+  // next chunk
+  let next_from = segments
+    .len()
+    .checked_sub(3)
+    .map_or(from, |i| segments[i].1)
+    .min(limit)
+    .max(end);
+  (end, next_from)
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::StreamConverter;
+  use crate::dictionary::UserDictionary;
+  use crate::{mlcts_from_myanmar_with_options, ConvertOptions};
+
+  #[test]
+  fn test_stream_converter()
+  {
+    let inputs = [
+      "မင်္ဂလာပါ ခင်ဗျာ။ နေကောင်းလား၊ ဗျာ။\r\nဟုတ်ကဲ့။",
+      "ကောင\u{1037}\u{103A}စကား \u{1025}\u{102E}း ၄င်း",
+      "မြန်\u{200B}မာ\u{200D}စာ\u{200B} ပေ",
+      "ပိဿာ <<abc>> တက္ကသိုလ်",
+      "{{ကင့်}} ကင့်{{x}}{{",
+      "ကဥ် ပု၀ ကေျျာင်းး ၇ာ ၁၀၇ ၀ယ်",
+      "<<a<<b >> ကား {{ က }} ခ < {ဂ",
+    ];
+
+    let prose = ConvertOptions {
+      capitalize_sentences: true,
+      western_punctuation: true,
+      ..Default::default()
+    };
+    let mut dictionary = UserDictionary::new();
+    dictionary
+      .protect("မင်္ဂလာပါ")
+      .substitute("တက္ကသိုလ်", "university")
+      .substitute("ကောင့်", "account");
+    let terms = ConvertOptions {
+      dictionary,
+      ..Default::default()
+    };
+    let autofix = ConvertOptions {
+      autofix: true,
+      ..Default::default()
+    };
+
+    for (input, options) in inputs.iter().flat_map(|i| {
+      [
+        (i, ConvertOptions::default()),
+        (i, prose.clone()),
+        (i, terms.clone()),
+        (i, autofix.clone()),
+      ]
+    })
+    {
+      let expected = mlcts_from_myanmar_with_options(input, &options);
+
+      // split the input at every character boundary
+      for (pos, _) in input.char_indices()
+      {
+        let mut converter = StreamConverter::new(options.clone());
+        converter.push_chunk(&input[.. pos]);
+        let mut output = converter.take_output();
+        converter.push_chunk(&input[pos ..]);
+        output.push_str(&converter.finish());
+        assert_eq!(output, expected, "split at {}", pos);
+      }
+
+      // one character at a time
+      let mut converter = StreamConverter::new(options.clone());
+      let mut output = String::new();
+      for c in input.chars()
+      {
+        converter.push_chunk(c.encode_utf8(&mut [0; 4]));
+        output.push_str(&converter.take_output());
+      }
+      output.push_str(&converter.finish());
+      assert_eq!(output, expected);
+    }
+  }
+
+  #[test]
+  fn test_stream_converter_spaced_syllables()
+  {
+    // syllables separated by spaces are converted as they arrive
+    let mut converter = StreamConverter::default();
+    let mut output = String::new();
+    for _ in 0 .. 1000
+    {
+      converter.push_chunk("ကား ");
+      output.push_str(&converter.take_output());
+    }
+    assert!(converter.pending.len() < "ကား ".len() * 2);
+    output.push_str(&converter.finish());
+    assert_eq!(output, crate::mlcts_from_myanmar(&"ကား ".repeat(1000)));
+  }
+}
@@ -0,0 +1,192 @@
+//! Subtitle (SRT and WebVTT) conversion.
+//!
+//! Only the cue text is converted. Cue numbers, timestamps, the `WEBVTT`
+//! header and other blocks such as `NOTE` or `STYLE` are copied as-is.
+
+use crate::mlcts_from_myanmar;
+use crate::normalize::strip_bom;
+
+/// Convert the cue text of an SRT or WebVTT subtitle file to MLCTS.
+/// Both formats are handled the same way: every non-empty line following a
+/// timing line (a line containing `-->`) up to the next blank line is treated
+/// as cue text. Inline cue tags like `<i>` or `<c.yellow>` are kept untouched.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::subtitle::mlcts_from_subtitles;
+///
+/// let input = "1\n00:00:01,000 --> 00:00:02,000\nမင်္ဂလာပါ\n";
+///
+/// assert_eq!(
+///   mlcts_from_subtitles(input, false),
+///   "1\n00:00:01,000 --> 00:00:02,000\nmangga. la pa\n"
+/// );
+/// assert_eq!(
+///   mlcts_from_subtitles(input, true),
+///   "1\n00:00:01,000 --> 00:00:02,000\nမင်္ဂလာပါ\nmangga. la pa\n"
+/// );
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The content of the subtitle file. A byte order mark at the start
+///   is removed.
+/// * `bilingual` - If the romanization should be appended under the original
+///   line instead of replacing it.
+///
+/// # Returns
+///
+/// The subtitle file content with romanized cue text.
+pub fn mlcts_from_subtitles(input: &str, bilingual: bool) -> String
+{
+  let mut output = String::with_capacity(input.len());
+  let mut in_cue = false;
+
+  for line in strip_bom(input).split_inclusive('\n')
+  {
+    let (text, line_ending) = split_line_ending(line);
+
+    if text.trim().is_empty()
+    {
+      // a blank line always ends the current block
+      in_cue = false;
+      output.push_str(line);
+      continue;
+    }
+
+    if text.contains("-->")
+    {
+      in_cue = true;
+      output.push_str(line);
+      continue;
+    }
+
+    if !in_cue
+    {
+      output.push_str(line);
+      continue;
+    }
+
+    if bilingual
+    {
+      // the last line might not have a line ending
+      let separator = if line_ending.is_empty()
+      {
+        "\n"
+      }
+      else
+      {
+        line_ending
+      };
+      output.push_str(text);
+      output.push_str(separator);
+    }
+    output.push_str(&mlcts_from_cue_text(text));
+    output.push_str(line_ending);
+  }
+
+  output
+}
+
+/// Split a line into its content and its line ending (`\n`, `\r\n` or empty
+/// for the last line).
+///
+/// # Arguments
+///
+/// * `line` - The line to split.
+///
+/// # Returns
+///
+/// The content and the line ending.
+fn split_line_ending(line: &str) -> (&str, &str)
+{
+  let content_len = line.trim_end_matches(['\r', '\n']).len();
+  line.split_at(content_len)
+}
+
+/// Convert a single line of cue text while keeping inline cue tags.
+///
+/// # Arguments
+///
+/// * `text` - The cue text to convert.
+///
+/// # Returns
+///
+/// The romanized cue text.
+fn mlcts_from_cue_text(text: &str) -> String
+{
+  let mut output = String::with_capacity(text.len());
+  let mut rest = text;
+
+  while let Some(tag_start) = rest.find('<')
+  {
+    let tag_len = match rest[tag_start ..].find('>')
+    {
+      Some(end) => end + 1,
+      // not a tag, convert the rest as a normal text
+      None => break,
+    };
+
+    output.push_str(&mlcts_from_myanmar(&rest[.. tag_start]));
+    output.push_str(&rest[tag_start .. tag_start + tag_len]);
+    rest = &rest[tag_start + tag_len ..];
+  }
+
+  output.push_str(&mlcts_from_myanmar(rest));
+  output
+}
+
+#[cfg(test)]
+mod tests
+{
+  #[test]
+  fn test_mlcts_from_subtitles()
+  {
+    let input = "1\r\n\
+      00:00:01,000 --> 00:00:02,500\r\n\
+      မင်္ဂလာပါ\r\n\
+      \r\n\
+      2\r\n\
+      00:00:03,000 --> 00:00:04,000\r\n\
+      တက္ကသိုလ်\r\n\
+      ပိဿာ\r\n";
+    let expected = "1\r\n\
+      00:00:01,000 --> 00:00:02,500\r\n\
+      mangga. la pa\r\n\
+      \r\n\
+      2\r\n\
+      00:00:03,000 --> 00:00:04,000\r\n\
+      takka. suil\r\n\
+      pissa\r\n";
+    assert_eq!(super::mlcts_from_subtitles(input, false), expected);
+    let input = format!("\u{FEFF}{}", input);
+    assert_eq!(super::mlcts_from_subtitles(&input, false), expected);
+
+    let input = "WEBVTT\n\
+      \n\
+      NOTE ပိဿာ\n\
+      \n\
+      intro\n\
+      00:01.000 --> 00:02.000 line:0\n\
+      <i>မင်္ဂလာပါ</i> <c.yellow>ပိဿာ</c>";
+    let expected = "WEBVTT\n\
+      \n\
+      NOTE ပိဿာ\n\
+      \n\
+      intro\n\
+      00:01.000 --> 00:02.000 line:0\n\
+      <i>mangga. la pa</i> <c.yellow>pissa</c>";
+    assert_eq!(super::mlcts_from_subtitles(input, false), expected);
+
+    let expected = "WEBVTT\n\
+      \n\
+      NOTE ပိဿာ\n\
+      \n\
+      intro\n\
+      00:01.000 --> 00:02.000 line:0\n\
+      <i>မင်္ဂလာပါ</i> <c.yellow>ပိဿာ</c>\n\
+      <i>mangga. la pa</i> <c.yellow>pissa</c>";
+    assert_eq!(super::mlcts_from_subtitles(input, true), expected);
+  }
+}
@@ -0,0 +1,265 @@
+//! Validation of the structure of Myanmar text.
+//!
+//! The validator only looks at the order and combination of the code points,
+//! it does not check if the syllables are real words.
+
+/// ္ (U+1039)
+const VIRAMA: char = '\u{1039}';
+
+/// Vowel sign combinations which are allowed on a single base.
+const VOWEL_SIGN_COMBINATIONS: &[&str] = &["ော", "ေါ", "ို"];
+
+/// Represents the kind of a structural problem in Myanmar text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ViolationKind
+{
+  /// Two vowel signs which cannot be combined on one base, e.g. ကိူ.
+  TwoVowelSigns,
+  /// A medial diacritic typed after a vowel sign, e.g. ကေျ instead of ကျေ.
+  MedialAfterVowelSign,
+  /// A stack sign (္) which is not followed by a consonant.
+  DanglingStackSign,
+  /// A dependent sign (vowel sign, medial, tone mark, etc.) with no base
+  /// consonant or vowel in front of it.
+  MissingBase,
+  /// The same sign typed twice in a row, e.g. ကား း.
+  DuplicatedSign,
+}
+
+/// Represents how serious a violation is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[non_exhaustive]
+pub enum Severity
+{
+  /// The text is rendered the same but encoded differently, e.g. a
+  /// duplicated sign which is drawn on top of itself.
+  Warning,
+  /// The text is structurally invalid.
+  Error,
+}
+
+/// Represents a structural problem found in Myanmar text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation
+{
+  /// The kind of the violation.
+  pub kind: ViolationKind,
+  /// How serious the violation is.
+  pub severity: Severity,
+  /// The start position of the offending character in the input.
+  pub start: usize,
+  /// The length of the offending character in the input.
+  pub len: usize,
+}
+
+impl Violation
+{
+  /// Creates a new violation. The severity is decided by the kind.
+  ///
+  /// # Arguments
+  ///
+  /// * `kind` - The kind of the violation.
+  /// * `start` - The start position of the offending character.
+  /// * `len` - The length of the offending character.
+  ///
+  /// # Returns
+  ///
+  /// A new violation.
+  pub fn new(kind: ViolationKind, start: usize, len: usize) -> Self
+  {
+    let severity = match kind
+    {
+      ViolationKind::DuplicatedSign => Severity::Warning,
+      _ => Severity::Error,
+    };
+    Self {
+      kind,
+      severity,
+      start,
+      len,
+    }
+  }
+}
+
+/// Find structurally invalid sequences in Myanmar text.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_generator::validate::{validate_myanmar, ViolationKind};
+///
+/// assert!(validate_myanmar("မင်္ဂလာပါ").is_empty());
+///
+/// let violations = validate_myanmar("ကေျာင်း");
+/// assert_eq!(violations.len(), 1);
+/// assert_eq!(violations[0].kind, ViolationKind::MedialAfterVowelSign);
+/// assert_eq!(violations[0].start, 6);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The Myanmar text to validate.
+///
+/// # Returns
+///
+/// Violations in the order they appear in the input.
+pub fn validate_myanmar(input: &str) -> Vec<Violation>
+{
+  let mut violations = Vec::new();
+  let mut has_base = false;
+  // vowel signs on the current base
+  let mut vowel_signs = String::new();
+  let mut prev = None;
+  let mut cursor = input.char_indices().peekable();
+
+  while let Some((pos, current)) = cursor.next()
+  {
+    let len = current.len_utf8();
+    let mut report = |kind| violations.push(Violation::new(kind, pos, len));
+
+    if is_base(current)
+    {
+      has_base = true;
+      vowel_signs.clear();
+    }
+    else if is_dependent_sign(current)
+    {
+      if !has_base
+      {
+        report(ViolationKind::MissingBase);
+      }
+      else if prev == Some(current)
+      {
+        report(ViolationKind::DuplicatedSign);
+      }
+      else if is_vowel_sign(current)
+      {
+        vowel_signs.push(current);
+        let valid = vowel_signs.chars().count() == 1
+          || VOWEL_SIGN_COMBINATIONS.contains(&vowel_signs.as_str());
+        if !valid
+        {
+          report(ViolationKind::TwoVowelSigns);
+        }
+      }
+      else if is_medial(current) && !vowel_signs.is_empty()
+      {
+        report(ViolationKind::MedialAfterVowelSign);
+      }
+
+      if current == VIRAMA
+        && !cursor.peek().is_some_and(|(_, c)| matches!(c, 'က' ..= 'အ'))
+      {
+        report(ViolationKind::DanglingStackSign);
+      }
+    }
+    else
+    {
+      has_base = false;
+      vowel_signs.clear();
+    }
+
+    prev = Some(current);
+  }
+
+  violations
+}
+
+/// Check if a character can carry dependent signs: consonants, independent
+/// vowels and the great sa.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is a base, `false` otherwise.
+pub(crate) fn is_base(c: char) -> bool
+{
+  matches!(c, 'က' ..= 'ဪ' | 'ဿ' | '၌' ..= '၏')
+}
+
+/// Check if a character is a dependent sign: vowel signs, anusvara, tone
+/// marks, asat, stack sign and medials.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is a dependent sign, `false` otherwise.
+pub(crate) fn is_dependent_sign(c: char) -> bool
+{
+  matches!(c, '\u{102B}' ..= '\u{103E}')
+}
+
+/// Check if a character is a dependent vowel sign.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is a vowel sign, `false` otherwise.
+pub(crate) fn is_vowel_sign(c: char) -> bool
+{
+  matches!(c, '\u{102B}' ..= '\u{1032}')
+}
+
+/// Check if a character is a medial diacritic.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is a medial, `false` otherwise.
+pub(crate) fn is_medial(c: char) -> bool
+{
+  matches!(c, '\u{103B}' ..= '\u{103E}')
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::{Severity, ViolationKind};
+
+  #[test]
+  fn test_validate_myanmar()
+  {
+    // sample text is also borrowed from https://github.com/ye-kyaw-thu/myWord
+    let input = "ကျွန်တော်ကသုတေသနသမားပါ။\n\
+    နေ့ရောညရောမြန်မာစာနဲ့ကွန်ပျူတာနဲ့ပဲအလုပ် များ ပါ တယ်\n\
+    ပိဿာ သန္နိဋ္ဌာန် ဦး ဪ ၎င်း ယောက်ျား";
+    assert_eq!(super::validate_myanmar(input), vec![]);
+
+    let cases = [
+      ("ကိူ", ViolationKind::TwoVowelSigns, 6),
+      ("ကေျာ", ViolationKind::MedialAfterVowelSign, 6),
+      ("က္", ViolationKind::DanglingStackSign, 3),
+      ("တက္ ကသိုလ်", ViolationKind::DanglingStackSign, 6),
+      ("း", ViolationKind::MissingBase, 0),
+      ("ပါ ့", ViolationKind::MissingBase, 7),
+      ("ကားး", ViolationKind::DuplicatedSign, 9),
+      ("ကိိ", ViolationKind::DuplicatedSign, 6),
+    ];
+    for (input, kind, start) in cases
+    {
+      let violations = super::validate_myanmar(input);
+      assert_eq!(violations.len(), 1, "{}", input);
+      assert_eq!(violations[0].kind, kind, "{}", input);
+      assert_eq!(violations[0].start, start, "{}", input);
+      assert_eq!(violations[0].len, 3, "{}", input);
+    }
+
+    let violations = super::validate_myanmar("ကားး");
+    assert_eq!(violations[0].severity, Severity::Warning);
+    let violations = super::validate_myanmar("ကိူ");
+    assert_eq!(violations[0].severity, Severity::Error);
+  }
+}
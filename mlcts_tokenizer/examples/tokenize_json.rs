@@ -0,0 +1,21 @@
+//! Tokenize MLCTS text from the standard input and print the tokens of every
+//! line as a JSON array.
+//!
+//! ```sh
+//! echo "mangga. la pa" | cargo run --example tokenize_json
+//! ```
+
+use std::io::{self, BufRead, Write};
+
+fn main() -> io::Result<()>
+{
+  let mut stdout = io::stdout().lock();
+  for line in io::stdin().lock().lines()
+  {
+    let line = line?;
+    let tokens = mlcts_tokenizer::tokenize(&line).collect::<Vec<_>>();
+    serde_json::to_writer(&mut stdout, &tokens)?;
+    writeln!(stdout)?;
+  }
+  Ok(())
+}
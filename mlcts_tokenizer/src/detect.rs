@@ -0,0 +1,286 @@
+//! Detection of the script of the input.
+//!
+//! The input is split into spans of Myanmar letters, Latin letters and other
+//! letters. Whitespace, digits and ASCII punctuation belong to the span they
+//! appear in. Myanmar spans are Unicode or Zawgyi depending on the
+//! characters and orderings which only appear in Zawgyi text. Latin spans
+//! are romanized Burmese if their words can be tokenized into syllables.
+
+use crate::{tokenize, TokenKind};
+
+/// Represents the script of a span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Script
+{
+  /// Myanmar text in Unicode.
+  Myanmar,
+  /// Myanmar text in the Zawgyi font encoding.
+  Zawgyi,
+  /// Burmese romanized in MLCTS.
+  Romanized,
+  /// Anything else, e.g. English.
+  Other,
+}
+
+/// Represents a part of the input in a single script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptSpan
+{
+  /// The script of the span.
+  pub script: Script,
+  /// The start position of the span in the input.
+  pub start: usize,
+  /// The length of the span in the input.
+  pub len: usize,
+}
+
+/// Represents the scripts of the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptReport
+{
+  /// The script of most of the letters.
+  pub script: Script,
+  /// The spans of the input in order.
+  pub spans: Vec<ScriptSpan>,
+}
+
+/// Letter classes of characters which start a span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class
+{
+  Myanmar,
+  Latin,
+  Other,
+}
+
+/// Detect the scripts of the input.
+///
+/// # Examples
+///
+/// ```
+/// use mlcts_tokenizer::detect::{detect, Script};
+///
+/// assert_eq!(detect("မင်္ဂလာပါ").script, Script::Myanmar);
+/// assert_eq!(detect("mangga. la pa").script, Script::Romanized);
+/// assert_eq!(detect("hello world").script, Script::Other);
+///
+/// let report = detect("ေနေကာင္းလား");
+/// assert_eq!(report.script, Script::Zawgyi);
+/// ```
+///
+/// # Arguments
+///
+/// * `input` - The input to detect.
+///
+/// # Returns
+///
+/// The scripts of the input. An input without letters is a single span of
+/// [`Script::Other`].
+pub fn detect(input: &str) -> ScriptReport
+{
+  let mut spans = Vec::<(Class, usize, usize)>::new();
+  for (pos, c) in input.char_indices()
+  {
+    let class = match c
+    {
+      '\u{1000}' ..= '\u{109F}' | '\u{AA60}' ..= '\u{AA7F}' =>
+      {
+        Some(Class::Myanmar)
+      }
+      c if c.is_ascii_alphabetic() => Some(Class::Latin),
+      c if c.is_whitespace() || c.is_ascii() || c.is_numeric() => None,
+      _ => Some(Class::Other),
+    };
+
+    match (spans.last_mut(), class)
+    {
+      (Some((_, _, len)), None) => *len += c.len_utf8(),
+      (Some((current, _, len)), Some(class)) if *current == class =>
+      {
+        *len += c.len_utf8()
+      }
+      (_, Some(class)) => spans.push((class, pos, c.len_utf8())),
+      (None, None) => spans.push((Class::Other, pos, c.len_utf8())),
+    }
+  }
+
+  // leading whitespace, digits, etc. belong to the first span with letters
+  if spans.len() > 1
+    && spans[0].0 == Class::Other
+    && !has_letters(&input[.. spans[0].2])
+  {
+    let (_, _, len) = spans.remove(0);
+    spans[0].1 = 0;
+    spans[0].2 += len;
+  }
+
+  let spans = spans
+    .into_iter()
+    .map(|(class, start, len)| {
+      let text = &input[start .. start + len];
+      let script = match class
+      {
+        Class::Myanmar if is_zawgyi(text) => Script::Zawgyi,
+        Class::Myanmar => Script::Myanmar,
+        Class::Latin if is_romanized(text) => Script::Romanized,
+        _ => Script::Other,
+      };
+      ScriptSpan { script, start, len }
+    })
+    .collect::<Vec<_>>();
+
+  let letters = |script: Script| {
+    spans
+      .iter()
+      .filter(|span| span.script == script)
+      .map(|span| {
+        input[span.start .. span.start + span.len]
+          .chars()
+          .filter(|c| !c.is_whitespace() && !c.is_ascii_punctuation())
+          .count()
+      })
+      .sum::<usize>()
+  };
+  let script = [Script::Myanmar, Script::Zawgyi, Script::Romanized]
+    .into_iter()
+    .filter(|script| letters(*script) > 0)
+    .max_by_key(|script| letters(*script))
+    .unwrap_or(Script::Other);
+  let script = if letters(Script::Other) > letters(script)
+  {
+    Script::Other
+  }
+  else
+  {
+    script
+  };
+
+  ScriptReport { script, spans }
+}
+
+/// Check if the text has any letters.
+///
+/// # Arguments
+///
+/// * `text` - The text to check.
+///
+/// # Returns
+///
+/// `true` if the text has any letters, `false` otherwise.
+fn has_letters(text: &str) -> bool
+{
+  text.chars().any(char::is_alphabetic)
+}
+
+/// Check if Myanmar text is encoded in Zawgyi. These only appear in Zawgyi:
+///
+/// * the stacked and variant glyphs (U+1060 to U+1097, these are letters of
+///   other languages in Unicode)
+/// * ေ or the Zawgyi ya-yit (U+103B) in front of a consonant, unless it follows
+///   a consonant or a medial
+/// * U+1039 used as the asat, i.e. not followed by a consonant
+///
+/// # Arguments
+///
+/// * `text` - The Myanmar text.
+///
+/// # Returns
+///
+/// `true` if at least a tenth of the Myanmar characters are Zawgyi
+/// indicators, `false` otherwise.
+fn is_zawgyi(text: &str) -> bool
+{
+  let is_consonant = |c: Option<&char>| matches!(c, Some('က' ..= 'အ'));
+  let is_medial =
+    |c: Option<&char>| matches!(c, Some('\u{103B}' ..= '\u{103E}'));
+  let chars = text.chars().collect::<Vec<_>>();
+  let myanmar = chars
+    .iter()
+    .filter(|c| matches!(c, '\u{1000}' ..= '\u{109F}'))
+    .count();
+
+  let indicators = (0 .. chars.len())
+    .filter(|&i| {
+      let prev = i.checked_sub(1).and_then(|i| chars.get(i));
+      let next = chars.get(i + 1);
+      match chars[i]
+      {
+        '\u{1060}' ..= '\u{1097}' => true,
+        'ေ' | '\u{103B}' =>
+        {
+          is_consonant(next) && !is_consonant(prev) && !is_medial(prev)
+        }
+        '\u{1039}' => !is_consonant(next),
+        _ => false,
+      }
+    })
+    .count();
+
+  myanmar > 0 && indicators * 10 >= myanmar
+}
+
+/// Check if Latin text is romanized Burmese, i.e. most of its words are
+/// tokenized into syllables.
+///
+/// # Arguments
+///
+/// * `text` - The Latin text.
+///
+/// # Returns
+///
+/// `true` if at least three quarters of the words are syllables, `false`
+/// otherwise.
+fn is_romanized(text: &str) -> bool
+{
+  let words = text
+    .split(|c: char| !c.is_ascii_alphabetic() && !matches!(c, '.' | ':'))
+    .filter(|word| word.contains(|c: char| c.is_ascii_alphabetic()))
+    .collect::<Vec<_>>();
+  let syllables = words
+    .iter()
+    .filter(|word| {
+      tokenize(&word.to_ascii_lowercase())
+        .all(|token| matches!(token.kind, TokenKind::Syllable(_)))
+    })
+    .count();
+
+  !words.is_empty() && syllables * 4 >= words.len() * 3
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::{detect, Script, ScriptSpan};
+
+  #[test]
+  fn test_detect()
+  {
+    assert_eq!(detect("").script, Script::Other);
+    assert_eq!(detect("").spans, vec![]);
+    assert_eq!(detect(" 123 ").script, Script::Other);
+    assert_eq!(detect("ကျွန်တော်က ကျောင်းသားပါ။").script, Script::Myanmar);
+    assert_eq!(detect("မြေကြီး").script, Script::Myanmar);
+    assert_eq!(detect("ရွေးကောက်ပွဲ").script, Script::Myanmar);
+    assert_eq!(detect("ျမန္မာစာ").script, Script::Zawgyi);
+    assert_eq!(detect("Mran ma ca").script, Script::Romanized);
+
+    // letters are counted, a Myanmar syllable has fewer of them
+    let input = " မင်္ဂလာပါ mangga. la pa, hello! 赵露思";
+    let report = detect(input);
+    assert_eq!(report.script, Script::Romanized);
+    assert_eq!(
+      report.spans.iter().map(|s| s.script).collect::<Vec<_>>(),
+      vec![Script::Myanmar, Script::Romanized, Script::Other]
+    );
+    assert_eq!(
+      report.spans[0],
+      ScriptSpan {
+        script: Script::Myanmar,
+        start: 0,
+        len: " မင်္ဂလာပါ ".len(),
+      }
+    );
+    let end = report.spans.last().map(|s| s.start + s.len);
+    assert_eq!(end, Some(input.len()));
+  }
+}
@@ -1,14 +1,28 @@
+//! # mlcts_tokenizer
+//!
+//! This crate tokenizes MLCTS text into syllables.
+//!
+//! # API stability
+//!
+//! The [`prelude`] ([`tokenize`], [`Token`], [`TokenKind`] and the syllable
+//! types) is stable. The [`Tokenizer`] itself and [`detect`] are experimental
+//! and may change in any release.
+
 use std::str::Chars;
 
+pub mod detect;
+pub mod prelude;
+
 // re-export mlcts_core crate
 pub use mlcts_core;
 use mlcts_core::*;
 
+#[doc(hidden)]
 pub const EOF_CHAR: char = '\0';
 
 /// Represents the kind of a token generated by the tokenizer.
 /// Token kind can be a syllable or other types of tokens.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
 pub enum TokenKind
 {
   /// An unknown token.
@@ -21,34 +35,56 @@ pub enum TokenKind
   EndOfInput,
 }
 
-/// Represents a token generated by the tokenizer.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Token
+/// Represents a token generated by the tokenizer. The token borrows its text
+/// from the input. Tokens can be serialized, e.g. to JSON:
+///
+/// ```
+/// let tokens = mlcts_tokenizer::tokenize("ma").collect::<Vec<_>>();
+/// let json = serde_json::to_string(&tokens[0]).unwrap();
+///
+/// assert!(json.starts_with(r#"{"kind":{"Syllable":{"consonant":"#));
+/// assert!(
+///   json.ends_with(r#""text":"ma","start":0,"len":2,"normalized":false}"#)
+/// );
+/// ```
+#[derive(serde::Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct Token<'i>
 {
   /// The kind of the token.
   pub kind: TokenKind,
-  /// The start position of the token in the input.
+  /// The text of the token in the input.
+  pub text: &'i str,
+  /// The start position of the token in the input, in bytes.
   pub start: usize,
-  /// The length of the token in the input.
+  /// The length of the token in the input, in bytes.
   pub len: usize,
+  /// If the syllable is spelled with a variant accepted in lenient mode
+  /// (e.g. `aw` for `au`) and is mapped to the canonical syllable.
+  pub normalized: bool,
 }
 
-impl Token
+impl<'i> Token<'i>
 {
-  /// Creates a new token with the given kind, start position, and length.
+  /// Creates a new token with the given kind, text, and start position.
   ///
   /// # Arguments
   ///
   /// * `kind` - The kind of the token.
-  /// * `start` - The start position of the token in the input.
-  /// * `len` - The length of the token in the input.
+  /// * `text` - The text of the token in the input.
+  /// * `start` - The start position of the token in the input, in bytes.
   ///
   /// # Returns
   ///
-  /// A new token with the given kind, start position, and length.
-  pub fn new(kind: TokenKind, start: usize, len: usize) -> Self
+  /// A new token with the given kind, text, and start position.
+  pub fn new(kind: TokenKind, text: &'i str, start: usize) -> Self
   {
-    Self { kind, start, len }
+    Self {
+      kind,
+      text,
+      start,
+      len: text.len(),
+      normalized: false,
+    }
   }
 }
 
@@ -61,9 +97,38 @@ impl Token
 /// # Returns
 ///
 /// An iterator that produces tokens from the input string.
-pub fn tokenize(input: &str) -> impl Iterator<Item = Token> + '_
+pub fn tokenize(input: &str) -> impl Iterator<Item = Token<'_>> + '_
+{
+  tokens(Tokenizer::new(input))
+}
+
+/// Creates an iterator that produces tokens from the input string in lenient
+/// mode. See [`Tokenizer::lenient`].
+///
+/// # Arguments
+///
+/// * `input` - The input string to tokenize.
+///
+/// # Returns
+///
+/// An iterator that produces tokens from the input string.
+pub fn tokenize_lenient(input: &str) -> impl Iterator<Item = Token<'_>> + '_
+{
+  tokens(Tokenizer::lenient(input))
+}
+
+/// Creates an iterator that produces tokens from the tokenizer.
+///
+/// # Arguments
+///
+/// * `cursor` - The tokenizer.
+///
+/// # Returns
+///
+/// An iterator that produces tokens until the end of the input.
+fn tokens<'i>(mut cursor: Tokenizer<'i>)
+  -> impl Iterator<Item = Token<'i>> + 'i
 {
-  let mut cursor = Tokenizer::new(input);
   std::iter::from_fn(move || {
     let token = cursor.next_token();
     if token.kind != TokenKind::EndOfInput
@@ -89,6 +154,10 @@ pub struct Tokenizer<'i>
   start: usize,
   /// Length remaining in the input.
   len_remaining: usize,
+  /// If spelling variants are accepted.
+  lenient: bool,
+  /// If a spelling variant is mapped in the current token.
+  normalized: bool,
 }
 
 /// Check if a character is a valid character used in MLCTS.
@@ -177,9 +246,77 @@ impl<'i> Tokenizer<'i>
       input: input.chars(),
       start: 0,
       len_remaining: input.len(),
+      lenient: false,
+      normalized: false,
+    }
+  }
+
+  /// Creates a new tokenizer which also accepts common deviations from
+  /// MLCTS and maps them to the canonical syllables:
+  ///
+  /// * `aw` for `au` (e.g. `kyaw` for `kyau`)
+  /// * `o` for `ui` (e.g. `mo:` for `mui:`)
+  ///
+  /// Tokens with a mapped variant are flagged with [`Token::normalized`].
+  /// Tone marks are optional in both modes.
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use mlcts_tokenizer::mlcts_core::*;
+  /// use mlcts_tokenizer::{TokenKind, Tokenizer};
+  ///
+  /// let mut tokenizer = Tokenizer::lenient("kyaw");
+  /// let token = tokenizer.next_token();
+  ///
+  /// let expected = syllable!(consonant!(K, Y), vowel!(Au));
+  /// assert_eq!(token.kind, TokenKind::Syllable(expected));
+  /// assert!(token.normalized);
+  /// ```
+  ///
+  /// # Arguments
+  ///
+  /// * `input` - The input string to tokenize.
+  ///
+  /// # Returns
+  ///
+  /// A new tokenizer in lenient mode.
+  pub fn lenient(input: &'i str) -> Self
+  {
+    Self {
+      lenient: true,
+      ..Self::new(input)
     }
   }
 
+  /// Creates a tokenizer for the rest of the input in the same mode, to try
+  /// out a possible tokenization.
+  ///
+  /// # Returns
+  ///
+  /// A new tokenizer starting at the current position.
+  fn fork(&self) -> Self
+  {
+    Self {
+      lenient: self.lenient,
+      ..Self::new(self.input.as_str())
+    }
+  }
+
+  /// Check if a character starts a vowel.
+  ///
+  /// # Arguments
+  ///
+  /// * `c` - The character to check.
+  ///
+  /// # Returns
+  ///
+  /// `true` if the character starts a vowel, `false` otherwise.
+  fn is_vowel_char(&self, c: char) -> bool
+  {
+    matches!(c, 'a' | 'i' | 'u' | 'e') || (self.lenient && c == 'o')
+  }
+
   /// Get the amount of consumed input.
   ///
   /// # Returns
@@ -316,6 +453,7 @@ impl<'i> Tokenizer<'i>
   {
     while !is_whitespace(self.peek())
       && !is_valid_mlcts_char(self.peek())
+      && !self.is_vowel_char(self.peek())
       && !self.is_eof()
     {
       self.advance();
@@ -357,11 +495,12 @@ impl<'i> Tokenizer<'i>
   /// # Returns
   ///
   /// The selected syllable.
-  fn select_vowel(&self, syllables: Vec<(Syllable, Syllable)>) -> Syllable
+  fn select_vowel(&self, syllables: [(Syllable, Syllable); 2]) -> Syllable
   {
     // this will always return the first syllable.
     // we need to implement dictionary checkings to select the correct syllable.
-    return syllables[0].0.clone();
+    let [(first, _), _] = syllables;
+    first
   }
 
   /// In certain cases like "lapa", tokenization can be ambiguous.
@@ -390,7 +529,7 @@ impl<'i> Tokenizer<'i>
   {
     let next_char_followed_virama = self.peek_nth(virama_len);
 
-    if !matches!(next_char_followed_virama, 'a' | 'i' | 'u' | 'e')
+    if !self.is_vowel_char(next_char_followed_virama)
     {
       // if there's no vowel after the virama consonant, we don't need to check
       // for possible ambiguity.
@@ -401,12 +540,11 @@ impl<'i> Tokenizer<'i>
 
     // we will try to generate the following combinations:
     // 1. (current consonant + current vowel + virama) + (next vowel)
-    // 2. (current consonant + current vowel) + (virama + next consonant + next vowel)
+    // 2. (current consonant + current vowel) + (virama + next consonant + next
+    //    vowel)
     // and select the best one.
-    let current_input_str = self.input.as_str();
-
     // remove the virama consonant and the vowel after it.
-    let mut cloned_tokenizer = Tokenizer::new(current_input_str);
+    let mut cloned_tokenizer = self.fork();
     cloned_tokenizer.advance_n(virama_len + 1);
     let candidate_vowel = cloned_tokenizer.parse_vowel(
       next_char_followed_virama,
@@ -420,14 +558,14 @@ impl<'i> Tokenizer<'i>
       Vowel::new(original_vowel, Some(possible_virama), None)
     );
     // assume that we consume the virama consonant.
-    let mut cloned_tokenizer_3 = Tokenizer::new(current_input_str);
+    let mut cloned_tokenizer_3 = self.fork();
     cloned_tokenizer_3.advance_n(virama_len);
     let following_syllable_2_vowel =
       cloned_tokenizer_3.parse_vowel(next_char_followed_virama, consonant!(A));
     let following_syllable_2 =
       syllable!(consonant!(A), following_syllable_2_vowel);
 
-    let selected_syllable = self.select_vowel(vec![
+    let selected_syllable = self.select_vowel([
       (base_syllable.clone(), following_syllable_2),
       (
         syllable!(consonant_part, Vowel::simple(original_vowel)),
@@ -627,6 +765,13 @@ impl<'i> Tokenizer<'i>
         self.advance();
         self.parse_virama_and_tone(BasicVowel::Au, consonant_part)
       }
+      ('a', 'w') if self.lenient && !self.is_vowel_char(self.peek_next()) =>
+      {
+        // consume 'w'
+        self.advance();
+        self.normalized = true;
+        self.parse_virama_and_tone(BasicVowel::Au, consonant_part)
+      }
       ('a', _) => self.parse_virama_and_tone(BasicVowel::A, consonant_part),
       ('i', _) => self.parse_virama_and_tone(BasicVowel::I, consonant_part),
       ('u', 'i') =>
@@ -637,6 +782,11 @@ impl<'i> Tokenizer<'i>
       }
       ('u', _) => self.parse_virama_and_tone(BasicVowel::U, consonant_part),
       ('e', _) => self.parse_virama_and_tone(BasicVowel::E, consonant_part),
+      ('o', _) if self.lenient =>
+      {
+        self.normalized = true;
+        self.parse_virama_and_tone(BasicVowel::Ui, consonant_part)
+      }
       _ => unreachable!(),
     }
   }
@@ -936,7 +1086,7 @@ impl<'i> Tokenizer<'i>
       consonant
     };
 
-    let vowel = if matches!(self.peek(), 'a' | 'i' | 'u' | 'e')
+    let vowel = if self.is_vowel_char(self.peek())
     {
       let curr = self.advance().unwrap();
       Some(self.parse_vowel(curr, consonant))
@@ -962,12 +1112,13 @@ impl<'i> Tokenizer<'i>
   /// # Returns
   ///
   /// The next token from the input.
-  pub fn next_token(&mut self) -> Token
+  pub fn next_token(&mut self) -> Token<'i>
   {
+    let rest = self.input.as_str();
     let first_char = match self.advance()
     {
       Some(c) => c,
-      None => return Token::new(TokenKind::EndOfInput, 0, 0),
+      None => return Token::new(TokenKind::EndOfInput, "", self.start),
     };
 
     let token_kind = match first_char
@@ -975,11 +1126,14 @@ impl<'i> Tokenizer<'i>
       'k' | 'h' | 'g' | 'n' | 'c' | 'j' | 't' | 'd' | 'p' | 'b' | 'm' | 'y'
       | 'r' | 'l' | 'w' | 's' | 'a' => self.parse_consonant(first_char),
       'i' | 'u' | 'e' => self.parse_vowel_syllable(first_char),
+      'o' if self.lenient => self.parse_vowel_syllable(first_char),
       c if is_whitespace(c) => self.parse_whitespace(),
       _ => self.parse_unknown(),
     };
 
-    let token = Token::new(token_kind, self.start, self.consumed_len());
+    let text = &rest[.. self.consumed_len()];
+    let mut token = Token::new(token_kind, text, self.start);
+    token.normalized = std::mem::take(&mut self.normalized);
     self.reset_consumed_len();
     token
   }
@@ -997,4 +1151,67 @@ mod tests
     println!("{:?}", tokenizer.next_token());
     println!("{:?}", tokenizer.next_token());
   }
+
+  #[test]
+  fn test_token_text()
+  {
+    let input = "min-ga.la pa ";
+    let tokens = tokenize(input).collect::<Vec<_>>();
+    assert_eq!(
+      tokens.iter().map(|t| t.text).collect::<Vec<_>>(),
+      vec!["min", "-", "ga.", "la", " ", "pa", " "]
+    );
+    for token in &tokens
+    {
+      assert_eq!(&input[token.start .. token.start + token.len], token.text);
+    }
+
+    // positions are in bytes
+    let tokens = tokenize("ကka").collect::<Vec<_>>();
+    assert_eq!(tokens[1].text, "ka");
+    assert_eq!(tokens[1].start, "က".len());
+  }
+
+  #[test]
+  fn test_lenient_tokenizer()
+  {
+    let syllables = |tokens: Vec<Token>| {
+      tokens
+        .into_iter()
+        .filter_map(|t| match t.kind
+        {
+          TokenKind::Syllable(s) => Some((s.to_mlcts(), t.normalized)),
+          _ => None,
+        })
+        .collect::<Vec<_>>()
+    };
+
+    let tokens = tokenize_lenient("Kyaw Mo: kyau ngo kawa").collect();
+    assert_eq!(
+      syllables(tokens),
+      vec![
+        ("kyau".to_string(), true),
+        ("mui:".to_string(), true),
+        ("kyau".to_string(), false),
+        ("ngui".to_string(), true),
+        ("ka".to_string(), false),
+        ("wa".to_string(), false),
+      ]
+    );
+
+    // "o" is ambiguous after a virama like the other vowels
+    let lenient = tokenize_lenient("mano").map(|t| t.kind);
+    let strict = tokenize("manui").map(|t| t.kind);
+    assert!(lenient.eq(strict));
+
+    // strict mode is unchanged
+    let kinds = tokenize("kyaw").map(|t| t.kind).collect::<Vec<_>>();
+    assert_eq!(
+      kinds,
+      vec![
+        TokenKind::Syllable(syllable!(consonant!(K, Y), vowel!(A))),
+        TokenKind::Unknown
+      ]
+    );
+  }
 }
@@ -0,0 +1,13 @@
+//! The stable core of the crate.
+//!
+//! ```
+//! use mlcts_tokenizer::prelude::*;
+//!
+//! let kinds: Vec<_> = tokenize("mran ma").map(|t| t.kind).collect();
+//! assert!(matches!(kinds[0], TokenKind::Syllable(_)));
+//! assert_eq!(kinds[1], TokenKind::Whitespace);
+//! ```
+
+pub use mlcts_core::{BasicConsonant, BasicVowel, Consonant, MedialDiacritic, Syllable, Tone, Virama, Vowel};
+
+pub use crate::{tokenize, tokenize_lenient, Token, TokenKind};
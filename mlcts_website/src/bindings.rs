@@ -0,0 +1,73 @@
+use mlcts_generator::stream::StreamConverter;
+use wasm_bindgen::prelude::*;
+
+/// Converts Myanmar text to MLCTS chunk by chunk from JavaScript, e.g. to
+/// convert a huge paste across animation frames without freezing the page.
+///
+/// ```js
+/// const converter = new StreamConverter();
+/// converter.push_chunk("မင်္ဂလာပ");
+/// converter.push_chunk("ါ");
+/// const output = converter.take_output() + converter.finish();
+/// ```
+#[wasm_bindgen(js_name = StreamConverter)]
+pub struct JsStreamConverter
+{
+  converter: StreamConverter,
+}
+
+#[wasm_bindgen(js_class = StreamConverter)]
+impl JsStreamConverter
+{
+  /// Create a new stream converter with the default options.
+  ///
+  /// # Returns
+  ///
+  /// A new stream converter.
+  #[wasm_bindgen(constructor)]
+  pub fn new() -> Self
+  {
+    Self {
+      converter: StreamConverter::default(),
+    }
+  }
+
+  /// Push a chunk of Myanmar text, see [`StreamConverter::push_chunk`].
+  ///
+  /// # Arguments
+  ///
+  /// * `text` - The next chunk of the input.
+  pub fn push_chunk(&mut self, text: &str)
+  {
+    self.converter.push_chunk(text);
+  }
+
+  /// Take the converted text so far, see [`StreamConverter::take_output`].
+  ///
+  /// # Returns
+  ///
+  /// MLCTS text converted since the last call.
+  pub fn take_output(&mut self) -> String
+  {
+    self.converter.take_output()
+  }
+
+  /// Convert the remaining text, see [`StreamConverter::finish`]. The
+  /// converter can't be used afterwards.
+  ///
+  /// # Returns
+  ///
+  /// MLCTS text which is not taken yet.
+  pub fn finish(self) -> String
+  {
+    self.converter.finish()
+  }
+}
+
+impl Default for JsStreamConverter
+{
+  fn default() -> Self
+  {
+    Self::new()
+  }
+}
@@ -0,0 +1,63 @@
+use leptos::*;
+use mlcts_generator::stream::StreamConverter;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::Promise;
+
+/// The number of bytes converted before the browser may render again.
+const CHUNK_SIZE: usize = 16 * 1024;
+
+/// Convert Myanmar text to MLCTS chunk by chunk, letting the browser render
+/// between the chunks so huge inputs don't freeze the page.
+///
+/// # Arguments
+///
+/// * `text` - The Myanmar text.
+/// * `on_chunk` - Called with the converted part of the text, from 0 to 1,
+///   after each chunk. The conversion is cancelled if it returns `false`.
+///
+/// # Returns
+///
+/// The MLCTS text or `None` if the conversion is cancelled.
+pub async fn convert_in_chunks(
+  text: &str,
+  mut on_chunk: impl FnMut(f64) -> bool,
+) -> Option<String>
+{
+  let mut converter = StreamConverter::default();
+  let mut output = String::new();
+  let mut start = 0;
+  while start < text.len()
+  {
+    if start > 0
+    {
+      next_frame().await;
+    }
+
+    let mut end = (start + CHUNK_SIZE).min(text.len());
+    while !text.is_char_boundary(end)
+    {
+      end += 1;
+    }
+
+    converter.push_chunk(&text[start .. end]);
+    output.push_str(&converter.take_output());
+    start = end;
+
+    if !on_chunk(start as f64 / text.len() as f64)
+    {
+      return None;
+    }
+  }
+
+  output.push_str(&converter.finish());
+  Some(output)
+}
+
+/// Wait until the browser had the chance to render.
+async fn next_frame()
+{
+  let promise = Promise::new(&mut |resolve, _| {
+    _ = window().set_timeout_with_callback(&resolve);
+  });
+  _ = JsFuture::from(promise).await;
+}
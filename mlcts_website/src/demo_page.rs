@@ -4,12 +4,21 @@ use leptos_meta::{Title, TitleProps};
 use mlcts_tokenizer::mlcts_core::Syllable;
 use mlcts_tokenizer::{tokenize, Token, TokenKind};
 
+use crate::chunked::convert_in_chunks;
+use crate::file_area::FileArea;
+use crate::i18n::{use_locale, Locale, Text};
+use crate::keyboard::{Keyboard, KeyboardProps};
+use crate::stats_panel::{Category, StatsPanel, StatsPanelProps};
+use crate::theme::{store_theme, use_theme, Theme};
+
 #[component]
 pub fn DemoPage() -> impl IntoView
 {
+  let locale = use_locale();
+
   (
     Title(TitleProps {
-      text: Some("LibMLCTS Demo".into()),
+      text: Some((move || locale.get().text(Text::Title)).into()),
       formatter: None,
     }),
     div()
@@ -21,20 +30,50 @@ pub fn DemoPage() -> impl IntoView
 #[component]
 fn MainArea() -> impl IntoView
 {
+  let locale = use_locale();
+  let theme = use_theme();
   let (input, set_input) = create_signal(String::new());
+  let textarea_ref = create_node_ref::<Textarea>();
+
+  let btn_cls = "border border-gray-300 dark:border-slate-600 rounded-md px-2";
+  let switch_locale = button()
+    .classes(btn_cls)
+    .classes("text-sm")
+    .child(move || locale.get().text(Text::SwitchLocale))
+    .on(ev::click, move |_| locale.update(|l| *l = l.toggle()));
+
+  let switch_theme = button()
+    .classes(btn_cls)
+    .attr("title", move || locale.get().text(Text::SwitchTheme))
+    .child(move || match theme.get()
+    {
+      Theme::Light => "\u{263E}",
+      Theme::Dark => "\u{2600}",
+    })
+    .on(ev::click, move |_| {
+      theme.update(|t| *t = t.toggle());
+      store_theme(theme.get_untracked());
+    });
 
   (
-    h1()
-      .classes("text-2xl font-bold mb-4")
-      .child("LibMLCTS Demo"),
+    div()
+      .classes("flex items-center justify-between mb-4")
+      .child((
+        h1()
+          .classes("text-2xl font-bold")
+          .child(move || locale.get().text(Text::Title)),
+        div()
+          .classes("flex gap-2")
+          .child((switch_locale, switch_theme)),
+      )),
     hr().class("mb-4", true),
     move || {
       if input.get().is_empty()
       {
         div()
           .classes("border border-slate-100 rounded-md p-4 bg-slate-100")
-          .classes("mb-4")
-          .child("Enter some text to see the tokens.")
+          .classes("dark:border-slate-800 dark:bg-slate-800 mb-4")
+          .child(move || locale.get().text(Text::EmptyInput))
           .into_view()
       }
       else
@@ -42,7 +81,16 @@ fn MainArea() -> impl IntoView
         OutputArea(OutputAreaProps { input }).into_view()
       }
     },
-    InputArea(InputAreaProps { input, set_input }),
+    InputArea(InputAreaProps {
+      input,
+      set_input,
+      textarea_ref,
+    }),
+    Keyboard(KeyboardProps {
+      set_input,
+      textarea: textarea_ref,
+    }),
+    FileArea(),
   )
 }
 
@@ -51,6 +99,8 @@ fn MainArea() -> impl IntoView
 /// # Arguments
 ///
 /// * `input` - The input signal.
+/// * `set_input` - The input setter.
+/// * `textarea_ref` - The reference to the textarea.
 ///
 /// # Returns
 ///
@@ -59,20 +109,27 @@ fn MainArea() -> impl IntoView
 fn InputArea(
   input: ReadSignal<String>,
   set_input: WriteSignal<String>,
+  textarea_ref: NodeRef<Textarea>,
 ) -> impl IntoView
 {
+  let locale = use_locale();
+
   let input_label = label()
-    .child("Input:")
+    .child(move || locale.get().text(Text::InputLabel))
     .attr("for", "text-input")
     .classes("text-lg block mb-2");
 
   let textarea = textarea()
     .id("text-input")
+    .node_ref(textarea_ref)
     .classes("w-full border border-gray-300 rounded-md p-4")
+    .classes("dark:border-slate-600 dark:bg-slate-800")
     .attr("rows", "5")
-    .attr("placeholder", "Enter Text Here")
+    .attr("placeholder", move || {
+      locale.get().text(Text::InputPlaceholder)
+    })
     .attr("spellcheck", "false")
-    .child(move || input.get())
+    .prop("value", move || input.get())
     .on(ev::input, move |e| {
       let value = event_target_value(&e);
       set_input.set(value);
@@ -81,7 +138,9 @@ fn InputArea(
   div().classes("mb-4").child((input_label, textarea))
 }
 
-/// The output area component.
+/// The output area component. Input in Myanmar script is romanized before
+/// it is tokenized, in chunks so a huge paste doesn't freeze the page. A
+/// conversion still running when the input changes is cancelled.
 ///
 /// # Arguments
 ///
@@ -93,15 +152,50 @@ fn InputArea(
 #[component]
 fn OutputArea(input: ReadSignal<String>) -> impl IntoView
 {
-  let (tokens, set_tokens) = create_signal(Vec::<Token>::new());
+  let locale = use_locale();
+  let selected = create_rw_signal(None::<Category>);
 
+  let is_myanmar = move || {
+    input.with(|i| i.contains(|c| matches!(c, '\u{1000}' ..= '\u{109F}')))
+  };
+  let (mlcts, set_mlcts) = create_signal(String::new());
+  let generation = store_value(0_usize);
   create_effect(move |_| {
     let text = input.get();
-    set_tokens(tokenize(&text).collect());
+    generation.update_value(|g| *g += 1);
+    if !is_myanmar()
+    {
+      set_mlcts.set(text);
+      return;
+    }
+
+    let current = generation.get_value();
+    spawn_local(async move {
+      let is_current = |_| generation.get_value() == current;
+      if let Some(converted) = convert_in_chunks(&text, is_current).await
+      {
+        set_mlcts.set(converted);
+      }
+    });
   });
 
+  let romanization = move || {
+    is_myanmar().then(|| {
+      div().classes("mb-4").child((
+        label()
+          .child(move || locale.get().text(Text::RomanizationLabel))
+          .attr("for", "romanization-output")
+          .classes("text-lg block mb-2"),
+        div()
+          .id("romanization-output")
+          .classes("font-mono break-words")
+          .child(move || mlcts.get()),
+      ))
+    })
+  };
+
   let label_elm = label()
-    .child("Tokens:")
+    .child(move || locale.get().text(Text::TokensLabel))
     .attr("for", "tokens-output")
     .classes("text-lg block mb-2");
 
@@ -109,37 +203,19 @@ fn OutputArea(input: ReadSignal<String>) -> impl IntoView
     .id("tokens-output")
     .classes("flex flex-wrap gap-2 items-stretch")
     .child(move || {
-      tokens
-        .get()
-        .into_iter()
-        .map(|token| token_view(token, input.get().clone()))
-        .collect::<Vec<_>>()
+      let (locale, selected) = (locale.get(), selected.get());
+      mlcts.with(|mlcts| {
+        tokenize(mlcts)
+          .map(|token| token_view(token, locale, selected))
+          .collect::<Vec<_>>()
+      })
     });
 
-  div().classes("mb-4").child((label_elm, tkn_box))
-}
-
-/// Get a substring of a string.
-///
-/// # Arguments
-///
-/// * `s` - The string to get the substring from.
-/// * `start` - The start index of the substring.
-/// * `length` - The length of the substring.
-///
-/// # Returns
-///
-/// The substring of the string.
-fn substring(s: &str, start: usize, length: usize) -> &str
-{
-  let end = s
-    .char_indices()
-    .nth(start + length)
-    .map_or_else(|| s.len(), |(idx, _)| idx);
-
-  let start = s.char_indices().nth(start).map_or(0, |(idx, _)| idx);
-
-  &s[start .. end]
+  (
+    romanization,
+    div().classes("mb-4").child((label_elm, tkn_box)),
+    StatsPanel(StatsPanelProps { mlcts, selected }),
+  )
 }
 
 /// Component to display a syllable token.
@@ -147,47 +223,63 @@ fn substring(s: &str, start: usize, length: usize) -> &str
 /// # Arguments
 ///
 /// * `syl` - The syllable to display.
+/// * `locale` - The locale of the labels.
+/// * `selected` - The highlighted category.
 ///
 /// # Returns
 ///
 /// The syllable token component.
-fn syllable_tkn(syl: Syllable) -> impl IntoView
+fn syllable_tkn(
+  syl: Syllable,
+  locale: Locale,
+  selected: Option<Category>,
+) -> impl IntoView
 {
+  let matched = selected.map(|category| category.matches(&syl));
+
   let cls = "px-2 border inline-block rounded-md text-white";
 
   let c = span()
     .classes(cls)
-    .class("bg-sky-500", true)
-    .child(format!("{:?}", syl.consonant.basic));
+    .classes("bg-sky-500 dark:bg-sky-700")
+    .attr("title", locale.text(Text::Consonant))
+    .child(locale.consonant(syl.consonant.basic));
 
   let m = syl.consonant.medial.map(|medial| {
     span()
       .classes(cls)
-      .class("bg-teal-500", true)
-      .child(format!("{:?}", medial))
+      .classes("bg-teal-500 dark:bg-teal-700")
+      .attr("title", locale.text(Text::Medial))
+      .child(locale.medial(medial))
   });
 
   let v = span()
     .classes(cls)
-    .class("bg-rose-500", true)
-    .child(format!("{:?}", syl.vowel.basic));
+    .classes("bg-rose-500 dark:bg-rose-700")
+    .attr("title", locale.text(Text::Vowel))
+    .child(locale.vowel(syl.vowel.basic));
 
   let vi = syl.vowel.virama.map(|virama| {
     span()
       .classes(cls)
-      .class("bg-amber-500", true)
-      .child(format!("{:?}", virama))
+      .classes("bg-amber-500 dark:bg-amber-700")
+      .attr("title", locale.text(Text::Virama))
+      .child(locale.virama(virama))
   });
 
   let t = syl.vowel.tone.map(|tone| {
     span()
       .classes(cls)
-      .class("bg-indigo-500", true)
-      .child(format!("{:?}", tone))
+      .classes("bg-indigo-500 dark:bg-indigo-700")
+      .attr("title", locale.text(Text::Tone))
+      .child(locale.tone(tone))
   });
 
   div()
     .classes("inline-flex items-center border border-stone-500 rounded-md p-1")
+    .class("ring-2", matched == Some(true))
+    .class("ring-yellow-400", matched == Some(true))
+    .class("opacity-30", matched == Some(false))
     .child((c, m, v, vi, t))
 }
 
@@ -196,27 +288,35 @@ fn syllable_tkn(syl: Syllable) -> impl IntoView
 /// # Arguments
 ///
 /// * `t` - The token to display.
-/// * `input` - The input string.
+/// * `locale` - The locale of the labels.
+/// * `selected` - The highlighted category.
 ///
 /// # Returns
 ///
 /// The token component.
-fn token_view(t: Token, input: String) -> impl IntoView
+fn token_view(
+  t: Token,
+  locale: Locale,
+  selected: Option<Category>,
+) -> impl IntoView
 {
-  let text = substring(input.as_str(), t.start, t.len).to_string();
+  let text = t.text.to_string();
   let common_class = "px-4 inline-flex items-center rounded-md";
 
   match t.kind
   {
-    TokenKind::Syllable(syl) => syllable_tkn(syl).into_view(),
+    TokenKind::Syllable(syl) => syllable_tkn(syl, locale, selected).into_view(),
     TokenKind::Unknown => div()
       .classes("bg-stone-600 text-white border border-stone-500")
       .classes(common_class)
+      .attr("title", locale.text(Text::Unknown))
       .child(text)
       .into_view(),
     TokenKind::Whitespace => div()
       .classes(common_class)
       .classes("bg-slate-50 text-slate-50 border border-stone-500 ")
+      .classes("dark:bg-slate-800 dark:text-slate-800")
+      .attr("title", locale.text(Text::Whitespace))
       .child("\u{00A0}")
       .into_view(),
     TokenKind::EndOfInput => unreachable!(),
@@ -0,0 +1,222 @@
+use std::time::Duration;
+
+use leptos::html::*;
+use leptos::*;
+use mlcts_tokenizer::{tokenize, TokenKind};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::Array;
+use web_sys::{Blob, BlobPropertyBag, HtmlAnchorElement, HtmlInputElement, Url};
+
+use crate::chunked::convert_in_chunks;
+use crate::i18n::{use_locale, Text};
+
+/// The time the URL of a downloaded file is kept before it is revoked.
+const REVOKE_DELAY: Duration = Duration::from_secs(10);
+
+/// A converted file.
+#[derive(Debug, Clone)]
+struct Converted
+{
+  name: String,
+  mlcts: String,
+}
+
+/// The file conversion component. A `.txt` file in Myanmar script is
+/// converted to MLCTS in chunks, then the result and its tokens can be
+/// downloaded. A conversion still running when another file is picked is
+/// cancelled.
+///
+/// # Returns
+///
+/// The file conversion component.
+#[component]
+pub fn FileArea() -> impl IntoView
+{
+  let locale = use_locale();
+  let (ratio, set_ratio) = create_signal(None::<f64>);
+  let (converted, set_converted) = create_signal(None::<Converted>);
+  let (read_error, set_read_error) = create_signal(false);
+  let generation = store_value(0_usize);
+
+  let file_label = label()
+    .child(move || locale.get().text(Text::FileLabel))
+    .attr("for", "file-input")
+    .classes("text-lg block mb-2");
+
+  let file_input = input()
+    .id("file-input")
+    .attr("type", "file")
+    .attr("accept", ".txt,text/plain")
+    .classes("block mb-2")
+    .on(ev::change, move |e| {
+      let target = event_target::<HtmlInputElement>(&e);
+      let Some(file) = target.files().and_then(|files| files.get(0))
+      else
+      {
+        return;
+      };
+
+      generation.update_value(|g| *g += 1);
+      let current = generation.get_value();
+      let is_current = move || generation.get_value() == current;
+      set_converted.set(None);
+      set_read_error.set(false);
+      set_ratio.set(Some(0.0));
+      spawn_local(async move {
+        let text = JsFuture::from(file.text())
+          .await
+          .ok()
+          .and_then(|text| text.as_string());
+        let Some(text) = text
+        else
+        {
+          if is_current()
+          {
+            set_ratio.set(None);
+            set_read_error.set(true);
+          }
+          return;
+        };
+        let mlcts = convert_in_chunks(&text, |ratio| {
+          if is_current()
+          {
+            set_ratio.set(Some(ratio));
+          }
+          is_current()
+        })
+        .await;
+        let Some(mlcts) = mlcts.filter(|_| is_current())
+        else
+        {
+          return;
+        };
+
+        set_ratio.set(None);
+        set_converted.set(Some(Converted {
+          name: file.name(),
+          mlcts,
+        }));
+      });
+    });
+
+  let progress_bar = move || {
+    ratio.get().map(|value| {
+      progress()
+        .classes("w-full mb-2")
+        .attr("max", "1")
+        .attr("value", value.to_string())
+    })
+  };
+
+  let error_message = move || {
+    read_error.get().then(|| {
+      p()
+        .classes("text-red-600 mb-2")
+        .child(move || locale.get().text(Text::FileReadError))
+    })
+  };
+
+  let downloads = move || {
+    converted.get().map(|converted| {
+      let btn_cls =
+        "border border-gray-300 dark:border-slate-600 rounded-md px-2";
+      let stem = converted.name.trim_end_matches(".txt").to_string();
+      let (mlcts, tokens) = (converted.mlcts.clone(), converted.mlcts);
+      let (mlcts_name, tokens_name) = (
+        format!("{}.mlcts.txt", stem),
+        format!("{}.tokens.json", stem),
+      );
+
+      div().classes("flex gap-2").child((
+        button()
+          .classes(btn_cls)
+          .child(move || locale.get().text(Text::DownloadMlcts))
+          .on(ev::click, move |_| {
+            download(&mlcts_name, "text/plain", &mlcts);
+          }),
+        button()
+          .classes(btn_cls)
+          .child(move || locale.get().text(Text::DownloadTokens))
+          .on(ev::click, move |_| {
+            download(&tokens_name, "application/json", &tokens_json(&tokens));
+          }),
+      ))
+    })
+  };
+
+  div().classes("mb-4").child((
+    file_label,
+    file_input,
+    progress_bar,
+    error_message,
+    downloads,
+  ))
+}
+
+/// Dump the tokens of MLCTS text as JSON.
+///
+/// # Arguments
+///
+/// * `mlcts` - The MLCTS text.
+///
+/// # Returns
+///
+/// A JSON array with an object for every token.
+fn tokens_json(mlcts: &str) -> String
+{
+  let tokens = tokenize(mlcts)
+    .map(|token| {
+      let (kind, syllable) = match token.kind
+      {
+        TokenKind::Syllable(syllable) => ("syllable", Some(syllable)),
+        TokenKind::Whitespace => ("whitespace", None),
+        _ => ("unknown", None),
+      };
+      serde_json::json!({
+        "kind": kind,
+        "start": token.start,
+        "len": token.len,
+        "text": token.text,
+        "syllable": syllable,
+      })
+    })
+    .collect::<Vec<_>>();
+
+  serde_json::to_string_pretty(&tokens).unwrap_or_default()
+}
+
+/// Let the browser download text as a file.
+///
+/// # Arguments
+///
+/// * `name` - The file name.
+/// * `mime` - The MIME type of the file.
+/// * `content` - The content of the file.
+fn download(name: &str, mime: &str, content: &str)
+{
+  let parts = Array::of1(&JsValue::from_str(content));
+  let options = BlobPropertyBag::new();
+  options.set_type(mime);
+  let Ok(blob) = Blob::new_with_str_sequence_and_options(&parts, &options)
+  else
+  {
+    return;
+  };
+  let Ok(url) = Url::create_object_url_with_blob(&blob)
+  else
+  {
+    return;
+  };
+
+  if let Ok(anchor) = document()
+    .create_element("a")
+    .map(|a| a.unchecked_into::<HtmlAnchorElement>())
+  {
+    anchor.set_href(&url);
+    anchor.set_download(name);
+    anchor.click();
+  }
+  // browsers may start the download after the click returns
+  set_timeout(move || _ = Url::revoke_object_url(&url), REVOKE_DELAY);
+}
@@ -0,0 +1,261 @@
+use leptos::*;
+use mlcts_tokenizer::mlcts_core::{BasicConsonant, BasicVowel, MedialDiacritic, Tone, Virama};
+
+/// The languages of the user interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale
+{
+  English,
+  Burmese,
+}
+
+/// The strings of the user interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text
+{
+  Title,
+  EmptyInput,
+  InputLabel,
+  InputPlaceholder,
+  TokensLabel,
+  RomanizationLabel,
+  KeyboardLabel,
+  StatsLabel,
+  FileLabel,
+  DownloadMlcts,
+  DownloadTokens,
+  FileReadError,
+  SwitchLocale,
+  SwitchTheme,
+  Consonant,
+  Medial,
+  Vowel,
+  Virama,
+  Tone,
+  Unknown,
+  Whitespace,
+}
+
+impl Locale
+{
+  /// Get the language tag of the locale.
+  ///
+  /// # Returns
+  ///
+  /// The BCP 47 language tag.
+  pub fn lang(self) -> &'static str
+  {
+    match self
+    {
+      Self::English => "en",
+      Self::Burmese => "my",
+    }
+  }
+
+  /// Get the other locale.
+  ///
+  /// # Returns
+  ///
+  /// The locale to switch to.
+  pub fn toggle(self) -> Self
+  {
+    match self
+    {
+      Self::English => Self::Burmese,
+      Self::Burmese => Self::English,
+    }
+  }
+
+  /// Get a string of the user interface.
+  ///
+  /// # Arguments
+  ///
+  /// * `text` - The string to get.
+  ///
+  /// # Returns
+  ///
+  /// The string in the locale.
+  pub fn text(self, text: Text) -> &'static str
+  {
+    match (self, text)
+    {
+      (Self::English, Text::Title) => "LibMLCTS Demo",
+      (Self::English, Text::EmptyInput) => "Enter some text to see the tokens.",
+      (Self::English, Text::InputLabel) => "Input:",
+      (Self::English, Text::InputPlaceholder) => "Enter Text Here",
+      (Self::English, Text::TokensLabel) => "Tokens:",
+      (Self::English, Text::RomanizationLabel) => "Romanization:",
+      (Self::English, Text::KeyboardLabel) => "Myanmar keyboard",
+      (Self::English, Text::StatsLabel) => "Statistics:",
+      (Self::English, Text::FileLabel) => "Convert a file:",
+      (Self::English, Text::DownloadMlcts) => "Download MLCTS",
+      (Self::English, Text::DownloadTokens) => "Download tokens (JSON)",
+      (Self::English, Text::FileReadError) => "The file could not be read.",
+      (Self::English, Text::SwitchLocale) => "မြန်မာ",
+      (Self::English, Text::SwitchTheme) => "Switch between light and dark",
+      (Self::English, Text::Consonant) => "Consonant",
+      (Self::English, Text::Medial) => "Medial",
+      (Self::English, Text::Vowel) => "Vowel",
+      (Self::English, Text::Virama) => "Virama",
+      (Self::English, Text::Tone) => "Tone",
+      (Self::English, Text::Unknown) => "Unknown",
+      (Self::English, Text::Whitespace) => "Whitespace",
+      (Self::Burmese, Text::Title) => "LibMLCTS နမူနာ",
+      (Self::Burmese, Text::EmptyInput) => "တိုကင်များကို ကြည့်ရန် စာသားရိုက်ထည့်ပါ။",
+      (Self::Burmese, Text::InputLabel) => "စာသား -",
+      (Self::Burmese, Text::InputPlaceholder) => "စာသားကို ဤနေရာတွင် ရိုက်ပါ",
+      (Self::Burmese, Text::TokensLabel) => "တိုကင်များ -",
+      (Self::Burmese, Text::RomanizationLabel) => "ရိုမန်အက္ခရာ -",
+      (Self::Burmese, Text::KeyboardLabel) => "မြန်မာ ကီးဘုတ်",
+      (Self::Burmese, Text::StatsLabel) => "စာရင်းအင်း -",
+      (Self::Burmese, Text::FileLabel) => "ဖိုင်ကို ပြောင်းရန် -",
+      (Self::Burmese, Text::DownloadMlcts) => "MLCTS ဒေါင်းလုဒ်လုပ်ရန်",
+      (Self::Burmese, Text::DownloadTokens) => "တိုကင်များ (JSON) ဒေါင်းလုဒ်လုပ်ရန်",
+      (Self::Burmese, Text::FileReadError) => "ဖိုင်ကို ဖတ်၍ မရပါ။",
+      (Self::Burmese, Text::SwitchLocale) => "English",
+      (Self::Burmese, Text::SwitchTheme) => "အလင်း၊ အမှောင် ပြောင်းရန်",
+      (Self::Burmese, Text::Consonant) => "ဗျည်း",
+      (Self::Burmese, Text::Medial) => "ဗျည်းတွဲ",
+      (Self::Burmese, Text::Vowel) => "သရ",
+      (Self::Burmese, Text::Virama) => "အသတ်",
+      (Self::Burmese, Text::Tone) => "အသံ",
+      (Self::Burmese, Text::Unknown) => "မသိ",
+      (Self::Burmese, Text::Whitespace) => "ကွက်လပ်",
+    }
+  }
+
+  /// Get the label of a consonant.
+  ///
+  /// # Arguments
+  ///
+  /// * `consonant` - The consonant.
+  ///
+  /// # Returns
+  ///
+  /// The enum variant name in English, the letter in Burmese.
+  pub fn consonant(self, consonant: BasicConsonant) -> String
+  {
+    match self
+    {
+      Self::English => format!("{:?}", consonant),
+      // the consonants are numbered after their code points
+      Self::Burmese => char::from_u32(0x1000 + consonant as u32)
+        .map(String::from)
+        .unwrap_or_default(),
+    }
+  }
+
+  /// Get the label of a medial diacritic.
+  ///
+  /// # Arguments
+  ///
+  /// * `medial` - The medial diacritic.
+  ///
+  /// # Returns
+  ///
+  /// The enum variant name in English, the diacritic names in Burmese.
+  pub fn medial(self, medial: MedialDiacritic) -> String
+  {
+    let names = match medial
+    {
+      MedialDiacritic::Y => "ယပင့်",
+      MedialDiacritic::R => "ရရစ်",
+      MedialDiacritic::W => "ဝဆွဲ",
+      MedialDiacritic::H => "ဟထိုး",
+      MedialDiacritic::Yw => "ယပင့် ဝဆွဲ",
+      MedialDiacritic::Rw => "ရရစ် ဝဆွဲ",
+      MedialDiacritic::Hy => "ယပင့် ဟထိုး",
+      MedialDiacritic::Hr => "ရရစ် ဟထိုး",
+      MedialDiacritic::Hw => "ဝဆွဲ ဟထိုး",
+      MedialDiacritic::Hyw => "ယပင့် ဝဆွဲ ဟထိုး",
+      MedialDiacritic::Hrw => "ရရစ် ဝဆွဲ ဟထိုး",
+    };
+    match self
+    {
+      Self::English => format!("{:?}", medial),
+      Self::Burmese => names.to_string(),
+    }
+  }
+
+  /// Get the label of a vowel.
+  ///
+  /// # Arguments
+  ///
+  /// * `vowel` - The vowel.
+  ///
+  /// # Returns
+  ///
+  /// The enum variant name in English, the vowel on အ in Burmese.
+  pub fn vowel(self, vowel: BasicVowel) -> String
+  {
+    let letters = match vowel
+    {
+      BasicVowel::A => "အ",
+      BasicVowel::I => "အိ",
+      BasicVowel::U => "အု",
+      BasicVowel::E => "အေ",
+      BasicVowel::Ei => "ဧ",
+      BasicVowel::Ai => "အဲ",
+      BasicVowel::Au => "အော",
+      BasicVowel::Ui => "အို",
+    };
+    match self
+    {
+      Self::English => format!("{:?}", vowel),
+      Self::Burmese => letters.to_string(),
+    }
+  }
+
+  /// Get the label of a virama.
+  ///
+  /// # Arguments
+  ///
+  /// * `virama` - The virama.
+  ///
+  /// # Returns
+  ///
+  /// The enum variant name in English, the killed consonant in Burmese.
+  pub fn virama(self, virama: Virama) -> String
+  {
+    match self
+    {
+      Self::English => format!("{:?}", virama),
+      Self::Burmese => format!("{}\u{103A}", self.consonant(virama.into())),
+    }
+  }
+
+  /// Get the label of a tone.
+  ///
+  /// # Arguments
+  ///
+  /// * `tone` - The tone.
+  ///
+  /// # Returns
+  ///
+  /// The enum variant name in English, the tone mark name in Burmese.
+  pub fn tone(self, tone: Tone) -> String
+  {
+    match (self, tone)
+    {
+      (Self::English, tone) => format!("{:?}", tone),
+      (Self::Burmese, Tone::High) => "ဝစ္စပေါက်".to_string(),
+      (Self::Burmese, Tone::Creaky) => "အောက်မြစ်".to_string(),
+    }
+  }
+}
+
+/// Provide the locale to the components, English by default.
+pub fn provide_locale()
+{
+  provide_context(create_rw_signal(Locale::English));
+}
+
+/// Get the locale provided by [`provide_locale`].
+///
+/// # Returns
+///
+/// The locale signal.
+pub fn use_locale() -> RwSignal<Locale>
+{
+  expect_context::<RwSignal<Locale>>()
+}
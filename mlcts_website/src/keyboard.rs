@@ -0,0 +1,132 @@
+use leptos::html::*;
+use leptos::*;
+
+use crate::i18n::{use_locale, Text};
+
+/// The keys of the keyboard, row by row.
+const ROWS: &[&[char]] = &[
+  // consonants
+  &['က', 'ခ', 'ဂ', 'ဃ', 'င', 'စ', 'ဆ', 'ဇ', 'ဈ', 'ည', 'ဉ'],
+  &['ဋ', 'ဌ', 'ဍ', 'ဎ', 'ဏ', 'တ', 'ထ', 'ဒ', 'ဓ', 'န'],
+  &[
+    'ပ', 'ဖ', 'ဗ', 'ဘ', 'မ', 'ယ', 'ရ', 'လ', 'ဝ', 'သ', 'ဟ', 'ဠ', 'အ',
+  ],
+  // medials
+  &['ျ', 'ြ', 'ွ', 'ှ'],
+  // vowel signs
+  &['ာ', 'ါ', 'ိ', 'ီ', 'ု', 'ူ', 'ေ', 'ဲ', 'ံ'],
+  // tones, asat and stacking
+  &['့', 'း', '်', '္'],
+  // punctuation
+  &['၊', '။'],
+];
+
+/// The on-screen Myanmar keyboard component. A key inserts its character
+/// into the textarea at the cursor.
+///
+/// # Arguments
+///
+/// * `set_input` - The input signal.
+/// * `textarea` - The textarea to insert into.
+///
+/// # Returns
+///
+/// The keyboard component.
+#[component]
+pub fn Keyboard(
+  set_input: WriteSignal<String>,
+  textarea: NodeRef<Textarea>,
+) -> impl IntoView
+{
+  let locale = use_locale();
+
+  let rows = ROWS.iter().map(|row| {
+    let keys = row.iter().map(|&c| {
+      // combining marks are shown on a dotted circle
+      let label = if is_mark(c)
+      {
+        format!("\u{25CC}{}", c)
+      }
+      else
+      {
+        c.to_string()
+      };
+
+      button()
+        .classes("border border-gray-300 dark:border-slate-600 rounded-md")
+        .classes("min-w-10 py-1 text-lg")
+        .attr("type", "button")
+        .child(label)
+        .on(ev::click, move |_| insert(c, set_input, textarea))
+    });
+
+    div()
+      .classes("flex flex-wrap gap-1 mb-1")
+      .child(keys.collect::<Vec<_>>())
+  });
+
+  details().classes("mb-4").child((
+    summary()
+      .classes("text-lg cursor-pointer mb-2")
+      .child(move || locale.get().text(Text::KeyboardLabel)),
+    rows.collect::<Vec<_>>(),
+  ))
+}
+
+/// Insert a character into the textarea at the cursor, replacing the
+/// selection.
+///
+/// # Arguments
+///
+/// * `c` - The character to insert.
+/// * `set_input` - The input signal.
+/// * `textarea` - The textarea to insert into.
+fn insert(c: char, set_input: WriteSignal<String>, textarea: NodeRef<Textarea>)
+{
+  let Some(textarea) = textarea.get()
+  else
+  {
+    return;
+  };
+
+  let mut value = textarea.value();
+  // the selection is in UTF-16 code units
+  let byte_index = |units: u32| {
+    let mut seen = 0;
+    value
+      .char_indices()
+      .find(|(_, c)| {
+        seen += c.len_utf16() as u32;
+        seen > units
+      })
+      .map_or(value.len(), |(i, _)| i)
+  };
+  let start = textarea.selection_start().ok().flatten().map(byte_index);
+  let end = textarea.selection_end().ok().flatten().map(byte_index);
+  let (start, end) = match (start, end)
+  {
+    (Some(start), Some(end)) if start <= end => (start, end),
+    _ => (value.len(), value.len()),
+  };
+  let cursor = value[.. start].encode_utf16().count() as u32 + 1;
+
+  value.replace_range(start .. end, &c.to_string());
+  set_input.set(value);
+
+  _ = textarea.focus();
+  _ = textarea.set_selection_range(cursor, cursor);
+}
+
+/// Check if a character is a combining mark.
+///
+/// # Arguments
+///
+/// * `c` - The character to check.
+///
+/// # Returns
+///
+/// `true` if the character is written on a consonant, `false` otherwise.
+fn is_mark(c: char) -> bool
+{
+  matches!(c, '\u{102B}' ..= '\u{103E}')
+}
@@ -2,19 +2,35 @@ use leptos::*;
 use leptos_meta::{provide_meta_context as use_ctx, *};
 use leptos_router::*;
 
+mod bindings;
+mod chunked;
 mod demo_page;
+mod file_area;
+mod i18n;
+mod keyboard;
+mod stats_panel;
+mod theme;
 
 use demo_page::DemoPage;
+use i18n::{provide_locale, use_locale};
+use theme::{provide_theme, use_theme};
 
 #[component]
 pub fn App() -> impl IntoView
 {
   use_ctx();
+  provide_locale();
+  provide_theme();
 
+  let locale = use_locale();
+  let theme = use_theme();
   let html_props = HtmlProps::builder()
-    .lang("en")
+    .lang(move || locale.get().lang())
     .dir("ltr")
-    .attributes(vec![("data-theme", "light".into_attribute())])
+    .attributes(vec![(
+      "data-theme",
+      (move || theme.get().name()).into_attribute(),
+    )])
     .build();
 
   (Html(html_props), create_router())
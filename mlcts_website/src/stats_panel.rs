@@ -0,0 +1,183 @@
+use leptos::html::*;
+use leptos::*;
+use mlcts_tokenizer::mlcts_core::{BasicConsonant, BasicVowel, Syllable, Tone};
+use mlcts_tokenizer::{tokenize, TokenKind};
+
+use crate::i18n::{use_locale, Locale, Text};
+
+/// A category of syllables counted in the panel.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Category
+{
+  Consonant(BasicConsonant),
+  Vowel(BasicVowel),
+  Tone(Tone),
+}
+
+impl Category
+{
+  /// Check if a syllable belongs to the category.
+  ///
+  /// # Arguments
+  ///
+  /// * `syl` - The syllable to check.
+  ///
+  /// # Returns
+  ///
+  /// `true` if the syllable belongs to the category, `false` otherwise.
+  pub fn matches(self, syl: &Syllable) -> bool
+  {
+    match self
+    {
+      Self::Consonant(c) => syl.consonant.basic == c,
+      Self::Vowel(v) => syl.vowel.basic == v,
+      Self::Tone(t) => syl.vowel.tone == Some(t),
+    }
+  }
+
+  /// Get the group of the category.
+  ///
+  /// # Returns
+  ///
+  /// 0 for consonants, 1 for vowels and 2 for tones.
+  fn group(self) -> usize
+  {
+    match self
+    {
+      Self::Consonant(_) => 0,
+      Self::Vowel(_) => 1,
+      Self::Tone(_) => 2,
+    }
+  }
+
+  /// Get the label of the category.
+  ///
+  /// # Arguments
+  ///
+  /// * `locale` - The locale of the label.
+  ///
+  /// # Returns
+  ///
+  /// The label of the category.
+  fn label(self, locale: Locale) -> String
+  {
+    match self
+    {
+      Self::Consonant(c) => locale.consonant(c),
+      Self::Vowel(v) => locale.vowel(v),
+      Self::Tone(t) => locale.tone(t),
+    }
+  }
+}
+
+/// Count the syllables of every category.
+///
+/// # Arguments
+///
+/// * `mlcts` - The MLCTS text to count.
+///
+/// # Returns
+///
+/// The categories with their counts, the most frequent first within the
+/// consonants, the vowels and the tones.
+fn count(mlcts: &str) -> Vec<(Category, usize)>
+{
+  let mut counts = Vec::<(Category, usize)>::new();
+  let mut add =
+    |category: Category| match counts.iter_mut().find(|(c, _)| *c == category)
+    {
+      Some((_, n)) => *n += 1,
+      None => counts.push((category, 1)),
+    };
+
+  for token in tokenize(mlcts)
+  {
+    if let TokenKind::Syllable(syl) = &token.kind
+    {
+      add(Category::Consonant(syl.consonant.basic));
+      add(Category::Vowel(syl.vowel.basic));
+      if let Some(tone) = syl.vowel.tone
+      {
+        add(Category::Tone(tone));
+      }
+    }
+  }
+
+  counts.sort_by_key(|(category, n)| (category.group(), std::cmp::Reverse(*n)));
+  counts
+}
+
+/// The statistics panel component. Clicking a category highlights the
+/// syllables belonging to it, clicking it again clears the highlight.
+///
+/// # Arguments
+///
+/// * `mlcts` - The MLCTS text.
+/// * `selected` - The highlighted category.
+///
+/// # Returns
+///
+/// The statistics panel component.
+#[component]
+pub fn StatsPanel(
+  mlcts: ReadSignal<String>,
+  selected: RwSignal<Option<Category>>,
+) -> impl IntoView
+{
+  let locale = use_locale();
+
+  let groups = move || {
+    let counts = mlcts.with(|mlcts| count(mlcts));
+    let locale = locale.get();
+
+    [
+      (Text::Consonant, "bg-sky-500 dark:bg-sky-700"),
+      (Text::Vowel, "bg-rose-500 dark:bg-rose-700"),
+      (Text::Tone, "bg-indigo-500 dark:bg-indigo-700"),
+    ]
+    .into_iter()
+    .enumerate()
+    .map(|(group, (heading, color))| {
+      let chips = counts
+        .iter()
+        .filter(|(category, _)| category.group() == group)
+        .map(|&(category, n)| {
+          let is_selected = move || selected.get() == Some(category);
+          button()
+            .classes("px-2 rounded-md text-white")
+            .classes(color)
+            .class("ring-2", is_selected)
+            .class("ring-yellow-400", is_selected)
+            .attr("type", "button")
+            .child(format!("{} {}", category.label(locale), n))
+            .on(ev::click, move |_| {
+              selected.update(|s| {
+                *s = if *s == Some(category)
+                {
+                  None
+                }
+                else
+                {
+                  Some(category)
+                }
+              })
+            })
+        })
+        .collect::<Vec<_>>();
+
+      div().classes("mb-2").child((
+        div().classes("text-sm mb-1").child(locale.text(heading)),
+        div().classes("flex flex-wrap gap-1").child(chips),
+      ))
+    })
+    .collect::<Vec<_>>()
+  };
+
+  div().classes("mb-4").child((
+    label()
+      .child(move || locale.get().text(Text::StatsLabel))
+      .attr("for", "stats-output")
+      .classes("text-lg block mb-2"),
+    div().id("stats-output").child(groups),
+  ))
+}
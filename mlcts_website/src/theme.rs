@@ -0,0 +1,112 @@
+use leptos::*;
+
+/// The key of the theme in the local storage.
+const STORAGE_KEY: &str = "theme";
+
+/// The color themes of the user interface.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Theme
+{
+  Light,
+  Dark,
+}
+
+impl Theme
+{
+  /// Get the name of the theme, used as the `data-theme` attribute.
+  ///
+  /// # Returns
+  ///
+  /// The name of the theme.
+  pub fn name(self) -> &'static str
+  {
+    match self
+    {
+      Self::Light => "light",
+      Self::Dark => "dark",
+    }
+  }
+
+  /// Get the other theme.
+  ///
+  /// # Returns
+  ///
+  /// The theme to switch to.
+  pub fn toggle(self) -> Self
+  {
+    match self
+    {
+      Self::Light => Self::Dark,
+      Self::Dark => Self::Light,
+    }
+  }
+}
+
+/// Provide the theme to the components. The theme is the one stored in the
+/// local storage, or the system preference if none is stored. The theme is
+/// only stored by [`store_theme`] when the user switches it, so the system
+/// preference is followed until then.
+pub fn provide_theme()
+{
+  provide_context(create_rw_signal(initial_theme()));
+}
+
+/// Store the theme chosen by the user in the local storage.
+///
+/// # Arguments
+///
+/// * `theme` - The theme to store.
+pub fn store_theme(theme: Theme)
+{
+  if let Some(storage) = window().local_storage().ok().flatten()
+  {
+    _ = storage.set_item(STORAGE_KEY, theme.name());
+  }
+}
+
+/// Get the theme provided by [`provide_theme`].
+///
+/// # Returns
+///
+/// The theme signal.
+pub fn use_theme() -> RwSignal<Theme>
+{
+  expect_context::<RwSignal<Theme>>()
+}
+
+/// Get the theme to start with.
+///
+/// # Returns
+///
+/// The stored theme, otherwise the dark theme if the system prefers it,
+/// otherwise the light theme.
+fn initial_theme() -> Theme
+{
+  let stored = window()
+    .local_storage()
+    .ok()
+    .flatten()
+    .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten());
+
+  match stored.as_deref()
+  {
+    Some("dark") => Theme::Dark,
+    Some("light") => Theme::Light,
+    _ =>
+    {
+      let prefers_dark = window()
+        .match_media("(prefers-color-scheme: dark)")
+        .ok()
+        .flatten()
+        .is_some_and(|query| query.matches());
+      if prefers_dark
+      {
+        Theme::Dark
+      }
+      else
+      {
+        Theme::Light
+      }
+    }
+  }
+}